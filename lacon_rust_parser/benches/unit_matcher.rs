@@ -0,0 +1,53 @@
+// Сравнение ДКА из build.rs (`UNITS_MATCHER`) с префиксным деревом,
+// которое строилось при первом обращении (`build_unit_tree`):
+//
+//     cargo bench --bench unit_matcher
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use lacon_rust_parser::lexer::scanner::Scanner;
+use lacon_rust_parser::shared::unit::units::{UNITS, UNITS_MATCHER, build_unit_tree};
+
+// Суффиксы с приставками, составные и с хвостом, который не входит в единицу
+const SUFFIXES: &[&str] = &[
+    "kg/m3", "MiB/s", "μs", "ft/s", "dBm", "kΩ", "°C", "mm;", "GHz)", "cd·sr", "nmol", "px",
+];
+
+fn unit_dense_source() -> String {
+    (0..2_000)
+        .map(|i| format!("let a{} = {}{}\n", i, i, SUFFIXES[i % SUFFIXES.len()]))
+        .collect()
+}
+
+fn matcher(c: &mut Criterion) {
+    let inputs: Vec<Vec<char>> = SUFFIXES.iter().map(|s| s.chars().collect()).collect();
+
+    c.bench_function("tree/build", |b| {
+        b.iter(|| build_unit_tree(black_box(UNITS)))
+    });
+
+    let tree = build_unit_tree(UNITS);
+    c.bench_function("tree/longest_match", |b| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .map(|input| tree.longest_match(black_box(input)))
+                .sum::<usize>()
+        })
+    });
+    c.bench_function("matcher/longest_match", |b| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .map(|input| UNITS_MATCHER.longest_match(black_box(input)))
+                .sum::<usize>()
+        })
+    });
+
+    let source = unit_dense_source();
+    c.bench_function("scanner/unit_dense", |b| {
+        b.iter(|| Scanner::new(black_box(source.clone())).scan_tokens().len())
+    });
+}
+
+criterion_group!(benches, matcher);
+criterion_main!(benches);
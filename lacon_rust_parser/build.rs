@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/shared/unit/loader.rs"]
+mod loader;
+
+use loader::{Definitions, FormulaSpec};
+
+const UNITS_DEF: &str = "src/shared/unit/units.def";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", UNITS_DEF);
+    println!("cargo:rerun-if-changed=src/shared/unit/loader.rs");
+
+    let text = fs::read_to_string(UNITS_DEF).expect("units.def is missing");
+    let defs = loader::parse(&text).unwrap_or_else(|e| panic!("{}:{}", UNITS_DEF, e));
+    defs.validate()
+        .unwrap_or_else(|e| panic!("{}:{}", UNITS_DEF, e));
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("prefixes.rs"),
+        generate_prefixes(&defs),
+    )
+    .unwrap();
+    fs::write(Path::new(&out_dir).join("units.rs"), generate_units(&defs)).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("units_matcher.rs"),
+        generate_matcher(&defs),
+    )
+    .unwrap();
+}
+
+fn generate_prefixes(defs: &Definitions) -> String {
+    let mut out = String::from("pub static PREFIXES: &[(&str, f64, PrefixGroup)] = &[\n");
+    for p in &defs.prefixes {
+        writeln!(
+            out,
+            "    ({:?}, {:?}, PrefixGroup::{}),",
+            p.symbol, p.factor, p.group
+        )
+        .unwrap();
+    }
+    out.push_str("];\n");
+    out
+}
+
+fn generate_units(defs: &Definitions) -> String {
+    let dims = |list: &[String]| -> String {
+        list.iter()
+            .map(|d| format!("Dimension::{}", d))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    // Множители вычисляются из выражений (2*pi), clippy узнает в них константы
+    let mut out =
+        String::from("#[allow(clippy::approx_constant)]\npub static UNITS: &[UnitDef] = &[\n");
+    for u in &defs.units {
+        let parts = match &u.parts {
+            Some((n, d)) => format!("Some(({:?}, {:?}))", n, d),
+            None => "None".to_string(),
+        };
+        let formula = match &u.formula {
+            FormulaSpec::None => "Formula::None".to_string(),
+            FormulaSpec::Simple(d) => format!("Formula::Simple(Dimension::{})", d),
+            FormulaSpec::Complex { num, den } => format!(
+                "Formula::Complex {{ num: Cow::Borrowed(&[{}]), den: Cow::Borrowed(&[{}]) }}",
+                dims(num),
+                dims(den)
+            ),
+        };
+
+        writeln!(
+            out,
+            "    UnitDef::new({:?}, Dimension::{}, {}, PrefixGroup::{}, PrefixGroup::{}, \
+             UnitProps {{ scale: {:?}, offset: {:?}, exponent: {:?}, mode: CalcMode::{}, \
+             basis: LayoutBasis::{}, formula: {} }}),",
+            u.symbol,
+            u.dimension,
+            parts,
+            u.numerator_group,
+            u.denominator_group,
+            u.scale,
+            u.offset,
+            u.exponent,
+            u.mode,
+            u.basis,
+            formula
+        )
+        .unwrap();
+    }
+    out.push_str("];\n");
+
+    out.push_str("\npub static AMBIGUOUS_UNITS: &[(&str, &[&str])] = &[\n");
+    for u in defs.units.iter().filter(|u| !u.ambiguous.is_empty()) {
+        writeln!(out, "    ({:?}, &{:?}),", u.symbol, u.ambiguous).unwrap();
+    }
+    out.push_str("];\n");
+    out
+}
+
+#[derive(Default)]
+struct TrieNode {
+    is_final: bool,
+    children: BTreeMap<char, TrieNode>,
+}
+
+/// Состояние ДКА: финальность и переходы, отсортированные по символу
+type State = (bool, Vec<(char, u32)>);
+
+/// Минимальный ДКА по всем формам встроенных единиц (включая приставки).
+/// Одинаковые поддеревья префиксного дерева сливаются в одно состояние.
+fn generate_matcher(defs: &Definitions) -> String {
+    let mut root = TrieNode::default();
+    for unit in &defs.units {
+        for form in defs.forms(unit) {
+            let mut node = &mut root;
+            for ch in form.chars() {
+                node = node.children.entry(ch).or_default();
+            }
+            node.is_final = true;
+        }
+    }
+
+    let mut states: Vec<State> = Vec::new();
+    let mut index: HashMap<State, u32> = HashMap::new();
+    let root = minimize(&root, &mut states, &mut index);
+
+    let mut finals = Vec::new();
+    let mut offsets = vec![0u32];
+    let mut labels = Vec::new();
+    let mut targets = Vec::new();
+    for (is_final, edges) in &states {
+        finals.push(is_final.to_string());
+        for (ch, target) in edges {
+            labels.push(format!("{:?}", ch));
+            targets.push(target.to_string());
+        }
+        offsets.push(labels.len() as u32);
+    }
+    let offsets: Vec<String> = offsets.iter().map(u32::to_string).collect();
+
+    let list = |items: &[String]| -> String {
+        items
+            .chunks(16)
+            .map(|chunk| format!("        {},\n", chunk.join(", ")))
+            .collect()
+    };
+
+    format!(
+        "pub static UNITS_MATCHER: UnitMatcher = UnitMatcher {{\n    \
+         root: {},\n    \
+         finals: &[\n{}    ],\n    \
+         offsets: &[\n{}    ],\n    \
+         labels: &[\n{}    ],\n    \
+         targets: &[\n{}    ],\n}};\n",
+        root,
+        list(&finals),
+        list(&offsets),
+        list(&labels),
+        list(&targets)
+    )
+}
+
+fn minimize(node: &TrieNode, states: &mut Vec<State>, index: &mut HashMap<State, u32>) -> u32 {
+    let edges = node
+        .children
+        .iter()
+        .map(|(&ch, child)| (ch, minimize(child, states, index)))
+        .collect();
+    let state = (node.is_final, edges);
+
+    if let Some(&id) = index.get(&state) {
+        return id;
+    }
+    let id = states.len() as u32;
+    states.push(state.clone());
+    index.insert(state, id);
+    id
+}
@@ -0,0 +1,24 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,           // +
+    Subtract,      // -
+    Monus,         // ∸ .-
+    Multiply,      // *
+    Divide,        // /
+    IntegerDivide, // //
+    Modulo,        // %
+    Power,         // ^
+    Tolerance,     // ± ∓
+    Range,         // ..
+    Equal,         // ==
+    NotEqual,      // !=
+    StrictEqual,   // ≣ ===
+    Matches,       // ~=
+    Less,          // <
+    LessEqual,     // <=
+    Greater,       // >
+    GreaterEqual,  // >=
+    And,           // and &&
+    Or,            // or ||
+    Coalesce,      // ??
+}
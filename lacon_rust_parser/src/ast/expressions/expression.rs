@@ -0,0 +1,86 @@
+use super::binary::BinaryOperator;
+use super::identifier::{Name, Segment};
+use super::unary::UnaryOperator;
+use crate::ast::statements::Block;
+use crate::utils::span::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+/// Ключевые слова, ссылающиеся на части документа относительно текущего места
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextKeyword {
+    This,
+    Parent,
+    Root,
+    Origin,
+    Super,
+    Here,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind {
+    /// `42`, `0x4E3`, `Infinity`
+    Number(f64),
+    /// `25kg`, `45°`: число и суффикс как записан (с приставкой)
+    Unit {
+        value: f64,
+        suffix: String,
+    },
+    /// Строка любого вида; вставки `${...}` — отдельные части
+    String(Vec<Segment>),
+    /// `#dbebed`
+    Color(String),
+    Boolean(bool),
+    Nil,
+    None,
+    Undefined,
+    Identifier(Name),
+    Context(ContextKeyword),
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    Call {
+        callee: Box<Expression>,
+        arguments: Vec<Expression>,
+    },
+    /// `a.b`; `path` — запись через `::` (`Align::center`)
+    Member {
+        object: Box<Expression>,
+        member: String,
+        path: bool,
+    },
+    Index {
+        object: Box<Expression>,
+        index: Box<Expression>,
+    },
+    Array(Vec<Expression>),
+    /// `(a, b)` — используется генераторами `spread (a, b) as let (x, y)`
+    Tuple(Vec<Expression>),
+    Dictionary(Block),
+    /// Пропущенный элемент массива: `[value, , value]`
+    Hole,
+}
+
+impl Expression {
+    pub fn new(kind: ExpressionKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// Имя, если выражение — простой идентификатор без вставок
+    pub fn as_identifier(&self) -> Option<&str> {
+        match &self.kind {
+            ExpressionKind::Identifier(name) => name.as_static(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use super::expression::Expression;
+use crate::utils::span::Span;
+
+/// Часть имени или строки: текст как записан либо вставка `${...}`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Interpolation(Expression),
+}
+
+/// Имя ключа или переменной. Может собираться из частей:
+/// `unicode-${code-point}` — текст `unicode-` и вставка `code-point`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Name {
+    pub segments: Vec<Segment>,
+    pub span: Span,
+}
+
+impl Name {
+    pub fn new(text: impl Into<String>, span: Span) -> Self {
+        Self {
+            segments: vec![Segment::Text(text.into())],
+            span,
+        }
+    }
+
+    /// Имя без вставок известно до вычисления; с вставками — только во время него
+    pub fn as_static(&self) -> Option<&str> {
+        match self.segments.as_slice() {
+            [Segment::Text(text)] => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Вставки имени, н-р, для разрешения `${code-point}`
+    pub fn interpolations(&self) -> impl Iterator<Item = &Expression> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Interpolation(expression) => Some(expression),
+            Segment::Text(_) => None,
+        })
+    }
+}
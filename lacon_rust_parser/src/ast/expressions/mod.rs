@@ -0,0 +1,9 @@
+pub mod binary;
+pub mod expression;
+pub mod identifier;
+pub mod unary;
+
+pub use binary::BinaryOperator;
+pub use expression::{ContextKeyword, Expression, ExpressionKind};
+pub use identifier::{Name, Segment};
+pub use unary::UnaryOperator;
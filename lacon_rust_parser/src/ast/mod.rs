@@ -1,7 +1,10 @@
 pub mod expressions;
 pub mod node;
 pub mod program;
+pub mod statements;
+pub mod types;
 
 pub use expressions::*;
-pub use node::*;
 pub use program::*;
+pub use statements::*;
+pub use types::*;
@@ -0,0 +1,7 @@
+use super::statements::Block;
+
+/// Разобранный документ
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub body: Block,
+}
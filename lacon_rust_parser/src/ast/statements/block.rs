@@ -0,0 +1,10 @@
+use super::statement::Statement;
+use crate::utils::span::Span;
+
+/// Тело документа, контейнера, словаря или генератора — в фигурных скобках
+/// или отступом
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub statements: Vec<Statement>,
+    pub span: Span,
+}
@@ -0,0 +1,43 @@
+use super::variable_decl::Modifiers;
+use crate::ast::expressions::{Expression, Name};
+use crate::utils::span::Span;
+
+/// `enum Align { start, center, end }`, `enum Gap { small = 4px, large = 16px }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDecl {
+    pub modifiers: Modifiers,
+    pub name: Name,
+    pub members: Vec<EnumMember>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumMember {
+    pub name: Name,
+    pub value: Option<EnumValue>,
+    pub span: Span,
+}
+
+/// Явное значение члена
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnumValue {
+    /// `small = 4px`, `title = "Title"`
+    Expression(Expression),
+    /// `left = U+2190`
+    CodePoint(char),
+}
+
+impl EnumDecl {
+    pub fn member(&self, name: &str) -> Option<&EnumMember> {
+        self.members
+            .iter()
+            .find(|member| member.name.as_static() == Some(name))
+    }
+
+    /// Имена членов в порядке объявления
+    pub fn member_names(&self) -> Vec<&str> {
+        self.members
+            .iter()
+            .filter_map(|member| member.name.as_static())
+            .collect()
+    }
+}
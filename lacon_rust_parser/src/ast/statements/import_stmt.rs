@@ -0,0 +1,9 @@
+use crate::ast::expressions::{Expression, Name};
+
+/// `import primary, accent from "colors.lacon"` — имена из другого файла;
+/// `import "base.lacon"` — все экспортируемые ключи файла в документ
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportDecl {
+    pub names: Vec<Name>,
+    pub source: Expression,
+}
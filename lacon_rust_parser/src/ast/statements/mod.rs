@@ -0,0 +1,16 @@
+pub mod block;
+pub mod enum_decl;
+pub mod import_stmt;
+pub mod statement;
+pub mod type_alias;
+pub mod variable_decl;
+
+pub use block::Block;
+pub use enum_decl::{EnumDecl, EnumMember, EnumValue};
+pub use import_stmt::ImportDecl;
+pub use statement::{
+    AssignOperator, Assignment, Binding, ContainerDecl, Generator, GeneratorKind, Property,
+    Separator, Statement, StatementKind,
+};
+pub use type_alias::{TypeAlias, TypeAliasKind};
+pub use variable_decl::{DeclarationKind, Modifiers, VariableDecl};
@@ -0,0 +1,114 @@
+use super::block::Block;
+use super::enum_decl::EnumDecl;
+use super::import_stmt::ImportDecl;
+use super::type_alias::TypeAlias;
+use super::variable_decl::{DeclarationKind, Modifiers, VariableDecl};
+use crate::ast::expressions::{Expression, Name};
+use crate::ast::types::{ObjectType, TypeAnnotation};
+use crate::utils::span::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementKind {
+    Variable(VariableDecl),
+    Property(Property),
+    Assignment(Assignment),
+    Container(ContainerDecl),
+    Generator(Generator),
+    /// `yield digit-${name} { ... }` — ключ, добавляемый генератором
+    Yield(Property),
+    /// `struct Button { ... }`, `interface Sized { ... }`
+    Schema(ObjectType),
+    Enum(EnumDecl),
+    /// `type Dim = Length | Percent`, `alias Px = Length`
+    TypeAlias(TypeAlias),
+    Import(ImportDecl),
+    Expression(Expression),
+}
+
+/// Как значение отделено от ключа
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    Colon,      // key: value
+    Equal,      // key = value
+    Whitespace, // key value
+    Block,      // key { ... } или key + блок отступом
+    None,       // key — ключ без значения
+}
+
+/// Ключ словаря: `key: value`, `key = value`, `key value`, `key { ... }`.
+/// Аннотация задает схему значения: `ok<Button> { ... }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub modifiers: Modifiers,
+    pub name: Name,
+    pub annotation: Option<TypeAnnotation>,
+    pub separator: Separator,
+    pub value: Option<Expression>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignOperator {
+    Add,           // +=
+    Subtract,      // -=
+    Multiply,      // *=
+    Divide,        // /=
+    Modulo,        // %=
+    IntegerDivide, // //=
+    Append,        // .=
+    ShiftLeft,     // <<=
+    ShiftRight,    // >>=
+    Increment,     // ++
+    Decrement,     // --
+    Push,          // array + item
+    Set,           // dictionary > key value
+}
+
+/// Изменение существующего значения: `count += 1`, `count++`, `array + item`,
+/// `dictionary > key value`. У `++`/`--` значения нет.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub target: Name,
+    /// Вложенные ключи после `>`: `parent > child > key value` -> [child, key]
+    pub path: Vec<Name>,
+    pub operator: AssignOperator,
+    pub value: Option<Expression>,
+}
+
+/// `container App` + тело
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerDecl {
+    pub modifiers: Modifiers,
+    pub name: Name,
+    pub body: Block,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorKind {
+    Spread,
+    Generate,
+}
+
+/// Имена, под которыми тело генератора видит очередной элемент:
+/// `as local let code-point`, `as let (letter-case, suffix)`, `as name`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub kind: DeclarationKind,
+    pub modifiers: Modifiers,
+    pub names: Vec<Name>,
+    pub span: Span,
+}
+
+/// `spread [...] as let name { ... }`, `generate (0x4E3 .. -10) as ... { ... }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub kind: GeneratorKind,
+    pub source: Expression,
+    pub binding: Option<Binding>,
+    pub body: Block,
+}
@@ -0,0 +1,19 @@
+use super::variable_decl::Modifiers;
+use crate::ast::expressions::Name;
+use crate::ast::types::TypeAnnotation;
+
+/// Ключевое слово объявления; по смыслу `type` и `alias` не различаются
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeAliasKind {
+    Type,  // type Dim = Length | Percent
+    Alias, // alias Px = Length
+}
+
+/// Другое имя для типа аннотации: `type Dim = Length | Percent`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+    pub kind: TypeAliasKind,
+    pub modifiers: Modifiers,
+    pub name: Name,
+    pub annotation: TypeAnnotation,
+}
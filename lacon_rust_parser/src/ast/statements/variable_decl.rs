@@ -0,0 +1,61 @@
+use crate::ast::expressions::{Expression, Name};
+use crate::ast::types::TypeAnnotation;
+use crate::lexer::token_type::TokenType;
+use bitflags::bitflags;
+
+bitflags! {
+    /// Модификаторы перед объявлением: `global const`, `private key`, `as local let`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Modifiers: u16 {
+        const LOCAL = 1 << 0;
+        const GLOBAL = 1 << 1;
+        const FINAL = 1 << 2;
+        const STATIC = 1 << 3;
+        const PUBLIC = 1 << 4;
+        const PRIVATE = 1 << 5;
+        const PROTECTED = 1 << 6;
+        const INTERNAL = 1 << 7;
+        const EXTERNAL = 1 << 8;
+    }
+}
+
+impl Modifiers {
+    pub fn from_token(token_type: &TokenType) -> Option<Self> {
+        let modifier = match token_type {
+            TokenType::Local => Modifiers::LOCAL,
+            TokenType::Global => Modifiers::GLOBAL,
+            TokenType::Final => Modifiers::FINAL,
+            TokenType::Static => Modifiers::STATIC,
+            TokenType::Public => Modifiers::PUBLIC,
+            TokenType::Private => Modifiers::PRIVATE,
+            TokenType::Protected => Modifiers::PROTECTED,
+            TokenType::Internal => Modifiers::INTERNAL,
+            TokenType::External => Modifiers::EXTERNAL,
+            _ => return None,
+        };
+        Some(modifier)
+    }
+
+    /// Модификаторы, которые сами по себе делают запись переменной:
+    /// `local code-point = ...` равносильно `local let code-point = ...`
+    pub fn declares_binding(self) -> bool {
+        self.intersects(Modifiers::LOCAL | Modifiers::GLOBAL | Modifiers::FINAL | Modifiers::STATIC)
+    }
+}
+
+/// `let`/`var`/`variable` или `const`/`constant`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationKind {
+    Variable,
+    Constant,
+}
+
+/// `let speed<Speed> = 278ft/s`, `const first-word snow`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDecl {
+    pub kind: DeclarationKind,
+    pub modifiers: Modifiers,
+    pub name: Name,
+    pub annotation: Option<TypeAnnotation>,
+    pub value: Option<Expression>,
+}
@@ -0,0 +1,5 @@
+pub mod objects;
+pub mod type_annotation;
+
+pub use objects::{FieldType, ObjectKind, ObjectType};
+pub use type_annotation::{TypeAnnotation, TypeKind};
@@ -0,0 +1,3 @@
+pub mod object_type;
+
+pub use object_type::{FieldType, ObjectKind, ObjectType};
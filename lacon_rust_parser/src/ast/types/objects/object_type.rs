@@ -0,0 +1,45 @@
+use crate::ast::expressions::{Expression, Name};
+use crate::ast::types::TypeAnnotation;
+use crate::utils::span::Span;
+
+/// `struct` описывает документ целиком, `interface` — набор полей,
+/// который struct обязуется объявить (`implements`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Struct,
+    Interface,
+}
+
+/// Схема словаря:
+/// `struct Button extends Control implements Sized { label<String> ... }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectType {
+    pub kind: ObjectKind,
+    pub name: Name,
+    pub extends: Vec<TypeAnnotation>,
+    pub implements: Vec<TypeAnnotation>,
+    pub fields: Vec<FieldType>,
+    pub span: Span,
+}
+
+/// Поле схемы: `width<Length> = 120px`, `icon?<String>`,
+/// `align<String> in [start, center, end] = start`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldType {
+    pub name: Name,
+    /// `?` после имени: поле можно не указывать
+    pub optional: bool,
+    /// Без аннотации подходит значение любого типа
+    pub annotation: Option<TypeAnnotation>,
+    /// Допустимые значения после `in`; пустой список — без ограничений
+    pub allowed: Vec<Expression>,
+    pub default: Option<Expression>,
+    pub span: Span,
+}
+
+impl FieldType {
+    /// Поле должно быть в словаре: не `?` и без значения по умолчанию
+    pub fn is_required(&self) -> bool {
+        !self.optional && self.default.is_none()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::utils::span::Span;
+use std::fmt;
+
+/// Аннотация в угловых скобках: `let speed<Speed>`, `let sizes<Array<Length>>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAnnotation {
+    pub kind: TypeKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    /// `Speed`, `Number`, `String`
+    Named(String),
+    /// `Array<Length>`, `Dictionary<String, Time>`
+    Generic {
+        name: String,
+        arguments: Vec<TypeAnnotation>,
+    },
+    /// `Length | Percent`
+    Union(Vec<TypeAnnotation>),
+}
+
+impl TypeAnnotation {
+    /// Имя без аргументов: для `extends` и `implements`
+    pub fn name(&self) -> Option<&str> {
+        match &self.kind {
+            TypeKind::Named(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeKind::Named(name) => write!(f, "{}", name),
+            TypeKind::Generic { name, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}<{}>", name, arguments.join(", "))
+            }
+            TypeKind::Union(members) => {
+                let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+        }
+    }
+}
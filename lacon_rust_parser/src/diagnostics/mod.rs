@@ -0,0 +1,3 @@
+pub mod warning;
+
+pub use warning::{LintConfig, LintId, LintLevel, Linter, Warning};
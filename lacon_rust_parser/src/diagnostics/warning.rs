@@ -0,0 +1,692 @@
+// Линты: предупреждения о записях, которые разбираются, но скорее всего ошибочны.
+//
+// У каждого линта постоянный идентификатор (`unused-variable`), по которому
+// меняется уровень (allow/warn/deny) в `LintConfig` и в комментариях документа:
+//
+//   /|\ lacon: allow unused-variable, duplicate-key   — уровень для всего файла
+//   /|\ lacon: deny unit-typo
+//   gap: 8pz /|\ lacon: ignore unit-typo              — только эта строка
+//   /|\ lacon: ignore                                 — следующая строка, все линты
+//
+// `ignore` в конце строки относится к ней самой, в отдельной строке — к следующей.
+// Неизвестный идентификатор в директиве — отдельный линт `unknown-lint`;
+// `ignore`, в котором все имена неизвестны, ничего не подавляет.
+// Директивы работают и в блочных комментариях `/* lacon: allow ... */`.
+
+use crate::ast::expressions::{Expression, ExpressionKind, Segment};
+use crate::ast::statements::{Block, EnumValue, StatementKind};
+use crate::lexer::error::{LexicalWarning, LexicalWarningType};
+use crate::lexer::position::Position;
+use crate::lexer::scanner::Scanner;
+use crate::lexer::token::{Token, TokenFlags};
+use crate::lexer::token_type::TokenType;
+use crate::parser::Parser;
+use crate::semantic::analyzer::Analyzer;
+use crate::semantic::scope::ScopeId;
+use crate::semantic::symbol_table::{SymbolId, SymbolKind, SymbolTable};
+use crate::shared::unit::registry::UnitRegistry;
+use crate::shared::unit::units::UNITS;
+use crate::utils::span::Span;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LintId {
+    /// `let`/`const`, на которые нет ни одной ссылки
+    UnusedVariable,
+    /// Ключ скрывает внешнюю привязку, и ссылки в блоке указывают на ключ
+    ShadowedKey,
+    /// Ключ повторяется в одном словаре
+    DuplicateKey,
+    /// Пропущенный элемент массива: `[value, , value]`
+    EmptyArrayItem,
+    /// Суффикс числа похож на опечатку в единице: `8pz`
+    UnitTypo,
+    /// Символ единицы значит разное в США и Британии: `5gal`
+    AmbiguousUnit,
+    /// Отступ строки смешивает пробелы и табуляцию
+    MixedIndentation,
+    /// Директива `lacon:` называет несуществующий линт: `ignore unit-tpyo`
+    UnknownLint,
+}
+
+impl LintId {
+    pub const ALL: [LintId; 8] = [
+        LintId::UnusedVariable,
+        LintId::ShadowedKey,
+        LintId::DuplicateKey,
+        LintId::EmptyArrayItem,
+        LintId::UnitTypo,
+        LintId::AmbiguousUnit,
+        LintId::MixedIndentation,
+        LintId::UnknownLint,
+    ];
+
+    /// Постоянный идентификатор для настроек и комментариев
+    pub fn name(self) -> &'static str {
+        match self {
+            LintId::UnusedVariable => "unused-variable",
+            LintId::ShadowedKey => "shadowed-key",
+            LintId::DuplicateKey => "duplicate-key",
+            LintId::EmptyArrayItem => "empty-array-item",
+            LintId::UnitTypo => "unit-typo",
+            LintId::AmbiguousUnit => "ambiguous-unit",
+            LintId::MixedIndentation => "mixed-indentation",
+            LintId::UnknownLint => "unknown-lint",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    /// Предупреждение считается ошибкой
+    Deny,
+}
+
+impl LintLevel {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Уровни линтов; по умолчанию все — Warn
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    levels: HashMap<LintId, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, lint: LintId, level: LintLevel) -> &mut Self {
+        self.levels.insert(lint, level);
+        self
+    }
+
+    pub fn level(&self, lint: LintId) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or(LintLevel::Warn)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub message: String,
+    pub span: Span,
+    pub lint: LintId,
+    pub level: LintLevel,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.level {
+            LintLevel::Deny => "Error",
+            _ => "Warning",
+        };
+        write!(
+            f,
+            "{} at {}: {} [{}]",
+            kind,
+            self.span,
+            self.message,
+            self.lint.name()
+        )
+    }
+}
+
+/// Директивы `lacon:` из комментариев документа
+#[derive(Debug, Default)]
+struct Suppressions {
+    levels: HashMap<LintId, LintLevel>,
+    /// Строка -> подавленные линты
+    lines: HashMap<usize, Vec<LintId>>,
+    /// Неизвестные имена линтов и комментарии, в которых они стоят
+    unknown: Vec<(String, Span)>,
+}
+
+impl Suppressions {
+    fn parse(source: &str) -> Self {
+        let mut result = Self::default();
+        let mut offset = 0;
+        for (index, line) in source.split('\n').enumerate() {
+            let line_offset = offset;
+            offset += line.len() + 1;
+            let Some((code, comment)) = split_comment(line) else {
+                continue;
+            };
+            let Some(directive) = comment.trim().strip_prefix("lacon:") else {
+                continue;
+            };
+            let directive = directive.trim().trim_end_matches("*/").trim();
+            let (word, rest) = directive.split_once(' ').unwrap_or((directive, ""));
+            let names: Vec<&str> = rest
+                .split([',', ' '])
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .collect();
+
+            let mut ids = Vec::new();
+            for name in &names {
+                match LintId::from_name(name) {
+                    Some(id) => ids.push(id),
+                    None => {
+                        let line = line.trim_end_matches('\r');
+                        let start = Position::new(
+                            index + 1,
+                            code.chars().count() + 1,
+                            line_offset + code.len(),
+                        );
+                        let end = Position::new(
+                            index + 1,
+                            line.chars().count() + 1,
+                            line_offset + line.len(),
+                        );
+                        result
+                            .unknown
+                            .push((name.to_string(), Span::new(start, end)));
+                    }
+                }
+            }
+
+            if word == "ignore" {
+                // Без имен — все линты
+                if names.is_empty() {
+                    ids.extend(LintId::ALL);
+                }
+                // Строки нумеруются с 1, как в Position
+                let target = if code.trim().is_empty() {
+                    index + 2
+                } else {
+                    index + 1
+                };
+                result.lines.entry(target).or_default().extend(ids);
+            } else if let Some(level) = LintLevel::from_name(word) {
+                for id in ids {
+                    result.levels.insert(id, level);
+                }
+            }
+        }
+        result
+    }
+
+    fn is_ignored(&self, lint: LintId, line: usize) -> bool {
+        self.lines.get(&line).is_some_and(|ids| ids.contains(&lint))
+    }
+}
+
+/// Код и комментарий строки; маркеры внутри строковых литералов не считаются
+fn split_comment(line: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if matches!(c, '"' | '\'' | '`') => quote = Some(c),
+            None if line[i..].starts_with("/|\\") => return Some((&line[..i], &line[i + 3..])),
+            None if line[i..].starts_with("/*") => return Some((&line[..i], &line[i + 2..])),
+            None => {}
+        }
+    }
+    None
+}
+
+pub struct Linter<'a> {
+    config: &'a LintConfig,
+    registry: UnitRegistry,
+    suppressions: Suppressions,
+    warnings: Vec<Warning>,
+}
+
+impl<'a> Linter<'a> {
+    /// Сканирует, разбирает и анализирует документ, затем применяет линты.
+    /// Ошибки разбора не мешают линтам: они работают по тому, что разобралось.
+    /// Лексические ошибки возвращает `parse()`, предупреждения сканера — линты.
+    pub fn run(source: &str, config: &'a LintConfig) -> Vec<Warning> {
+        let mut scanner = Scanner::new(source.to_string());
+        let tokens = scanner.scan_tokens().clone();
+        let program = Parser::new(tokens.clone()).parse_program();
+        let analysis = Analyzer::analyze(&program);
+
+        let mut linter = Linter {
+            config,
+            registry: scanner.registry().clone(),
+            suppressions: Suppressions::parse(source),
+            warnings: Vec::new(),
+        };
+        linter.check_directives();
+        linter.check_symbols(&analysis.table);
+        linter.check_block(&program.body);
+        linter.check_tokens(&tokens);
+        linter.check_lexical(&scanner.warnings, &tokens);
+        linter.check_indentation(source);
+
+        linter.warnings.sort_by_key(|w| w.span.start);
+        linter.warnings
+    }
+
+    fn report(&mut self, lint: LintId, span: Span, message: String) {
+        let level = self
+            .suppressions
+            .levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| self.config.level(lint));
+        if level == LintLevel::Allow || self.suppressions.is_ignored(lint, span.start.line) {
+            return;
+        }
+        self.warnings.push(Warning {
+            message,
+            span,
+            lint,
+            level,
+        });
+    }
+
+    /// unknown-lint
+    fn check_directives(&mut self) {
+        for (name, span) in std::mem::take(&mut self.suppressions.unknown) {
+            self.report(
+                LintId::UnknownLint,
+                span,
+                format!("Unknown lint '{}'", name),
+            );
+        }
+    }
+
+    /// unused-variable, shadowed-key, duplicate-key
+    fn check_symbols(&mut self, table: &SymbolTable) {
+        for (index, symbol) in table.symbols().iter().enumerate() {
+            let id = SymbolId(index);
+
+            if symbol.is_binding()
+                && !symbol.name.starts_with('_')
+                && table.references_to(id).next().is_none()
+            {
+                self.report(
+                    LintId::UnusedVariable,
+                    symbol.span,
+                    format!("'{}' is never used", symbol.name),
+                );
+            }
+            if symbol.kind != SymbolKind::Key {
+                continue;
+            }
+
+            let first = table
+                .scope(symbol.scope)
+                .declared(&symbol.name)
+                .first()
+                .map(|&other| (other, table.symbol(other)));
+            if let Some((first, original)) = first
+                && first != id
+                && original.kind == SymbolKind::Key
+            {
+                self.report(
+                    LintId::DuplicateKey,
+                    symbol.span,
+                    format!(
+                        "Key '{}' is already defined at {}",
+                        symbol.name, original.span
+                    ),
+                );
+                continue;
+            }
+
+            let Some(parent) = table.scope(symbol.scope).parent else {
+                continue;
+            };
+            let Some(outer) = table.lookup(parent, &symbol.name, symbol.span.start) else {
+                continue;
+            };
+            let captured = table
+                .references_to(id)
+                .any(|r| is_within(table, r.scope, symbol.scope));
+            if table.symbol(outer).is_binding() && captured {
+                self.report(
+                    LintId::ShadowedKey,
+                    symbol.span,
+                    format!(
+                        "Key '{}' shadows the binding declared at {}; uses in this block refer to the key",
+                        symbol.name,
+                        table.symbol(outer).span
+                    ),
+                );
+            }
+        }
+    }
+
+    /// empty-array-item
+    fn check_block(&mut self, block: &Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Variable(decl) => self.check_optional(&decl.value),
+                StatementKind::Property(property) | StatementKind::Yield(property) => {
+                    self.check_optional(&property.value)
+                }
+                StatementKind::Assignment(assignment) => self.check_optional(&assignment.value),
+                StatementKind::Container(container) => self.check_block(&container.body),
+                StatementKind::Generator(generator) => {
+                    self.check_expression(&generator.source);
+                    self.check_block(&generator.body);
+                }
+                StatementKind::Schema(schema) => {
+                    for field in &schema.fields {
+                        field.allowed.iter().for_each(|a| self.check_expression(a));
+                        self.check_optional(&field.default);
+                    }
+                }
+                StatementKind::Enum(decl) => {
+                    for member in &decl.members {
+                        if let Some(EnumValue::Expression(value)) = &member.value {
+                            self.check_expression(value);
+                        }
+                    }
+                }
+                StatementKind::TypeAlias(_) | StatementKind::Import(_) => {}
+                StatementKind::Expression(expression) => self.check_expression(expression),
+            }
+        }
+    }
+
+    fn check_optional(&mut self, value: &Option<Expression>) {
+        if let Some(value) = value {
+            self.check_expression(value);
+        }
+    }
+
+    fn check_expression(&mut self, expression: &Expression) {
+        match &expression.kind {
+            ExpressionKind::Array(items) => {
+                for item in items {
+                    if item.kind == ExpressionKind::Hole {
+                        self.report(
+                            LintId::EmptyArrayItem,
+                            item.span,
+                            "Empty array item".to_string(),
+                        );
+                    }
+                    self.check_expression(item);
+                }
+            }
+            ExpressionKind::Tuple(items) => items.iter().for_each(|i| self.check_expression(i)),
+            ExpressionKind::Dictionary(block) => self.check_block(block),
+            ExpressionKind::String(segments) => {
+                for segment in segments {
+                    if let Segment::Interpolation(inner) = segment {
+                        self.check_expression(inner);
+                    }
+                }
+            }
+            ExpressionKind::Unary { operand, .. } => self.check_expression(operand),
+            ExpressionKind::Binary { left, right, .. } => {
+                self.check_expression(left);
+                self.check_expression(right);
+            }
+            ExpressionKind::Call { callee, arguments } => {
+                self.check_expression(callee);
+                arguments.iter().for_each(|a| self.check_expression(a));
+            }
+            ExpressionKind::Member { object, .. } => self.check_expression(object),
+            ExpressionKind::Index { object, index } => {
+                self.check_expression(object);
+                self.check_expression(index);
+            }
+            _ => {}
+        }
+    }
+
+    /// unit-typo: число, к которому вплотную примыкает неизвестный суффикс
+    fn check_tokens(&mut self, tokens: &[Token]) {
+        for pair in tokens.windows(2) {
+            let [number, suffix] = pair else {
+                continue;
+            };
+            if number.token_type != TokenType::Number
+                || suffix.token_type != TokenType::Identifier
+                || suffix.flags.contains(TokenFlags::HAS_PRECEDING_WHITESPACE)
+            {
+                continue;
+            }
+            if let Some(unit) = self.suggest_unit(&suffix.lexeme) {
+                self.report(
+                    LintId::UnitTypo,
+                    Span::of_token(suffix),
+                    format!(
+                        "Unknown unit suffix '{}', did you mean '{}'?",
+                        suffix.lexeme, unit
+                    ),
+                );
+            }
+        }
+    }
+
+    /// ambiguous-unit: предупреждения сканера; участок — весь токен с единицей
+    fn check_lexical(&mut self, warnings: &[LexicalWarning], tokens: &[Token]) {
+        for warning in warnings {
+            let lint = match warning.warning_type {
+                LexicalWarningType::AmbiguousUnit(_) => LintId::AmbiguousUnit,
+            };
+            let span = tokens
+                .iter()
+                .map(Span::of_token)
+                .find(|span| {
+                    (span.start.offset..span.end.offset).contains(&warning.position.offset)
+                })
+                .unwrap_or_else(|| Span::point(warning.position));
+            self.report(lint, span, warning.message.clone());
+        }
+    }
+
+    /// Ближайшая известная единица на расстоянии одной правки.
+    /// Правки перебираются от более вероятных опечаток к менее вероятным:
+    /// регистр, удвоенная буква, перестановка соседних, соседняя клавиша,
+    /// лишняя буква, пропущенная буква, любая замена.
+    fn suggest_unit(&self, suffix: &str) -> Option<String> {
+        let chars: Vec<char> = suffix.chars().collect();
+        if chars.len() < 2 {
+            return None;
+        }
+        let mut alphabet: Vec<char> = UNITS
+            .iter()
+            .flat_map(|u| u.symbol.chars())
+            .chain(self.registry.units().iter().flat_map(|u| u.symbol.chars()))
+            .filter(|c| c.is_alphabetic())
+            .collect::<HashSet<char>>()
+            .into_iter()
+            .collect();
+        alphabet.sort_unstable();
+
+        let n = chars.len();
+        let mut edits: Vec<Vec<char>> = Vec::new();
+        let mut edit = |f: &dyn Fn(&mut Vec<char>)| {
+            let mut edited = chars.clone();
+            f(&mut edited);
+            edits.push(edited);
+        };
+        for i in (1..n).filter(|&i| chars[i] == chars[i - 1]) {
+            edit(&|c| {
+                c.remove(i);
+            });
+        }
+        for i in 1..n {
+            edit(&|c| c.swap(i - 1, i));
+        }
+        for adjacent in [true, false] {
+            if !adjacent {
+                for i in 0..n {
+                    edit(&|c| {
+                        c.remove(i);
+                    });
+                }
+                for i in 0..=n {
+                    for &r in &alphabet {
+                        edit(&|c| c.insert(i, r));
+                    }
+                }
+            }
+            // С конца: первую букву символа путают реже
+            for i in (0..n).rev() {
+                for &r in alphabet
+                    .iter()
+                    .filter(|&&r| is_adjacent_key(chars[i], r) == adjacent)
+                {
+                    edit(&|c| c[i] = r);
+                }
+            }
+        }
+
+        let mut candidates = vec![suffix.to_lowercase()];
+        candidates.extend(edits.into_iter().map(|c| c.into_iter().collect::<String>()));
+        candidates
+            .into_iter()
+            .find(|candidate| candidate != suffix && self.registry.resolve(candidate).is_some())
+    }
+
+    /// mixed-indentation
+    fn check_indentation(&mut self, source: &str) {
+        let mut offset = 0;
+        for (index, line) in source.split('\n').enumerate() {
+            let indent: &str = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+            if indent.contains(' ') && indent.contains('\t') && !line.trim().is_empty() {
+                let start = Position::new(index + 1, 1, offset);
+                let end =
+                    Position::new(index + 1, indent.chars().count() + 1, offset + indent.len());
+                self.report(
+                    LintId::MixedIndentation,
+                    Span::new(start, end),
+                    "Indentation mixes spaces and tabs".to_string(),
+                );
+            }
+            offset += line.len() + 1;
+        }
+    }
+}
+
+/// Соседние клавиши QWERTY в одном регистре: `x` рядом с `z`, `s`, `d`, `c`
+fn is_adjacent_key(a: char, b: char) -> bool {
+    const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let locate = |c: char| {
+        let c = c.to_ascii_lowercase();
+        ROWS.iter()
+            .enumerate()
+            .find_map(|(row, keys)| Some((row as i32, keys.find(c)? as i32)))
+    };
+    match (locate(a), locate(b)) {
+        // Нижние ряды сдвинуты вправо на полклавиши
+        (Some((ra, ca)), Some((rb, cb))) if a != b && a.is_uppercase() == b.is_uppercase() => {
+            match ra - rb {
+                0 => (ca - cb).abs() == 1,
+                1 => cb == ca || cb == ca + 1,
+                -1 => ca == cb || ca == cb + 1,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Лежит ли область `scope` внутри `ancestor` (или совпадает с ней)
+fn is_within(table: &SymbolTable, scope: ScopeId, ancestor: ScopeId) -> bool {
+    let mut current = Some(scope);
+    while let Some(id) = current {
+        if id == ancestor {
+            return true;
+        }
+        current = table.scope(id).parent;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(source: &str) -> Vec<(LintId, usize)> {
+        Linter::run(source, &LintConfig::new())
+            .iter()
+            .map(|w| (w.lint, w.span.start.line))
+            .collect()
+    }
+
+    #[test]
+    fn reports_suspicious_constructs() {
+        let source = "let gap = 4px\nlet unused = 1\nlet _spare = 2\nbox {\n    gap: 8px\n    inner: gap\n    inner: 2\n}\nlist = [gap, , 2]\nwidth = 8pxx\n\t  height = 2\n";
+        assert_eq!(
+            lints(source),
+            [
+                (LintId::UnusedVariable, 2),
+                (LintId::ShadowedKey, 5),
+                (LintId::DuplicateKey, 7),
+                (LintId::EmptyArrayItem, 9),
+                (LintId::UnitTypo, 10),
+                (LintId::MixedIndentation, 11),
+            ]
+        );
+
+        let warnings = Linter::run(source, &LintConfig::new());
+        assert_eq!(
+            warnings[4].to_string(),
+            "Warning at 10:10: Unknown unit suffix 'pxx', did you mean 'px'? [unit-typo]"
+        );
+    }
+
+    #[test]
+    fn levels_and_comment_suppression() {
+        let mut config = LintConfig::new();
+        config
+            .set(LintId::UnusedVariable, LintLevel::Deny)
+            .set(LintId::EmptyArrayItem, LintLevel::Allow);
+        let warnings = Linter::run("let a = 1\nlist = [1, , 2]\n", &config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].level, LintLevel::Deny);
+        assert!(warnings[0].to_string().starts_with("Error at 1:5"));
+
+        let source = "/|\\ lacon: allow unused-variable\nlet a = 1\nw = 8pz /|\\ lacon: ignore unit-typo\n/* lacon: ignore */\nlist = [1, , 2]\nh = 8pz\ns = '/|\\ lacon: ignore'\n";
+        assert_eq!(lints(source), [(LintId::UnitTypo, 6)]);
+
+        // Опечатка в имени не подавляет все линты строки
+        let source = "/|\\ lacon: ignore unit-tpyo\nlist = [1, , 2]\n/|\\ lacon: allow unused-varible\nlet a = 1\n";
+        assert_eq!(
+            lints(source),
+            [
+                (LintId::UnknownLint, 1),
+                (LintId::EmptyArrayItem, 2),
+                (LintId::UnknownLint, 3),
+                (LintId::UnusedVariable, 4),
+            ]
+        );
+        let warnings = Linter::run(source, &LintConfig::new());
+        assert_eq!(
+            warnings[0].to_string(),
+            "Warning at 1:1: Unknown lint 'unit-tpyo' [unknown-lint]"
+        );
+    }
+
+    #[test]
+    fn scanner_warnings_are_lints() {
+        let source = "v = 5gal\nw = 2gal /|\\ lacon: ignore ambiguous-unit\n";
+        assert_eq!(lints(source), [(LintId::AmbiguousUnit, 1)]);
+        let warnings = Linter::run(source, &LintConfig::new());
+        assert_eq!(
+            warnings[0].to_string(),
+            "Warning at 1:5: Unit 'gal' is ambiguous, assuming gal_us; write gal_us or gal_uk to be explicit [ambiguous-unit]"
+        );
+
+        let mut config = LintConfig::new();
+        config.set(LintId::AmbiguousUnit, LintLevel::Deny);
+        assert_eq!(Linter::run(source, &config)[0].level, LintLevel::Deny);
+        assert!(lints("/|\\ lacon: allow ambiguous-unit\nv = 5gal\n").is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::shared::unit::conversion::ConversionError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeError {
+    pub message: String,
+    pub error_type: RuntimeErrorType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeErrorType {
+    /// Операнды нельзя привести к одной единице
+    IncompatibleUnits,
+    /// Сложение двух абсолютных значений аффинной шкалы (10°C + 5°C)
+    AffineAddition,
+    /// Ошибка перевода между единицами
+    Conversion(ConversionError),
+}
+
+impl RuntimeError {
+    pub fn new(error_type: RuntimeErrorType, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            error_type,
+        }
+    }
+}
+
+impl From<ConversionError> for RuntimeError {
+    fn from(err: ConversionError) -> Self {
+        let error_type = match err {
+            ConversionError::DimensionMismatch { .. } => RuntimeErrorType::IncompatibleUnits,
+            _ => RuntimeErrorType::Conversion(err.clone()),
+        };
+        Self::new(error_type, err.to_string())
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Runtime Error: {} ({:?})", self.message, self.error_type)
+    }
+}
@@ -0,0 +1 @@
+pub mod unit_ops;
@@ -0,0 +1,375 @@
+use crate::interpreter::error::{RuntimeError, RuntimeErrorType};
+use crate::interpreter::types::units::{Quantity, QuantityKind, UnitValue};
+use crate::shared::unit::conversion::ConversionError;
+use crate::shared::unit::dimensions::Dimension;
+use crate::shared::unit::units::find_unit;
+
+// Правила для аффинных величин (°C, °F...):
+//   абсолютное ± разность   -> абсолютное
+//   разность ± разность     -> разность
+//   абсолютное - абсолютное -> разность
+//   абсолютное + абсолютное -> ошибка (10°C + 5°C не имеет смысла)
+//   разность - абсолютное   -> ошибка
+// Для линейных шкал без смещения (K, m, kg) запрещающих случаев нет.
+// Результат всегда выражается в единице левого операнда.
+//
+// Логарифмические уровни (dB, dBm, Np) складываются через линейные величины:
+//   уровень + уровень   (10dBm + 10dBm) -> сумма мощностей, ≈13.01dBm
+//   уровень ± усиление  (10dBm + 3dB)   -> мощность умножается на отношение
+//   усиление ± усиление (3dB + 3dB)     -> отношения перемножаются, 6dB
+//   уровень - уровень   (20dBm - 10dBm) -> отношение, 10dB
+// Усиление — безразмерный логарифмический уровень (dB, bel, Np).
+//
+// Умножение и деление сводят единицы к вектору размерности (compound.rs):
+// 2m * 3N -> 6m·N (Energy). Результат без именованной размерности — ошибка.
+// Безразмерные доли (%, ‰, ppm) только масштабируют другой операнд:
+// 50% * 10m -> 5m, 10m / 50% -> 20m. Безразмерный итог (m / m) — в `ratio`.
+
+pub fn add(lhs: &UnitValue, rhs: &UnitValue) -> Result<UnitValue, RuntimeError> {
+    if lhs.unit.is_logarithmic() || rhs.unit.is_logarithmic() {
+        return combine_levels(lhs, rhs, false);
+    }
+
+    let affine = lhs.unit.is_affine() || rhs.unit.is_affine();
+
+    let kind = match (lhs.kind, rhs.kind) {
+        (QuantityKind::Absolute, QuantityKind::Absolute) if affine => {
+            return Err(RuntimeError::new(
+                RuntimeErrorType::AffineAddition,
+                format!(
+                    "Cannot add two absolute temperatures ({} + {}); subtract them or add a difference",
+                    lhs, rhs
+                ),
+            ));
+        }
+        (QuantityKind::Delta, QuantityKind::Delta) => QuantityKind::Delta,
+        _ => QuantityKind::Absolute,
+    };
+
+    // Абсолютное значение задает шкалу результата
+    let (base, other) = if lhs.is_delta() && !rhs.is_delta() {
+        (rhs, lhs)
+    } else {
+        (lhs, rhs)
+    };
+    let other = other.convert_to(&base.unit)?;
+
+    Ok(UnitValue {
+        value: base.value + other.value,
+        unit: base.unit.clone(),
+        kind,
+    })
+}
+
+pub fn subtract(lhs: &UnitValue, rhs: &UnitValue) -> Result<UnitValue, RuntimeError> {
+    if lhs.unit.is_logarithmic() || rhs.unit.is_logarithmic() {
+        return combine_levels(lhs, rhs, true);
+    }
+
+    let affine = lhs.unit.is_affine() || rhs.unit.is_affine();
+
+    let kind = match (lhs.kind, rhs.kind) {
+        (QuantityKind::Delta, QuantityKind::Absolute) if affine => {
+            return Err(RuntimeError::new(
+                RuntimeErrorType::AffineAddition,
+                format!(
+                    "Cannot subtract an absolute temperature from a difference ({} - {})",
+                    lhs, rhs
+                ),
+            ));
+        }
+        (QuantityKind::Absolute, QuantityKind::Absolute) => {
+            // Разность на нелинейной шкале не выражается в ее же единицах
+            if lhs.unit.normalize_delta(1.0).is_none() {
+                return Err(ConversionError::NonLinearDelta(lhs.unit.symbol.to_string()).into());
+            }
+            QuantityKind::Delta
+        }
+        (QuantityKind::Absolute, QuantityKind::Delta) => QuantityKind::Absolute,
+        _ => QuantityKind::Delta,
+    };
+
+    let other = rhs.convert_to(&lhs.unit)?;
+
+    Ok(UnitValue {
+        value: lhs.value - other.value,
+        unit: lhs.unit.clone(),
+        kind,
+    })
+}
+
+pub fn multiply(lhs: &UnitValue, rhs: &UnitValue) -> Result<UnitValue, RuntimeError> {
+    combine_units(lhs, rhs, false)
+}
+
+pub fn divide(lhs: &UnitValue, rhs: &UnitValue) -> Result<UnitValue, RuntimeError> {
+    combine_units(lhs, rhs, true)
+}
+
+fn combine_units(
+    lhs: &UnitValue,
+    rhs: &UnitValue,
+    divide: bool,
+) -> Result<UnitValue, RuntimeError> {
+    let incompatible = |reason: String| {
+        RuntimeError::new(
+            RuntimeErrorType::IncompatibleUnits,
+            format!(
+                "Cannot {} {} and {}: {}",
+                if divide { "divide" } else { "multiply" },
+                lhs,
+                rhs,
+                reason
+            ),
+        )
+    };
+    // Усиление (3dB, 1Np) — отношение уровней, а не множитель: мощность и поле
+    // масштабируются по-разному, поэтому оно прибавляется к уровню (dBm, dBV)
+    if is_gain(lhs) || is_gain(rhs) {
+        return Err(incompatible(
+            "a gain applies to a level (dBm, dBV); add it instead".to_string(),
+        ));
+    }
+    let scalable = |value: &UnitValue| !value.unit.is_affine() && !value.unit.is_logarithmic();
+    if rhs.unit.dimension == Dimension::Percent && scalable(lhs) {
+        let factor = rhs.to_base()?;
+        let value = if divide {
+            lhs.value / factor
+        } else {
+            lhs.value * factor
+        };
+        return Ok(UnitValue::new(value, lhs.unit.clone()));
+    }
+    if lhs.unit.dimension == Dimension::Percent && scalable(rhs) && !divide {
+        return Ok(UnitValue::new(rhs.value * lhs.to_base()?, rhs.unit.clone()));
+    }
+
+    // Алгебра размерностей — в `Quantity`; здесь только возврат к `UnitValue`
+    let quantity = |value: &UnitValue| {
+        Quantity::from_unit_value(value).map_err(|e| incompatible(e.to_string()))
+    };
+    let (lhs_quantity, rhs_quantity) = (quantity(lhs)?, quantity(rhs)?);
+    let result = if divide {
+        lhs_quantity.divide(&rhs_quantity)
+    } else {
+        lhs_quantity.multiply(&rhs_quantity)
+    }
+    .map_err(|e| incompatible(e.to_string()))?;
+
+    if result.dimension().is_dimensionless() {
+        let ratio = find_unit("ratio").unwrap();
+        return Ok(UnitValue::new(result.to_coherent(), ratio.clone()));
+    }
+    result
+        .to_unit_value()
+        .ok_or_else(|| incompatible(format!("'{}' has no named dimension", result.dimension())))
+}
+
+fn is_gain(value: &UnitValue) -> bool {
+    value.unit.is_logarithmic() && value.unit.dimension == Dimension::Percent
+}
+
+fn combine_levels(
+    lhs: &UnitValue,
+    rhs: &UnitValue,
+    subtract: bool,
+) -> Result<UnitValue, RuntimeError> {
+    let incompatible = || {
+        RuntimeError::new(
+            RuntimeErrorType::IncompatibleUnits,
+            format!(
+                "Cannot {} {} and {}",
+                if subtract { "subtract" } else { "add" },
+                lhs,
+                rhs
+            ),
+        )
+    };
+
+    // Усиление применяется к уровню: для полевых величин (dBV) — корень из отношения мощностей
+    if is_gain(rhs) && (lhs.unit.is_logarithmic() || is_gain(lhs)) {
+        let ratio = rhs.to_base()?;
+        let ratio = if subtract { 1.0 / ratio } else { ratio };
+        let base = lhs.to_base()? * ratio.powf(1.0 / lhs.unit.props.exponent);
+        return Ok(UnitValue::new(lhs.unit.denormalize(base), lhs.unit.clone()));
+    }
+    if is_gain(lhs) && rhs.unit.is_logarithmic() && !subtract {
+        return combine_levels(rhs, lhs, false);
+    }
+    if is_gain(lhs) || is_gain(rhs) || lhs.unit.dimension != rhs.unit.dimension {
+        return Err(incompatible());
+    }
+
+    let (a, b) = (lhs.to_base()?, rhs.to_base()?);
+    if subtract && lhs.unit.is_logarithmic() && rhs.unit.is_logarithmic() {
+        // Разность уровней — отношение величин, выраженное в dB
+        let db = find_unit("dB").unwrap();
+        let ratio = (a / b).powf(lhs.unit.props.exponent);
+        return Ok(UnitValue::new(db.denormalize(ratio), db.clone()));
+    }
+
+    let base = if subtract { a - b } else { a + b };
+    if base <= 0.0 && lhs.unit.is_logarithmic() {
+        return Err(incompatible());
+    }
+    Ok(UnitValue::new(lhs.unit.denormalize(base), lhs.unit.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::unit::units::find_unit;
+
+    fn abs(value: f64, symbol: &str) -> UnitValue {
+        UnitValue::new(value, find_unit(symbol).unwrap().clone())
+    }
+
+    fn delta(value: f64, symbol: &str) -> UnitValue {
+        UnitValue::delta(value, find_unit(symbol).unwrap().clone())
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn absolute_temperatures_cannot_be_added() {
+        let err = add(&abs(10.0, "°C"), &abs(5.0, "°C")).unwrap_err();
+        assert_eq!(err.error_type, RuntimeErrorType::AffineAddition);
+
+        // Кельвин не аффинный — сложение допустимо
+        let sum = add(&abs(10.0, "K"), &abs(5.0, "K")).unwrap();
+        assert_close(sum.value, 15.0);
+    }
+
+    #[test]
+    fn subtraction_yields_delta() {
+        let diff = subtract(&abs(20.0, "°C"), &abs(50.0, "°F")).unwrap();
+        assert_eq!(diff.kind, QuantityKind::Delta);
+        assert_close(diff.value, 10.0);
+
+        // Δ10°C == Δ18°F, а не 50°F
+        let in_f = diff.convert_to(find_unit("°F").unwrap()).unwrap();
+        assert_close(in_f.value, 18.0);
+        assert_eq!(in_f.to_string(), "Δ18°F");
+    }
+
+    #[test]
+    fn delta_shifts_absolute() {
+        let warmer = add(&abs(20.0, "°C"), &delta(9.0, "°F")).unwrap();
+        assert_eq!(warmer.kind, QuantityKind::Absolute);
+        assert_close(warmer.value, 25.0);
+
+        let commuted = add(&delta(5.0, "K"), &abs(20.0, "°C")).unwrap();
+        assert_eq!(commuted.unit.symbol, "°C");
+        assert_close(commuted.value, 25.0);
+
+        let err = subtract(&delta(5.0, "°C"), &abs(20.0, "°C")).unwrap_err();
+        assert_eq!(err.error_type, RuntimeErrorType::AffineAddition);
+    }
+
+    #[test]
+    fn exponential_scale_has_no_delta() {
+        let err = subtract(&abs(20.0, "°Da"), &abs(10.0, "°Da")).unwrap_err();
+        assert!(matches!(err.error_type, RuntimeErrorType::Conversion(_)));
+    }
+
+    #[test]
+    fn logarithmic_levels() {
+        // Две равные мощности дают +3.01 dB, а не 20dBm
+        let sum = add(&abs(10.0, "dBm"), &abs(10.0, "dBm")).unwrap();
+        assert_close(sum.value, 10.0 + 10.0 * 2f64.log10());
+        assert_eq!(sum.unit.symbol, "dBm");
+
+        // Уровень + линейная мощность той же размерности
+        let mixed = add(&abs(0.0, "dBm"), &abs(1e-3, "W")).unwrap();
+        assert_close(mixed.value, 10.0 * 2f64.log10());
+
+        let gained = add(&abs(10.0, "dBm"), &abs(3.0, "dB")).unwrap();
+        assert_close(gained.value, 13.0);
+        let commuted = add(&abs(3.0, "dB"), &abs(10.0, "dBm")).unwrap();
+        assert_eq!(commuted.unit.symbol, "dBm");
+        assert_close(commuted.value, 13.0);
+
+        // Усиление 6 dB по мощности поднимает уровень напряжения тоже на 6 dB
+        assert_close(add(&abs(0.0, "dBV"), &abs(6.0, "dB")).unwrap().value, 6.0);
+        assert_close(add(&abs(1.0, "Np"), &abs(1.0, "Np")).unwrap().value, 2.0);
+
+        let diff = subtract(&abs(20.0, "dBm"), &abs(10.0, "dBm")).unwrap();
+        assert_eq!(diff.unit.symbol, "dB");
+        assert_close(diff.value, 10.0);
+
+        let err = add(&abs(10.0, "dBm"), &abs(1.0, "dBV")).unwrap_err();
+        assert_eq!(err.error_type, RuntimeErrorType::IncompatibleUnits);
+    }
+
+    #[test]
+    fn products_and_quotients() {
+        let work = multiply(&abs(2.0, "N"), &abs(3.0, "m")).unwrap();
+        assert_eq!(work.unit.dimension, Dimension::Energy);
+        assert_eq!(work.unit.symbol, "N·m");
+        assert_close(work.to_base().unwrap(), 6.0);
+
+        let speed = divide(&abs(1.0, "mi"), &abs(1.0, "hour")).unwrap();
+        assert_eq!(speed.unit.dimension, Dimension::Velocity);
+        let mph = speed.convert_to(find_unit("mi/h").unwrap()).unwrap();
+        assert_close(mph.value, 1.0);
+
+        let err = multiply(&abs(1.0, "m"), &abs(1.0, "dBm")).unwrap_err();
+        assert_eq!(err.error_type, RuntimeErrorType::IncompatibleUnits);
+        // Усиление не масштабирует величину: 1V·6dB ≠ 3.98V, 10m·1Np ≠ 73.89m
+        for (lhs, gain) in [
+            (abs(1.0, "V"), abs(6.0, "dB")),
+            (abs(10.0, "m"), abs(1.0, "Np")),
+        ] {
+            let err = multiply(&lhs, &gain).unwrap_err();
+            assert_eq!(err.error_type, RuntimeErrorType::IncompatibleUnits);
+            let err = multiply(&gain, &lhs).unwrap_err();
+            assert_eq!(err.error_type, RuntimeErrorType::IncompatibleUnits);
+        }
+
+        // Как и у `Quantity`: точка на шкале со смещением не умножается и не делится, разность — да
+        assert!(multiply(&abs(20.0, "°C"), &abs(2.0, "g")).is_err());
+        let ratio = divide(&delta(20.0, "°C"), &delta(10.0, "K")).unwrap();
+        assert_close(ratio.value, 2.0);
+    }
+
+    #[test]
+    fn dimensionless_family() {
+        let half = multiply(&abs(50.0, "%"), &abs(10.0, "m")).unwrap();
+        assert_eq!(half.unit.symbol, "m");
+        assert_close(half.value, 5.0);
+        assert_close(
+            divide(&abs(10.0, "m"), &abs(50.0, "%")).unwrap().value,
+            20.0,
+        );
+        assert_close(
+            multiply(&abs(2.0, "g"), &abs(500.0, "ppm")).unwrap().value,
+            1e-3,
+        );
+
+        // Члены семейства переводятся друг в друга и складываются
+        let sum = add(&abs(1.0, "%"), &abs(5.0, "‰")).unwrap();
+        assert_close(sum.value, 1.5);
+        let ppb = abs(1.0, "ppm")
+            .convert_to(find_unit("ppb").unwrap())
+            .unwrap();
+        assert_close(ppb.value, 1000.0);
+
+        let ratio = divide(&abs(3.0, "m"), &abs(1.5, "m")).unwrap();
+        assert_eq!(ratio.unit.symbol, "ratio");
+        assert_close(
+            ratio.convert_to(find_unit("%").unwrap()).unwrap().value,
+            200.0,
+        );
+
+        // fr — единица вёрстки, а не доля
+        assert!(multiply(&abs(1.0, "fr"), &abs(10.0, "m")).is_err());
+        assert!(abs(1.0, "fr").convert_to(find_unit("%").unwrap()).is_err());
+    }
+}
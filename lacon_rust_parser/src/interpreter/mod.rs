@@ -0,0 +1,6 @@
+pub mod error;
+pub mod evaluator;
+pub mod stdlib;
+pub mod types;
+
+pub use error::{RuntimeError, RuntimeErrorType};
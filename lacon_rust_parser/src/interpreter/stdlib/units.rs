@@ -0,0 +1,213 @@
+use crate::interpreter::types::units::Quantity;
+use crate::shared::unit::compound::CompoundUnit;
+use crate::shared::unit::dimensions::{Dimension, DimensionVector};
+use crate::shared::unit::registry::UnitRegistry;
+
+// Физические постоянные (CODATA 2018) для пространства имен `units`:
+// `const e = m * c^2`, `weight = 70kg * g0`.
+// Единица записана составным выражением (compound.rs), поэтому у каждой
+// постоянной есть вектор размерности, даже если имени у него нет (G, h).
+// Точные по определению СИ значения помечены `exact`.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constant {
+    /// Первое имя — основное, остальные — ASCII-написания
+    pub names: &'static [&'static str],
+    pub value: f64,
+    /// Единица в когерентных единицах СИ
+    pub unit: &'static str,
+    pub exact: bool,
+    pub description: &'static str,
+}
+
+pub static CONSTANTS: &[Constant] = &[
+    Constant {
+        names: &["c"],
+        value: 299_792_458.0,
+        unit: "m/s",
+        exact: true,
+        description: "speed of light in vacuum",
+    },
+    Constant {
+        names: &["G"],
+        value: 6.674_30e-11,
+        unit: "m3/(kg·s2)",
+        exact: false,
+        description: "Newtonian constant of gravitation",
+    },
+    Constant {
+        names: &["h"],
+        value: 6.626_070_15e-34,
+        unit: "J·s",
+        exact: true,
+        description: "Planck constant",
+    },
+    Constant {
+        names: &["ħ", "hbar"],
+        value: 6.626_070_15e-34 / (2.0 * std::f64::consts::PI),
+        unit: "J·s",
+        exact: true,
+        description: "reduced Planck constant",
+    },
+    Constant {
+        names: &["k_B", "kB"],
+        value: 1.380_649e-23,
+        unit: "J/K",
+        exact: true,
+        description: "Boltzmann constant",
+    },
+    Constant {
+        names: &["N_A", "NA"],
+        value: 6.022_140_76e23,
+        unit: "mol^-1",
+        exact: true,
+        description: "Avogadro constant",
+    },
+    Constant {
+        names: &["e"],
+        value: 1.602_176_634e-19,
+        unit: "A·s",
+        exact: true,
+        description: "elementary charge",
+    },
+    Constant {
+        names: &["ε₀", "eps0"],
+        value: 8.854_187_812_8e-12,
+        unit: "A2·s4/(kg·m3)",
+        exact: false,
+        description: "vacuum electric permittivity",
+    },
+    Constant {
+        names: &["μ₀", "mu0"],
+        value: 1.256_637_062_12e-6,
+        unit: "N/A2",
+        exact: false,
+        description: "vacuum magnetic permeability",
+    },
+    Constant {
+        names: &["g₀", "g0"],
+        value: 9.806_65,
+        unit: "m/s2",
+        exact: true,
+        description: "standard acceleration of gravity",
+    },
+    Constant {
+        names: &["atm"],
+        value: 101_325.0,
+        unit: "Pa",
+        exact: true,
+        description: "standard atmosphere",
+    },
+];
+
+impl Constant {
+    pub fn name(&self) -> &'static str {
+        self.names[0]
+    }
+
+    /// Значение с единицей для вычислений
+    pub fn quantity(&self) -> Quantity {
+        // Единицы таблицы проверяются тестом, разбор не может упасть
+        let unit = CompoundUnit::parse(self.unit, &UnitRegistry::new())
+            .unwrap_or_else(|e| panic!("constant {}: {}", self.name(), e));
+        Quantity::new(self.value, unit)
+    }
+
+    pub fn dimension(&self) -> DimensionVector {
+        self.quantity().dimension()
+    }
+
+    /// Для проверки типов: `c` — Velocity, у `G` имени нет
+    pub fn named_dimension(&self) -> Option<Dimension> {
+        self.quantity().named_dimension()
+    }
+}
+
+/// Поиск по любому из имен: `ħ` и `hbar` — одна постоянная
+pub fn constant(name: &str) -> Option<&'static Constant> {
+    CONSTANTS.iter().find(|c| c.names.contains(&name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::types::units::{Quantity, UnitValue};
+
+    fn get(name: &str) -> Quantity {
+        constant(name).unwrap().quantity()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual / expected - 1.0).abs() < 1e-12,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn constants_carry_dimensions() {
+        let expected = [
+            ("c", Some(Dimension::Velocity)),
+            ("G", None),
+            ("h", None),
+            ("k_B", None),
+            ("e", Some(Dimension::ElectricCharge)),
+            ("ε₀", None),
+            ("g0", Some(Dimension::Acceleration)),
+            ("atm", Some(Dimension::Pressure)),
+        ];
+        for (name, dimension) in expected {
+            assert_eq!(
+                constant(name).unwrap().named_dimension(),
+                dimension,
+                "{}",
+                name
+            );
+        }
+        assert_eq!(get("G").dimension().to_string(), "m³·kg⁻¹·s⁻²");
+        assert_eq!(constant("hbar"), constant("ħ"));
+        assert_eq!(constant("π"), None);
+
+        // Все единицы таблицы разбираются
+        for c in CONSTANTS {
+            c.quantity();
+        }
+    }
+
+    #[test]
+    fn constants_in_expressions() {
+        let registry = UnitRegistry::new();
+        let value = |number: f64, suffix: &str| {
+            let (factor, def) = registry.resolve(suffix).unwrap();
+            Quantity::from_unit_value(&UnitValue::new(number * factor, def.clone())).unwrap()
+        };
+
+        // const e = m * c^2
+        let energy = value(1.0, "kg")
+            .multiply(&get("c").powi(2).unwrap())
+            .unwrap();
+        assert_eq!(energy.named_dimension(), Some(Dimension::Energy));
+        assert_close(energy.to_coherent(), 8.987_551_787_368_176e16);
+
+        // weight = 70kg * g0
+        let weight = value(70.0, "kg").multiply(&get("g0")).unwrap();
+        let newtons = weight.to_unit_value().unwrap();
+        assert_eq!(newtons.unit.dimension, Dimension::Force);
+        assert_close(newtons.to_base().unwrap(), 686.4655);
+
+        // c² = 1 / (ε₀·μ₀)
+        let c_squared = get("ε₀")
+            .multiply(&get("μ₀"))
+            .and_then(|q| q.powi(-1))
+            .unwrap();
+        let c = get("c").powi(2).unwrap();
+        assert_eq!(c_squared.dimension(), c.dimension());
+        assert!((c_squared.to_coherent() / c.to_coherent() - 1.0).abs() < 1e-9);
+
+        // Энергия фотона E = h·ν, ν в Гц
+        let photon = get("h").multiply(&value(5.0e14, "Hz")).unwrap();
+        assert_eq!(photon.named_dimension(), Some(Dimension::Energy));
+    }
+}
@@ -0,0 +1,388 @@
+use super::unit_value::UnitValue;
+use crate::shared::unit::conversion::ConversionError;
+use crate::shared::unit::definition::UnitDef;
+use crate::shared::unit::dimensions::Dimension;
+use crate::shared::unit::units::find_unit;
+use std::fmt;
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 3600.0;
+const DAY: f64 = 86400.0;
+
+/// Как считать месяцы и годы в секундах. У календарных единиц нет
+/// постоянной длины, поэтому любой такой перевод сопровождается предупреждением.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CalendarConvention {
+    /// Средний григорианский год, 365.2425 дня (так заданы `month` и `year` в реестре)
+    #[default]
+    Gregorian,
+    /// Юлианский год, 365.25 дня (астрономия)
+    Julian,
+    /// Месяц 30 дней, год 360 (финансовые расчеты)
+    ThirtyDayMonth,
+}
+
+impl CalendarConvention {
+    pub fn month_seconds(self) -> f64 {
+        match self {
+            CalendarConvention::Gregorian => 365.2425 * DAY / 12.0,
+            CalendarConvention::Julian => 365.25 * DAY / 12.0,
+            CalendarConvention::ThirtyDayMonth => 30.0 * DAY,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            CalendarConvention::Gregorian => "Gregorian mean year (365.2425 days)",
+            CalendarConvention::Julian => "Julian year (365.25 days)",
+            CalendarConvention::ThirtyDayMonth => "30-day month (360-day year)",
+        }
+    }
+}
+
+/// Календарная часть приведена к точной длине по соглашению
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarWarning {
+    pub months: f64,
+    pub convention: CalendarConvention,
+}
+
+impl fmt::Display for CalendarWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Calendar duration of {} months has no fixed length, assuming {}",
+            self.months,
+            self.convention.describe()
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DurationError {
+    /// Запись не соответствует ISO 8601 (`PT1H30M`, `P1Y2M`)
+    InvalidIso(String),
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationError::InvalidIso(text) => write!(f, "Invalid ISO 8601 duration '{}'", text),
+        }
+    }
+}
+
+/// Длительность из двух частей: календарной (месяцы, годы) и точной (секунды).
+/// `P1Y2M` — 14 месяцев без точной длины, `PT1H30M` — ровно 5400 секунд.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Duration {
+    pub months: f64,
+    pub seconds: f64,
+}
+
+impl Duration {
+    pub fn is_exact(&self) -> bool {
+        self.months == 0.0
+    }
+
+    /// Полная длина в секундах; календарная часть — по соглашению, с предупреждением
+    pub fn to_seconds(&self, convention: CalendarConvention) -> (f64, Option<CalendarWarning>) {
+        if self.is_exact() {
+            return (self.seconds, None);
+        }
+        let warning = CalendarWarning {
+            months: self.months,
+            convention,
+        };
+        (
+            self.months * convention.month_seconds() + self.seconds,
+            Some(warning),
+        )
+    }
+
+    /// Разбор `PnYnMnWnDTnHnMnS`; дробной может быть только последняя часть.
+    /// Знак части (`P1M-1D`) допускается для длительностей со смешанными знаками.
+    pub fn parse_iso8601(text: &str) -> Result<Self, DurationError> {
+        let invalid = || DurationError::InvalidIso(text.to_string());
+        let (sign, rest) = match text.strip_prefix('-') {
+            Some(rest) => (-1.0, rest),
+            None => (1.0, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+        let mut duration = Duration::default();
+        let mut in_time = false;
+        let mut number = String::new();
+        let mut seen_fraction = false;
+        // Порядок обозначений фиксирован: нельзя `P1D2Y`
+        let mut last_rank = 0;
+
+        for c in rest.chars() {
+            match c {
+                '0'..='9' => number.push(c),
+                '-' if number.is_empty() => number.push(c),
+                '.' | ',' => number.push('.'),
+                'T' if !in_time && number.is_empty() => {
+                    in_time = true;
+                    last_rank = 4;
+                }
+                designator => {
+                    if number.is_empty() || seen_fraction {
+                        return Err(invalid());
+                    }
+                    let value: f64 = number.parse().map_err(|_| invalid())?;
+                    seen_fraction = number.contains('.');
+                    number.clear();
+
+                    let (rank, months, seconds) = match (in_time, designator) {
+                        (false, 'Y') => (1, 12.0, 0.0),
+                        (false, 'M') => (2, 1.0, 0.0),
+                        (false, 'W') => (3, 0.0, 7.0 * DAY),
+                        (false, 'D') => (4, 0.0, DAY),
+                        (true, 'H') => (5, 0.0, HOUR),
+                        (true, 'M') => (6, 0.0, MINUTE),
+                        (true, 'S') => (7, 0.0, 1.0),
+                        _ => return Err(invalid()),
+                    };
+                    if rank <= last_rank {
+                        return Err(invalid());
+                    }
+                    last_rank = rank;
+                    duration.months += value * months;
+                    duration.seconds += value * seconds;
+                }
+            }
+        }
+
+        // `P`, `PT`, `P1`
+        if !number.is_empty() || last_rank == 0 || (in_time && last_rank == 4) {
+            return Err(invalid());
+        }
+        duration.months *= sign;
+        duration.seconds *= sign;
+        Ok(duration)
+    }
+
+    /// Запись ISO 8601: годы и месяцы из календарной части, дни..секунды из точной.
+    /// Недели не используются: `P1W2D` стандарт не допускает.
+    /// Знак записи задает календарная часть; точная часть с обратным знаком
+    /// получает свой минус у каждого числа: 1 месяц без суток — `P1M-1D`.
+    pub fn to_iso8601(&self) -> String {
+        let negative = self.months < 0.0 || (self.months == 0.0 && self.seconds < 0.0);
+        let sign = if negative { -1.0 } else { 1.0 };
+        let months = self.months.abs();
+        let time_sign = (self.seconds * sign).signum();
+        let mut seconds = self.seconds.abs();
+
+        let mut out = String::from(if negative { "-P" } else { "P" });
+        let years = (months / 12.0).trunc();
+        push_part(&mut out, years, 'Y');
+        push_part(&mut out, months - years * 12.0, 'M');
+
+        let days = (seconds / DAY).trunc();
+        seconds -= days * DAY;
+        push_part(&mut out, days * time_sign, 'D');
+
+        if seconds > 0.0 {
+            out.push('T');
+            let hours = (seconds / HOUR).trunc();
+            seconds -= hours * HOUR;
+            let minutes = (seconds / MINUTE).trunc();
+            seconds -= minutes * MINUTE;
+            push_part(&mut out, hours * time_sign, 'H');
+            push_part(&mut out, minutes * time_sign, 'M');
+            // Шум деления (0.1 + 0.2) не должен попадать в запись
+            push_part(&mut out, (seconds * 1e9).round() / 1e9 * time_sign, 'S');
+        }
+
+        if out.ends_with('P') {
+            out.push_str("0D");
+        }
+        out
+    }
+}
+
+fn push_part(out: &mut String, value: f64, designator: char) {
+    if value != 0.0 {
+        out.push_str(&value.to_string());
+        out.push(designator);
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_iso8601())
+    }
+}
+
+fn months_per(unit: &UnitDef) -> f64 {
+    if unit.symbol == "year" { 12.0 } else { 1.0 }
+}
+
+/// Перевод между единицами времени: календарные между собой (`1year` -> `12month`)
+/// и точные между собой переводятся без оговорок, смешанные — по соглашению.
+/// `UnitDef::convert_to` смешанный перевод отклоняет: соглашения у него нет.
+pub fn convert_time(
+    value: f64,
+    from: &UnitDef,
+    to: &UnitDef,
+    convention: CalendarConvention,
+) -> Result<(f64, Option<CalendarWarning>), ConversionError> {
+    let duration = UnitValue::new(value, from.clone()).to_duration()?;
+    if to.dimension != Dimension::Time {
+        return Err(ConversionError::DimensionMismatch {
+            from: from.dimension,
+            to: to.dimension,
+        });
+    }
+
+    if to.is_calendar() && duration.is_exact() {
+        let months = duration.seconds / convention.month_seconds();
+        let warning = CalendarWarning { months, convention };
+        return Ok((months / months_per(to), Some(warning)));
+    }
+    if to.is_calendar() {
+        return Ok((duration.months / months_per(to), None));
+    }
+
+    let (seconds, warning) = duration.to_seconds(convention);
+    Ok((to.denormalize(seconds), warning))
+}
+
+impl UnitValue {
+    /// Длительность из значения времени; `month`/`year` дают календарную часть
+    pub fn to_duration(&self) -> Result<Duration, ConversionError> {
+        if self.unit.dimension != Dimension::Time {
+            return Err(ConversionError::DimensionMismatch {
+                from: self.unit.dimension,
+                to: Dimension::Time,
+            });
+        }
+        if self.unit.is_calendar() {
+            return Ok(Duration {
+                months: self.value * months_per(&self.unit),
+                seconds: 0.0,
+            });
+        }
+        Ok(Duration {
+            months: 0.0,
+            seconds: self.to_base()?,
+        })
+    }
+
+    /// Обратно к значению: календарная длительность — в месяцах, точная — в секундах.
+    /// Смешанная (`P1MT1H`) единицей не выражается без соглашения.
+    pub fn from_duration(
+        duration: &Duration,
+        convention: CalendarConvention,
+    ) -> (UnitValue, Option<CalendarWarning>) {
+        let unit = |symbol| find_unit(symbol).unwrap().clone();
+        if duration.seconds == 0.0 && duration.months != 0.0 {
+            return (UnitValue::new(duration.months, unit("month")), None);
+        }
+        let (seconds, warning) = duration.to_seconds(convention);
+        (UnitValue::new(seconds, unit("s")), warning)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso(text: &str) -> Duration {
+        Duration::parse_iso8601(text).unwrap()
+    }
+
+    #[test]
+    fn iso8601_round_trip() {
+        assert_eq!(iso("PT1H30M").seconds, 5400.0);
+        assert_eq!(
+            iso("P1Y2M"),
+            Duration {
+                months: 14.0,
+                seconds: 0.0
+            }
+        );
+        assert_eq!(iso("P2W").seconds, 14.0 * DAY);
+        assert_eq!(iso("PT0,5S").seconds, 0.5);
+        assert_eq!(iso("-P1D").seconds, -DAY);
+
+        for text in ["PT1H30M", "P1Y2M", "P3DT4H5M6.5S", "-P1Y", "P0D"] {
+            assert_eq!(iso(text).to_iso8601(), text);
+        }
+
+        // Части с разными знаками: знак точной части не теряется
+        let mixed = Duration {
+            months: 1.0,
+            seconds: -DAY,
+        };
+        assert_eq!(mixed.to_iso8601(), "P1M-1D");
+        assert_eq!(iso("P1M-1D"), mixed);
+        let mixed = Duration {
+            months: -1.0,
+            seconds: HOUR,
+        };
+        assert_eq!(mixed.to_iso8601(), "-P1MT-1H");
+        assert_eq!(iso("-P1MT-1H"), mixed);
+
+        for text in [
+            "", "P", "PT", "1H", "P1H", "PT1D", "P1D2Y", "P1.5Y2M", "P1", "P-T1H", "P--1D",
+        ] {
+            assert!(Duration::parse_iso8601(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn calendar_conversions_warn() {
+        let unit = |symbol| find_unit(symbol).unwrap();
+
+        // Точные единицы и календарные между собой — без предупреждения
+        assert_eq!(
+            convert_time(90.0, unit("min"), unit("hour"), CalendarConvention::Julian),
+            Ok((1.5, None))
+        );
+        assert_eq!(
+            convert_time(2.0, unit("year"), unit("month"), CalendarConvention::Julian),
+            Ok((24.0, None))
+        );
+
+        let (days, warning) =
+            convert_time(1.0, unit("year"), unit("day"), CalendarConvention::Julian).unwrap();
+        assert!((days - 365.25).abs() < 1e-9);
+        assert!(warning.unwrap().to_string().contains("Julian year"));
+
+        let (months, warning) = convert_time(
+            90.0,
+            unit("day"),
+            unit("month"),
+            CalendarConvention::ThirtyDayMonth,
+        )
+        .unwrap();
+        assert!((months - 3.0).abs() < 1e-9);
+        assert!(warning.is_some());
+
+        let value = UnitValue::new(1.5, unit("hour").clone());
+        assert_eq!(value.to_duration().unwrap().to_iso8601(), "PT1H30M");
+        let calendar = UnitValue::new(14.0, unit("month").clone());
+        assert_eq!(calendar.to_duration().unwrap().to_iso8601(), "P1Y2M");
+        assert!(
+            UnitValue::new(1.0, unit("m").clone())
+                .to_duration()
+                .is_err()
+        );
+
+        // Без соглашения календарные единицы не переводятся в точные
+        assert_eq!(
+            unit("month").convert_to(1.0, unit("day")),
+            Err(ConversionError::CalendarConvention {
+                from: "month".into(),
+                to: "day".into(),
+            })
+        );
+        assert!(unit("s").convert_delta_to(1.0, unit("year")).is_err());
+        assert_eq!(unit("year").convert_to(1.0, unit("month")), Ok(12.0));
+        let month = UnitValue::new(1.0, unit("month").clone());
+        let day = UnitValue::new(1.0, unit("day").clone());
+        assert!(crate::interpreter::evaluator::unit_ops::add(&month, &day).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use super::unit_value::UnitValue;
+use crate::shared::unit::angle::format_dms;
+use crate::shared::unit::conversion::ConversionError;
+use crate::shared::unit::definition::{PrefixGroup, UnitDef};
+use crate::shared::unit::dimensions::Dimension;
+use crate::shared::unit::prefixes::PREFIXES;
+use crate::shared::unit::registry::UnitRegistry;
+use crate::shared::unit::units::find_unit;
+
+/// Запись числа при выводе
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// Подбор приставки: `10000kΩ` -> `10MΩ`
+    #[default]
+    Prefixed,
+    /// Без приставки, порядок кратен трем: `10e6Ω`
+    Engineering,
+    /// Как есть, в единице значения
+    Plain,
+    /// Углы в градусах-минутах-секундах: `45°30′15″`
+    Dms,
+}
+
+/// Настройки вывода значения с единицей
+#[derive(Debug, Clone, Default)]
+pub struct UnitFormat {
+    pub notation: Notation,
+    /// Фиксированная единица вывода (`kΩ`, `°F`), отменяет подбор приставки
+    pub target: Option<String>,
+    /// Число значащих цифр; без него — кратчайшая запись без шума округления.
+    /// Для `Notation::Dms` — знаков после запятой у секунд.
+    pub significant_digits: Option<usize>,
+    /// IEC-приставки (Ki, Mi...) для объемов данных вместо десятичных
+    pub binary: bool,
+}
+
+impl UnitValue {
+    /// Вывод по встроенному реестру
+    pub fn format(&self, options: &UnitFormat) -> Result<String, ConversionError> {
+        self.format_in(options, &UnitRegistry::new())
+    }
+
+    /// Вывод с учетом единиц, объявленных в документе (для `target`)
+    pub fn format_in(
+        &self,
+        options: &UnitFormat,
+        registry: &UnitRegistry,
+    ) -> Result<String, ConversionError> {
+        let digits = options.significant_digits;
+        let delta = if self.is_delta() && self.unit.is_affine() {
+            "Δ"
+        } else {
+            ""
+        };
+
+        if let Some(target) = &options.target {
+            let (factor, def) = registry
+                .resolve(target)
+                .ok_or_else(|| ConversionError::UnknownUnit(target.clone()))?;
+            let value = self.convert_to(def)?.value / factor;
+            let delta = if self.is_delta() && def.is_affine() {
+                "Δ"
+            } else {
+                ""
+            };
+            return Ok(format!(
+                "{}{}{}",
+                delta,
+                render_number(value, digits),
+                target
+            ));
+        }
+
+        let text = match options.notation {
+            Notation::Plain => format!("{}{}", render_number(self.value, digits), self.unit.symbol),
+            Notation::Engineering => {
+                format!(
+                    "{}{}",
+                    render_engineering(self.value, digits),
+                    self.unit.symbol
+                )
+            }
+            Notation::Dms => {
+                if self.unit.dimension != Dimension::Angle {
+                    return Err(ConversionError::DimensionMismatch {
+                        from: self.unit.dimension,
+                        to: Dimension::Angle,
+                    });
+                }
+                let degrees = self.convert_to(find_unit("°").unwrap())?.value;
+                format_dms(degrees, digits.unwrap_or(0))
+            }
+            Notation::Prefixed => {
+                let (value, prefix) = best_prefix(self.value, &self.unit, options);
+                format!(
+                    "{}{}",
+                    render_number(value, digits),
+                    prefixed_symbol(&self.unit, prefix)
+                )
+            }
+        };
+        Ok(format!("{}{}", delta, text))
+    }
+}
+
+/// Допустимые приставки единицы по убыванию множителя, включая пустую.
+/// Шкалы со смещением (°C) и единицы без группы (mph, px) не получают приставок.
+fn prefix_candidates(unit: &UnitDef, binary: bool) -> Vec<(&'static str, f64)> {
+    if unit.is_affine() {
+        return Vec::new();
+    }
+
+    let group = match unit.numerator_group {
+        PrefixGroup::SI | PrefixGroup::Metric | PrefixGroup::Digital => unit.numerator_group,
+        PrefixGroup::Thermal | PrefixGroup::None => return Vec::new(),
+    };
+
+    let mut candidates: Vec<(&'static str, f64)> = PREFIXES
+        .iter()
+        .filter(|(_, _, g)| *g == group)
+        // Для СИ только кратные тысяче: без h, da, d, c
+        .filter(|(_, factor, _)| group != PrefixGroup::SI || factor.log10().round() as i32 % 3 == 0)
+        // Для данных — либо десятичные (kB), либо IEC (KiB): обе части в группе Digital
+        .filter(|(_, factor, _)| {
+            group != PrefixGroup::Digital || (factor.log2().fract() == 0.0) == binary
+        })
+        .map(|(symbol, factor, _)| (*symbol, *factor))
+        .chain(std::iter::once(("", 1.0)))
+        .collect();
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    candidates
+}
+
+fn best_prefix(value: f64, unit: &UnitDef, options: &UnitFormat) -> (f64, &'static str) {
+    let candidates = prefix_candidates(unit, options.binary);
+    if candidates.is_empty() || value == 0.0 || !value.is_finite() {
+        return (value, "");
+    }
+
+    let magnitude = value.abs();
+    let mut index = candidates
+        .iter()
+        .position(|(_, factor)| *factor <= magnitude)
+        .unwrap_or(candidates.len() - 1);
+
+    // После округления 999.96 может стать 1000 — тогда берем приставку крупнее
+    if index > 0 {
+        let (_, larger) = candidates[index - 1];
+        let rounded =
+            round_significant(magnitude / candidates[index].1, options.significant_digits);
+        if rounded * candidates[index].1 >= larger {
+            index -= 1;
+        }
+    }
+
+    let (prefix, factor) = candidates[index];
+    (value / factor, prefix)
+}
+
+fn prefixed_symbol(unit: &UnitDef, prefix: &str) -> String {
+    match unit.parts {
+        Some((numerator, denominator)) if !prefix.is_empty() => {
+            format!("{}{}/{}", prefix, numerator, denominator)
+        }
+        _ => format!("{}{}", prefix, unit.symbol),
+    }
+}
+
+/// Без заданной точности — 12 значащих цифр, чтобы скрыть шум (0.1 * 3)
+fn round_significant(value: f64, digits: Option<usize>) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let digits = digits.unwrap_or(12).max(1) as i32;
+    let magnitude = value.abs().log10().floor() as i32;
+    let scale = 10f64.powi(digits - 1 - magnitude);
+    (value * scale).round() / scale
+}
+
+fn render_number(value: f64, digits: Option<usize>) -> String {
+    let rounded = round_significant(value, digits);
+    match digits {
+        // Значащие нули сохраняются: 3 цифры -> "1.00"
+        Some(n) if rounded != 0.0 && rounded.is_finite() => {
+            let magnitude = rounded.abs().log10().floor() as i32;
+            let decimals = (n.max(1) as i32 - 1 - magnitude).max(0) as usize;
+            format!("{:.*}", decimals, rounded)
+        }
+        _ => rounded.to_string(),
+    }
+}
+
+fn render_engineering(value: f64, digits: Option<usize>) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return render_number(value, digits);
+    }
+
+    let mut exponent = (value.abs().log10().floor() as i32).div_euclid(3) * 3;
+    let mut mantissa = value / 10f64.powi(exponent);
+    if round_significant(mantissa, digits).abs() >= 1000.0 {
+        mantissa /= 1000.0;
+        exponent += 3;
+    }
+
+    match exponent {
+        0 => render_number(mantissa, digits),
+        _ => format!("{}e{}", render_number(mantissa, digits), exponent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::types::units::QuantityKind;
+
+    /// Значение из суффикса с приставкой, как его дает сканер: `10000kΩ`
+    fn value(number: f64, suffix: &str) -> UnitValue {
+        let registry = UnitRegistry::new();
+        let (factor, def) = registry.resolve(suffix).unwrap();
+        UnitValue::new(number * factor, def.clone())
+    }
+
+    fn show(value: &UnitValue, options: UnitFormat) -> String {
+        value.format(&options).unwrap()
+    }
+
+    #[test]
+    fn picks_best_prefix() {
+        let auto = UnitFormat::default;
+
+        assert_eq!(show(&value(10_000.0, "kΩ"), auto()), "10MΩ");
+        assert_eq!(show(&value(0.1, "kL"), auto()), "100L");
+        assert_eq!(show(&value(0.000_25, "s"), auto()), "250μs");
+        assert_eq!(show(&value(5_000.0, "m/s"), auto()), "5km/s");
+        assert_eq!(show(&value(0.0, "m"), auto()), "0m");
+
+        // Шкалы со смещением и единицы без группы приставок не получают
+        assert_eq!(show(&value(25_000.0, "°C"), auto()), "25000°C");
+        assert_eq!(show(&value(0.001, "mi/h"), auto()), "0.001mi/h");
+    }
+
+    #[test]
+    fn significant_digits_and_engineering() {
+        let digits = |n| UnitFormat {
+            significant_digits: Some(n),
+            ..UnitFormat::default()
+        };
+        assert_eq!(show(&value(999.96, "Ω"), digits(3)), "1.00kΩ");
+        assert_eq!(show(&value(1234.5, "m"), digits(2)), "1.2km");
+
+        let engineering = UnitFormat {
+            notation: Notation::Engineering,
+            ..UnitFormat::default()
+        };
+        assert_eq!(
+            show(&value(12_345.0, "Ω"), engineering.clone()),
+            "12.345e3Ω"
+        );
+        assert_eq!(show(&value(0.0042, "°C"), engineering), "4.2e-3°C");
+    }
+
+    #[test]
+    fn fixed_target_and_binary_prefixes() {
+        let target = |unit: &str| UnitFormat {
+            target: Some(unit.to_string()),
+            ..UnitFormat::default()
+        };
+        assert_eq!(show(&value(10.0, "MΩ"), target("kΩ")), "10000kΩ");
+        assert_eq!(show(&value(100.0, "°C"), target("°F")), "212°F");
+
+        let mut diff = value(10.0, "°C");
+        diff.kind = QuantityKind::Delta;
+        assert_eq!(show(&diff, target("°F")), "Δ18°F");
+
+        assert!(matches!(
+            value(1.0, "s").format(&target("m")),
+            Err(ConversionError::DimensionMismatch { .. })
+        ));
+
+        let binary = UnitFormat {
+            binary: true,
+            ..UnitFormat::default()
+        };
+        assert_eq!(show(&value(1536.0, "B"), binary), "1.5KiB");
+    }
+
+    #[test]
+    fn angles_in_dms() {
+        let dms = UnitFormat {
+            notation: Notation::Dms,
+            ..UnitFormat::default()
+        };
+        let quarter = value(0.25, "turn");
+        assert_eq!(show(&quarter, dms.clone()), "90°0′0″");
+        assert_eq!(show(&value(50.0, "grad"), dms.clone()), "45°0′0″");
+        assert_eq!(show(&value(1.0, "rad"), dms.clone()), "57°17′45″");
+        assert!(value(1.0, "m").format(&dms).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+pub mod duration;
+pub mod format;
+pub mod quantity;
+pub mod uncertain;
+pub mod unit_value;
+
+pub use duration::{CalendarConvention, CalendarWarning, Duration};
+pub use format::{Notation, UnitFormat};
+pub use quantity::Quantity;
+pub use uncertain::{UncertainRecord, UncertainValue, Uncertainty};
+pub use unit_value::{QuantityKind, UnitValue};
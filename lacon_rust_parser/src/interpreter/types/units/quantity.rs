@@ -0,0 +1,96 @@
+use super::unit_value::UnitValue;
+use crate::shared::unit::compound::{CompoundError, CompoundUnit};
+use crate::shared::unit::conversion::ConversionError;
+use crate::shared::unit::dimensions::{Dimension, DimensionVector};
+use std::fmt;
+
+/// Величина с произвольным вектором размерности. В отличие от `UnitValue`
+/// промежуточный результат может не иметь имени: в `m * c^2` множитель
+/// `c^2` (m²·s⁻²) безымянный, а итог — энергия.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    /// Значение в единице `unit`
+    pub value: f64,
+    pub unit: CompoundUnit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: CompoundUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Точка на шкале со смещением (20°C) не умножается; разность (Δ20°C) — да
+    pub fn from_unit_value(value: &UnitValue) -> Result<Self, CompoundError> {
+        if value.unit.is_affine() && !value.is_delta() {
+            return Err(CompoundError::NonLinearUnit(value.unit.symbol.to_string()));
+        }
+        Ok(Self::new(
+            value.value,
+            CompoundUnit::from_unit(&value.unit)?,
+        ))
+    }
+
+    pub fn dimension(&self) -> DimensionVector {
+        self.unit.dimension
+    }
+
+    pub fn named_dimension(&self) -> Option<Dimension> {
+        self.unit.named_dimension()
+    }
+
+    /// Значение в когерентных единицах СИ (kg, m, s...)
+    pub fn to_coherent(&self) -> f64 {
+        self.value * self.unit.scale
+    }
+
+    /// Обратно к значению с именованной размерностью
+    pub fn to_unit_value(&self) -> Option<UnitValue> {
+        Some(UnitValue::new(self.value, self.unit.to_unit_def()?))
+    }
+
+    pub fn convert_to(&self, target: &CompoundUnit) -> Result<Quantity, ConversionError> {
+        let value = self.unit.convert_to(self.value, target)?;
+        Ok(Self::new(value, target.clone()))
+    }
+
+    pub fn add(&self, other: &Quantity) -> Result<Quantity, ConversionError> {
+        let other = other.convert_to(&self.unit)?;
+        Ok(Self::new(self.value + other.value, self.unit.clone()))
+    }
+
+    pub fn subtract(&self, other: &Quantity) -> Result<Quantity, ConversionError> {
+        let other = other.convert_to(&self.unit)?;
+        Ok(Self::new(self.value - other.value, self.unit.clone()))
+    }
+
+    pub fn multiply(&self, other: &Quantity) -> Result<Quantity, CompoundError> {
+        Ok(Self::new(
+            self.value * other.value,
+            self.unit.combine(&other.unit, false)?,
+        ))
+    }
+
+    pub fn divide(&self, other: &Quantity) -> Result<Quantity, CompoundError> {
+        Ok(Self::new(
+            self.value / other.value,
+            self.unit.combine(&other.unit, true)?,
+        ))
+    }
+
+    pub fn powi(&self, exponent: i8) -> Result<Quantity, CompoundError> {
+        Ok(Self::new(
+            self.value.powi(exponent as i32),
+            self.unit.powi(exponent)?,
+        ))
+    }
+
+    pub fn scale(&self, factor: f64) -> Quantity {
+        Self::new(self.value * factor, self.unit.clone())
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.symbol)
+    }
+}
@@ -0,0 +1,335 @@
+use super::unit_value::UnitValue;
+use crate::interpreter::error::{RuntimeError, RuntimeErrorType};
+use crate::interpreter::evaluator::unit_ops;
+use crate::shared::unit::conversion::ConversionError;
+use crate::shared::unit::definition::UnitDef;
+use crate::shared::unit::dimensions::Dimension;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+// Значение с допуском: `5m ± 2cm`, `12V ± 5%`.
+// `∓` задает тот же интервал: знак важен только для коррелированных величин,
+// а распространение здесь — для независимых (первый порядок):
+//   a ± b     -> σ = √(σa² + σb²)
+//   a · b     -> σ = √((b·σa)² + (a·σb)²)
+//   a / b     -> σ = √((σa/b)² + (a·σb/b²)²)
+//   aⁿ        -> σ = |n·aⁿ⁻¹| · σa
+// Форма абсолютная: деление на значение дало бы NaN у нуля (0m ± 1mm).
+// Если единица результата не произведение единиц операндов (ft / m -> ratio),
+// σ умножается на ее масштаб.
+
+/// Как записан допуск; при выводе сохраняется форма записи
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Uncertainty {
+    /// В единицах значения
+    Absolute(f64),
+    /// Доля значения (0.05 для `± 5%`)
+    Relative(f64),
+}
+
+#[derive(Debug, Clone)]
+pub struct UncertainValue {
+    pub value: UnitValue,
+    pub uncertainty: Uncertainty,
+}
+
+impl UncertainValue {
+    pub fn new(value: UnitValue, uncertainty: Uncertainty) -> Self {
+        let uncertainty = match uncertainty {
+            Uncertainty::Absolute(u) => Uncertainty::Absolute(u.abs()),
+            Uncertainty::Relative(r) => Uncertainty::Relative(r.abs()),
+        };
+        Self { value, uncertainty }
+    }
+
+    /// Правая часть `±`: проценты дают относительный допуск,
+    /// остальное переводится в единицу значения как разность (`20°C ± 1°F`)
+    pub fn with_tolerance(
+        value: UnitValue,
+        tolerance: &UnitValue,
+    ) -> Result<Self, ConversionError> {
+        if tolerance.unit.dimension == Dimension::Percent && !tolerance.unit.is_logarithmic() {
+            let fraction = tolerance.to_base()?;
+            return Ok(Self::new(value, Uncertainty::Relative(fraction)));
+        }
+        let delta = UnitValue::delta(tolerance.value, tolerance.unit.clone());
+        let absolute = delta.convert_to(&value.unit)?.value;
+        Ok(Self::new(value, Uncertainty::Absolute(absolute)))
+    }
+
+    /// Допуск в единицах значения
+    pub fn absolute(&self) -> f64 {
+        match self.uncertainty {
+            Uncertainty::Absolute(u) => u,
+            Uncertainty::Relative(r) => r * self.value.value.abs(),
+        }
+    }
+
+    /// Допуск как доля значения; у нуля с ненулевым допуском — бесконечность
+    pub fn relative(&self) -> f64 {
+        match self.uncertainty {
+            Uncertainty::Relative(r) => r,
+            Uncertainty::Absolute(0.0) => 0.0,
+            Uncertainty::Absolute(u) => u / self.value.value.abs(),
+        }
+    }
+
+    pub fn convert_to(&self, target: &UnitDef) -> Result<UncertainValue, ConversionError> {
+        let value = self.value.convert_to(target)?;
+        let uncertainty = match self.uncertainty {
+            Uncertainty::Relative(r) => Uncertainty::Relative(r),
+            Uncertainty::Absolute(u) => {
+                let delta = UnitValue::delta(u, self.value.unit.clone());
+                Uncertainty::Absolute(delta.convert_to(target)?.value)
+            }
+        };
+        Ok(Self::new(value, uncertainty))
+    }
+
+    pub fn add(&self, other: &UncertainValue) -> Result<UncertainValue, RuntimeError> {
+        self.sum(other, false)
+    }
+
+    pub fn subtract(&self, other: &UncertainValue) -> Result<UncertainValue, RuntimeError> {
+        self.sum(other, true)
+    }
+
+    pub fn multiply(&self, other: &UncertainValue) -> Result<UncertainValue, RuntimeError> {
+        self.product(other, false)
+    }
+
+    pub fn divide(&self, other: &UncertainValue) -> Result<UncertainValue, RuntimeError> {
+        self.product(other, true)
+    }
+
+    /// Умножение на точное число: допуск масштабируется вместе со значением
+    pub fn scale(&self, factor: f64) -> UncertainValue {
+        let value = UnitValue {
+            value: self.value.value * factor,
+            ..self.value.clone()
+        };
+        let uncertainty = match self.uncertainty {
+            Uncertainty::Absolute(u) => Uncertainty::Absolute(u * factor),
+            relative => relative,
+        };
+        Self::new(value, uncertainty)
+    }
+
+    /// Натуральная степень: `(3m ± 1%)²` -> `9m·m ± 2%`
+    pub fn powi(&self, exponent: u32) -> Result<UncertainValue, RuntimeError> {
+        if exponent == 0 {
+            return Err(RuntimeError::new(
+                RuntimeErrorType::IncompatibleUnits,
+                format!("Cannot raise {} to the power 0", self),
+            ));
+        }
+        let one = unit_one(&self.value);
+        let mut value = self.value.clone();
+        let mut scale = one.clone();
+        for _ in 1..exponent {
+            value = unit_ops::multiply(&value, &self.value)?;
+            scale = unit_ops::multiply(&scale, &one)?;
+        }
+        let a = self.value.value;
+        let absolute =
+            exponent as f64 * a.powi(exponent as i32 - 1) * self.absolute() * scale.value;
+        Ok(Self::new(value, Uncertainty::Absolute(absolute)))
+    }
+
+    /// Запись для JSON; допуск всегда абсолютный, в единице значения
+    pub fn to_record(&self) -> UncertainRecord {
+        UncertainRecord {
+            value: self.value.value,
+            uncertainty: self.absolute(),
+            unit: self.value.unit.symbol.to_string(),
+        }
+    }
+
+    fn sum(&self, other: &UncertainValue, subtract: bool) -> Result<UncertainValue, RuntimeError> {
+        let value = if subtract {
+            unit_ops::subtract(&self.value, &other.value)?
+        } else {
+            unit_ops::add(&self.value, &other.value)?
+        };
+        // Допуск второго операнда — в единице результата
+        let other_uncertainty = UnitValue::delta(other.absolute(), other.value.unit.clone())
+            .convert_to(&value.unit)?
+            .value;
+        let absolute = self.absolute().hypot(other_uncertainty);
+        Ok(Self::new(value, Uncertainty::Absolute(absolute)))
+    }
+
+    fn product(
+        &self,
+        other: &UncertainValue,
+        divide: bool,
+    ) -> Result<UncertainValue, RuntimeError> {
+        let (value, scale) = if divide {
+            (
+                unit_ops::divide(&self.value, &other.value)?,
+                unit_ops::divide(&unit_one(&self.value), &unit_one(&other.value))?,
+            )
+        } else {
+            (
+                unit_ops::multiply(&self.value, &other.value)?,
+                unit_ops::multiply(&unit_one(&self.value), &unit_one(&other.value))?,
+            )
+        };
+        let (a, b) = (self.value.value, other.value.value);
+        let (sa, sb) = (self.absolute(), other.absolute());
+        let absolute = if divide {
+            (sa / b).hypot(a * sb / (b * b))
+        } else {
+            (b * sa).hypot(a * sb)
+        };
+        Ok(Self::new(
+            value,
+            Uncertainty::Absolute(absolute * scale.value),
+        ))
+    }
+}
+
+/// Единица значения с тем же видом (точка или разность): масштаб единицы результата
+fn unit_one(value: &UnitValue) -> UnitValue {
+    UnitValue {
+        value: 1.0,
+        ..value.clone()
+    }
+}
+
+impl fmt::Display for UncertainValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.uncertainty {
+            Uncertainty::Absolute(u) => {
+                write!(f, "{} ± {}{}", self.value, u, self.value.unit.symbol)
+            }
+            Uncertainty::Relative(r) => write!(f, "{} ± {}%", self.value, r * 100.0),
+        }
+    }
+}
+
+/// JSON: `{"value": 5, "uncertainty": 0.02, "unit": "m"}`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UncertainRecord {
+    pub value: f64,
+    pub uncertainty: f64,
+    pub unit: String,
+}
+
+impl Serialize for UncertainValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_record().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::scanner::Scanner;
+    use crate::lexer::token::Token;
+    use crate::lexer::token_type::TokenType;
+    use crate::shared::unit::registry::UnitRegistry;
+
+    fn value(number: f64, suffix: &str) -> UnitValue {
+        let registry = UnitRegistry::new();
+        let (factor, def) = registry.resolve(suffix).unwrap();
+        UnitValue::new(number * factor, def.clone())
+    }
+
+    fn from_token(token: &Token) -> UnitValue {
+        let number: f64 = token.literal.as_ref().unwrap().parse().unwrap();
+        value(number, token.get_unit_suffix())
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn tolerance_from_tokens() {
+        let mut scanner =
+            Scanner::new("a = 5m ± 2cm\nb = 12V +- 5%\nc = 3mm ∓ 0.1mm\nd = 1s -+ 1ms\n".into());
+        let tokens: Vec<Token> = scanner.scan_tokens().clone();
+
+        let values: Vec<UncertainValue> = tokens
+            .windows(3)
+            .filter(|w| matches!(w[1].token_type, TokenType::PlusMinus | TokenType::MinusPlus))
+            .map(|w| UncertainValue::with_tolerance(from_token(&w[0]), &from_token(&w[2])).unwrap())
+            .collect();
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0].uncertainty, Uncertainty::Absolute(0.02));
+        assert_eq!(values[1].uncertainty, Uncertainty::Relative(0.05));
+        assert_close(values[1].absolute(), 0.6);
+        assert_close(values[2].absolute(), 1e-4);
+        assert_close(values[3].absolute(), 1e-3);
+        assert_eq!(values[1].to_string(), "12V ± 5%");
+    }
+
+    #[test]
+    fn first_order_propagation() {
+        let absolute = |number, suffix, u| {
+            UncertainValue::new(value(number, suffix), Uncertainty::Absolute(u))
+        };
+
+        // Сумма: допуски складываются квадратично, в единице левого операнда
+        let sum = absolute(5.0, "m", 0.03)
+            .add(&absolute(40.0, "cm", 0.04))
+            .unwrap();
+        assert_close(sum.value.value, 5.4);
+        assert_close(sum.absolute(), 0.05);
+
+        // Произведение: квадратично складываются относительные допуски
+        let area = absolute(4.0, "m", 0.12)
+            .multiply(&absolute(2.0, "m", 0.08))
+            .unwrap();
+        assert_eq!(area.value.unit.dimension, Dimension::Area);
+        assert_close(area.value.value, 8.0);
+        assert_close(area.relative(), 0.05);
+
+        let square = absolute(3.0, "m", 0.03).powi(2).unwrap();
+        assert_close(square.value.value, 9.0);
+        assert_close(square.relative(), 0.02);
+
+        // Нулевое значение: допуск конечен, а не NaN
+        let zero = absolute(0.0, "m", 0.001)
+            .multiply(&absolute(2.0, "m", 0.0))
+            .unwrap();
+        assert_close(zero.value.value, 0.0);
+        assert_close(zero.absolute(), 0.002);
+        let ratio = absolute(0.0, "m", 0.001)
+            .divide(&absolute(2.0, "s", 0.1))
+            .unwrap();
+        assert_close(ratio.absolute(), 0.0005);
+        assert_close(absolute(0.0, "m", 0.001).powi(2).unwrap().absolute(), 0.0);
+
+        // Безразмерный результат в масштабе единиц: ft / m -> ratio
+        let mixed = absolute(1.0, "ft", 0.1)
+            .divide(&absolute(1.0, "m", 0.0))
+            .unwrap();
+        assert_close(mixed.value.value, 0.3048);
+        assert_close(mixed.absolute(), 0.03048);
+
+        let doubled = absolute(1.0, "s", 0.1).scale(-2.0);
+        assert_close(doubled.absolute(), 0.2);
+
+        let in_feet = absolute(5.0, "m", 0.02)
+            .convert_to(&value(1.0, "ft").unit)
+            .unwrap();
+        assert_close(in_feet.absolute(), 0.02 / 0.3048);
+    }
+
+    #[test]
+    fn serializes_as_record() {
+        let measured = UncertainValue::with_tolerance(value(12.0, "V"), &value(5.0, "%")).unwrap();
+        let record = measured.to_record();
+        assert_eq!(record.unit, "V");
+        assert_close(record.value, 12.0);
+        assert_close(record.uncertainty, 0.6);
+    }
+}
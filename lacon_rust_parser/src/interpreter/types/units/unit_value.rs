@@ -0,0 +1,95 @@
+use crate::shared::unit::conversion::ConversionError;
+use crate::shared::unit::definition::UnitDef;
+use crate::shared::unit::layout::LayoutContext;
+use std::fmt;
+
+/// Характер значения на аффинной шкале.
+/// `25°C` — точка на шкале (Absolute), `25°C - 20°C` — интервал (Delta).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantityKind {
+    Absolute,
+    Delta,
+}
+
+/// Число с единицей измерения (`25kg`, `10°C`)
+#[derive(Debug, Clone)]
+pub struct UnitValue {
+    pub value: f64,
+    pub unit: UnitDef,
+    pub kind: QuantityKind,
+}
+
+impl UnitValue {
+    pub fn new(value: f64, unit: UnitDef) -> Self {
+        Self {
+            value,
+            unit,
+            kind: QuantityKind::Absolute,
+        }
+    }
+
+    pub fn delta(value: f64, unit: UnitDef) -> Self {
+        Self {
+            value,
+            unit,
+            kind: QuantityKind::Delta,
+        }
+    }
+
+    pub fn is_delta(&self) -> bool {
+        self.kind == QuantityKind::Delta
+    }
+
+    /// Значение в базовой единице размерности
+    pub fn to_base(&self) -> Result<f64, ConversionError> {
+        match self.kind {
+            QuantityKind::Absolute => Ok(self.unit.normalize(self.value)),
+            QuantityKind::Delta => self
+                .unit
+                .normalize_delta(self.value)
+                .ok_or_else(|| ConversionError::NonLinearDelta(self.unit.symbol.to_string())),
+        }
+    }
+
+    /// Перевод в другую единицу с сохранением характера значения
+    pub fn convert_to(&self, target: &UnitDef) -> Result<UnitValue, ConversionError> {
+        let value = match self.kind {
+            QuantityKind::Absolute => self.unit.convert_to(self.value, target)?,
+            QuantityKind::Delta => self.unit.convert_delta_to(self.value, target)?,
+        };
+
+        Ok(Self {
+            value,
+            unit: target.clone(),
+            kind: self.kind,
+        })
+    }
+
+    /// Перевод единиц вёрстки (em -> px, fr -> px) в заданном контексте отрисовки
+    pub fn convert_in(
+        &self,
+        target: &UnitDef,
+        context: &LayoutContext,
+    ) -> Result<UnitValue, ConversionError> {
+        if !self.unit.props.basis.is_absolute() || !target.props.basis.is_absolute() {
+            // Смещений у единиц вёрстки нет, характер значения не важен
+            let value = self.unit.convert_in(self.value, target, context)?;
+            return Ok(Self {
+                value,
+                unit: target.clone(),
+                kind: self.kind,
+            });
+        }
+        self.convert_to(target)
+    }
+}
+
+impl fmt::Display for UnitValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_delta() && self.unit.is_affine() {
+            write!(f, "Δ{}{}", self.value, self.unit.symbol)
+        } else {
+            write!(f, "{}{}", self.value, self.unit.symbol)
+        }
+    }
+}
@@ -8,12 +8,14 @@ pub struct LexicalError {
     pub error_type: LexicalErrorType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LexicalErrorType {
     InvalidCharacter(char),
     UnterminatedString,
     UnterminatedBlockComment,
     InvalidIndent,
+    InvalidUnitDeclaration,
+    InvalidAngle,
 }
 
 impl fmt::Display for LexicalError {
@@ -25,3 +27,23 @@ impl fmt::Display for LexicalError {
         )
     }
 }
+
+/// Не ошибка: токен разобран, но запись стоит уточнить
+#[derive(Debug, Clone)]
+pub struct LexicalWarning {
+    pub message: String,
+    pub position: Position,
+    pub warning_type: LexicalWarningType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexicalWarningType {
+    /// Символ единицы значит разное в США и Британии (gal, ton, hp)
+    AmbiguousUnit(String),
+}
+
+impl fmt::Display for LexicalWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warning at {}: {}", self.position, self.message)
+    }
+}
@@ -6,7 +6,7 @@ pub mod scanner;
 pub mod token;
 pub mod token_type;
 
+pub use crate::shared::unit;
 pub use position::Position;
 pub use token::{Token, TokenFlags};
 pub use token_type::TokenType;
-pub use crate::shared::unit;
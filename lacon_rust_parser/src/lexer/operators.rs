@@ -110,6 +110,10 @@ pub fn match_operator(c1: char, c2: Option<char>, c3: Option<char>) -> OpMatch {
                 token_type: TokenType::Arrow,
                 consume_count: 1,
             },
+            Some('+') => OpMatch {
+                token_type: TokenType::MinusPlus,
+                consume_count: 1,
+            },
             _ => simple(TokenType::Minus),
         },
         '*' => match c2 {
@@ -187,11 +191,11 @@ pub fn match_operator(c1: char, c2: Option<char>, c3: Option<char>) -> OpMatch {
         },
         '>' => match c2 {
             Some('>') if c3 == Some('=') => OpMatch {
-                token_type: TokenType::LessLessEqual,
+                token_type: TokenType::GreaterGreaterEqual,
                 consume_count: 2,
             },
             Some('>') => OpMatch {
-                token_type: TokenType::LessLess,
+                token_type: TokenType::GreaterGreater,
                 consume_count: 1,
             },
             Some('=') => OpMatch {
@@ -278,6 +282,7 @@ pub fn match_operator(c1: char, c2: Option<char>, c3: Option<char>) -> OpMatch {
         '\u{2238}' => simple(TokenType::DotMinus),
         '\u{2214}' => simple(TokenType::DotPlus),
         '\u{00B1}' => simple(TokenType::PlusMinus),
+        '\u{2213}' => simple(TokenType::MinusPlus),
         '\u{00D7}' => simple(TokenType::Star),
         '\u{00F7}' => simple(TokenType::Slash),
         '\u{2260}' => simple(TokenType::BangEqual),
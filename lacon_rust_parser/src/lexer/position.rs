@@ -2,14 +2,14 @@ use std::fmt;
 
 /// Структура Position отслеживает точное местоположение в исходном коде.
 /// Используется для генерации сообщений об ошибках и в Source Maps.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Position {
     /// Номер строки (начиная с 1)
     pub line: usize,
-    
+
     /// Номер колонки (начиная with 1)
     pub column: usize,
-    
+
     /// Абсолютное смещение от начала файла в байтах
     pub offset: usize,
 }
@@ -25,7 +25,11 @@ impl Position {
     }
 
     pub fn new(line: usize, column: usize, offset: usize) -> Self {
-        Self { line, column, offset }
+        Self {
+            line,
+            column,
+            offset,
+        }
     }
 
     /// Обновляет позицию на основе прочитанного символа.
@@ -68,4 +72,4 @@ impl std::ops::Add<usize> for Position {
             offset: self.offset + rhs,
         }
     }
-}
\ No newline at end of file
+}
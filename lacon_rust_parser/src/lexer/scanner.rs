@@ -1,11 +1,20 @@
-use crate::lexer::error::{LexicalError, LexicalErrorType};
+// Исходный стиль лексера: `map_or(false, ..)`, `is_digit(10)`, вложенные `if let`
+#![allow(
+    clippy::collapsible_if,
+    clippy::is_digit_ascii_radix,
+    clippy::manual_range_contains,
+    clippy::unnecessary_map_or
+)]
+
+use crate::lexer::error::{LexicalError, LexicalErrorType, LexicalWarning, LexicalWarningType};
 use crate::lexer::keywords::get_keyword_token;
 use crate::lexer::operators::match_operator;
 use crate::lexer::position::Position;
 use crate::lexer::token::Token;
 use crate::lexer::token_type::TokenType;
-use crate::shared::unit::units::{UNITS, UNITS_TREE};
-use crate::utils::unit::get_unit_type;
+use crate::shared::unit::angle;
+use crate::shared::unit::compound;
+use crate::shared::unit::registry::UnitRegistry;
 
 pub struct Scanner {
     source: Vec<char>,
@@ -19,11 +28,30 @@ pub struct Scanner {
     string_stack: Vec<(char, bool)>,
     is_at_line_start: bool,
     had_whitespace: bool,
+    /// Единицы документа: встроенные + объявленные через `unit`
+    registry: UnitRegistry,
+    /// Глубина `context_stack`, на которой открыт блок `unit { ... }`
+    unit_block_depth: Option<usize>,
     pub errors: Vec<LexicalError>,
+    pub warnings: Vec<LexicalWarning>,
+}
+
+/// Разобранное (но еще не зарегистрированное) объявление `symbol<Dimension, Group> = value`
+struct UnitDeclaration {
+    symbol: String,
+    dimension: String,
+    group: Option<String>,
+    value: String,
+    base: String,
 }
 
 impl Scanner {
     pub fn new(source: String) -> Self {
+        Self::with_registry(source, UnitRegistry::new())
+    }
+
+    /// Сканер, которому заранее известны единицы (н-р, из импортированных документов)
+    pub fn with_registry(source: String, registry: UnitRegistry) -> Self {
         let start_pos = Position::start();
         Self {
             source: source.chars().collect(),
@@ -37,10 +65,17 @@ impl Scanner {
             string_stack: Vec::new(),
             is_at_line_start: true,
             had_whitespace: false, // На старте пробела нет
+            registry,
+            unit_block_depth: None,
             errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
+    pub fn registry(&self) -> &UnitRegistry {
+        &self.registry
+    }
+
     pub fn scan_tokens(&mut self) -> &Vec<Token> {
         self.add_token_raw(TokenType::SOF);
 
@@ -66,6 +101,10 @@ impl Scanner {
     }
 
     fn scan_token(&mut self) {
+        if self.at_unit_declaration_start() && self.scan_unit_declaration() {
+            return;
+        }
+
         let c = self.advance();
 
         match c {
@@ -93,6 +132,9 @@ impl Scanner {
                     '[' => TokenType::LeftBracket,
                     _ => TokenType::LeftBrace,
                 };
+                if c == '{' && self.last_token_type() == Some(&TokenType::UnitDecl) {
+                    self.unit_block_depth = Some(self.context_stack.len());
+                }
                 self.context_stack.push(t_type);
                 self.handle_operator(c);
             }
@@ -101,6 +143,9 @@ impl Scanner {
                 if !self.context_stack.is_empty() {
                     self.context_stack.pop();
                 }
+                if self.unit_block_depth == Some(self.context_stack.len()) {
+                    self.unit_block_depth = None;
+                }
                 self.handle_operator(c);
 
                 if c == '}' {
@@ -253,10 +298,171 @@ impl Scanner {
             }
         }
         let text = self.get_lexeme();
+        if text == "unit" && self.is_unit_declaration_keyword() {
+            self.add_token(TokenType::UnitDecl);
+            return;
+        }
         let t_type = get_keyword_token(&text).unwrap_or(TokenType::Identifier);
         self.add_token(t_type);
     }
 
+    // --- Объявления единиц: `unit tile<Length> = 16px`, `unit { ... }` ---
+
+    /// `unit` — ключевое слово, только если стоит в начале строки и за ним идет
+    /// полное объявление или блок; иначе это обычный ключ (`unit: px`).
+    fn is_unit_declaration_keyword(&self) -> bool {
+        if !self.is_at_line_start {
+            return false;
+        }
+        let mut pos = self.current;
+        if !matches!(self.source.get(pos), Some(' ') | Some('\t')) {
+            return false;
+        }
+        while matches!(self.source.get(pos), Some(' ') | Some('\t')) {
+            pos += 1;
+        }
+
+        if self.source.get(pos) == Some(&'{') {
+            // Блок — только если первая запись внутри является объявлением
+            pos += 1;
+            while matches!(self.source.get(pos), Some(c) if c.is_whitespace()) {
+                pos += 1;
+            }
+        }
+        self.parse_unit_declaration(pos).is_some()
+    }
+
+    fn at_unit_declaration_start(&self) -> bool {
+        match self.last_token_type() {
+            Some(TokenType::UnitDecl) => true,
+            Some(
+                TokenType::LeftBrace
+                | TokenType::Newline
+                | TokenType::Semicolon
+                | TokenType::Indent
+                | TokenType::Dedent,
+            ) => self
+                .unit_block_depth
+                .is_some_and(|depth| self.context_stack.len() == depth + 1),
+            _ => false,
+        }
+    }
+
+    /// Регистрирует единицу и выдает ее символ одним токеном-идентификатором
+    /// (символы вроде `px@2x` иначе распались бы на несколько токенов).
+    /// Остаток объявления сканируется как обычно.
+    fn scan_unit_declaration(&mut self) -> bool {
+        if !self.peek().is_some_and(is_unit_symbol_start) {
+            return false;
+        }
+
+        let Some(decl) = self.parse_unit_declaration(self.current) else {
+            self.errors.push(LexicalError {
+                message: "Expected unit declaration `symbol<Dimension> = value`".into(),
+                position: self.position,
+                error_type: LexicalErrorType::InvalidUnitDeclaration,
+            });
+            return false;
+        };
+
+        let value = decl.value.replace('_', "").parse::<f64>();
+        let result = match value {
+            Ok(value) => self
+                .registry
+                .declare(
+                    &decl.symbol,
+                    &decl.dimension,
+                    decl.group.as_deref(),
+                    value,
+                    &decl.base,
+                )
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(_) => Err(format!("Invalid unit value '{}'", decl.value)),
+        };
+        if let Err(message) = result {
+            self.errors.push(LexicalError {
+                message,
+                position: self.position,
+                error_type: LexicalErrorType::InvalidUnitDeclaration,
+            });
+        }
+
+        for _ in decl.symbol.chars() {
+            self.advance();
+        }
+        self.add_token(TokenType::Identifier);
+        true
+    }
+
+    /// Разбор объявления без продвижения сканера
+    fn parse_unit_declaration(&self, from: usize) -> Option<UnitDeclaration> {
+        let src = &self.source;
+        let mut pos = from;
+        let take_while = |pos: &mut usize, pred: &dyn Fn(char) -> bool| -> String {
+            let start = *pos;
+            while src.get(*pos).is_some_and(|&c| pred(c)) {
+                *pos += 1;
+            }
+            src[start..*pos].iter().collect()
+        };
+        let skip_ws = |pos: &mut usize| {
+            while matches!(src.get(*pos), Some(' ') | Some('\t')) {
+                *pos += 1;
+            }
+        };
+
+        if !src.get(pos).copied().is_some_and(is_unit_symbol_start) {
+            return None;
+        }
+        let symbol = take_while(&mut pos, &|c| {
+            !c.is_whitespace() && !"<>=:{}()[],;".contains(c)
+        });
+
+        // <Dimension> или <Dimension, Group> — обязательны
+        if src.get(pos) != Some(&'<') {
+            return None;
+        }
+        pos += 1;
+        skip_ws(&mut pos);
+        let dimension = take_while(&mut pos, &|c| c.is_alphanumeric());
+        skip_ws(&mut pos);
+        let group = if src.get(pos) == Some(&',') {
+            pos += 1;
+            skip_ws(&mut pos);
+            let group = take_while(&mut pos, &|c| c.is_alphanumeric());
+            skip_ws(&mut pos);
+            Some(group)
+        } else {
+            None
+        };
+        if dimension.is_empty() || src.get(pos) != Some(&'>') {
+            return None;
+        }
+        pos += 1;
+        skip_ws(&mut pos);
+
+        if !matches!(src.get(pos), Some('=') | Some(':')) {
+            return None;
+        }
+        pos += 1;
+        skip_ws(&mut pos);
+
+        let value = take_while(&mut pos, &|c| c.is_ascii_digit() || c == '.' || c == '_');
+        let base = take_while(&mut pos, &|c| !c.is_whitespace() && !",;{}()[]".contains(c));
+        if value.is_empty() || base.is_empty() {
+            return None;
+        }
+
+        Some(UnitDeclaration {
+            symbol,
+            dimension,
+            group,
+            value,
+            base,
+        })
+    }
+
     fn scan_number(&mut self) {
         let mut radix: u32 = 10;
         if self.source[self.start] == '0' {
@@ -300,11 +506,32 @@ impl Scanner {
     }
 
     fn process_unit_suffix(&mut self, value_literal: String) {
+        if self.scan_dms_suffix(&value_literal) {
+            return;
+        }
+
         // 1. Берем срез чаров от текущей позиции до конца
         let lookahead = &self.source[self.current..];
-        let unit_char_count = UNITS_TREE.longest_match(lookahead);
+        // `%` вплотную к числу — единица, если за ним не начинается операнд:
+        // `10%` и `10% + x` — проценты, `10%3`, `10%x`, `10%(n)` — остаток.
+        // С пробелом перед `%` (`10 % 3`) суффикса нет вовсе.
+        if lookahead.first() == Some(&'%')
+            && lookahead
+                .get(1)
+                .is_some_and(|&c| c.is_alphanumeric() || c == '_' || c == '(')
+        {
+            self.add_token_with_literal(TokenType::Number, value_literal);
+            return;
+        }
+        // 2. Составное выражение (kg·m/s2, W/(m2·K)) или перечисленная форма (bit/s).
+        // Если составное не проходит границу слова (`70kg·g0`), пробуем простую единицу.
+        let simple = self.registry.longest_match(lookahead);
+        let compound = compound::match_len(lookahead, &self.registry);
 
-        if unit_char_count > 0 {
+        for unit_char_count in [compound.max(simple), simple] {
+            if unit_char_count == 0 {
+                continue;
+            }
             // 3. Проверка границы слова (Word Boundary)
             // Чтобы "10m" внутри "10meters" не распозналось как юнит 'm'
             let is_valid_boundary = if let Some(&nc) = lookahead.get(unit_char_count) {
@@ -314,6 +541,8 @@ impl Scanner {
             };
 
             if is_valid_boundary {
+                let suffix: String = lookahead[..unit_char_count].iter().collect();
+                self.check_ambiguous_units(&suffix);
                 for _ in 0..unit_char_count {
                     self.advance();
                 }
@@ -327,6 +556,52 @@ impl Scanner {
         self.add_token_with_literal(TokenType::Number, value_literal);
     }
 
+    /// Предупреждение на каждый неоднозначный символ суффикса (`gal`, `lbf/gal`)
+    fn check_ambiguous_units(&mut self, suffix: &str) {
+        for symbol in suffix.split(|c| !compound::is_symbol_char(c)) {
+            let Some(variants) = self.registry.ambiguity(symbol) else {
+                continue;
+            };
+            let message = format!(
+                "Unit '{}' is ambiguous, assuming {}; write {} to be explicit",
+                symbol,
+                variants[0],
+                variants.join(" or ")
+            );
+            self.warnings.push(LexicalWarning {
+                message,
+                position: self.position,
+                warning_type: LexicalWarningType::AmbiguousUnit(symbol.to_string()),
+            });
+        }
+    }
+
+    /// `45°30′15″` — один токен, литерал которого — десятичные градусы
+    fn scan_dms_suffix(&mut self, value_literal: &str) -> bool {
+        let Some(dms) = angle::scan_dms(&self.source[self.current..]) else {
+            return false;
+        };
+        // Градусы — только целое десятичное число
+        let Ok(degrees) = value_literal.replace('_', "").parse::<u64>() else {
+            return false;
+        };
+
+        if !dms.is_valid() {
+            self.errors.push(LexicalError {
+                message: "Minutes and seconds of an angle must be less than 60".into(),
+                position: self.position,
+                error_type: LexicalErrorType::InvalidAngle,
+            });
+        }
+
+        for _ in 0..dms.len {
+            self.advance();
+        }
+        let degrees = dms.to_degrees(degrees as f64);
+        self.add_token_with_literal(TokenType::UnitDms, degrees.to_string());
+        true
+    }
+
     fn scan_infinity_as_number(&mut self) {
         for _ in 0..7 {
             self.advance();
@@ -534,6 +809,9 @@ impl Scanner {
             false
         }
     }
+    fn last_token_type(&self) -> Option<&TokenType> {
+        self.tokens.last().map(|t| &t.token_type)
+    }
     fn get_lexeme(&self) -> String {
         self.source[self.start..self.current].iter().collect()
     }
@@ -560,3 +838,7 @@ impl Scanner {
         self.add_token(TokenType::Error);
     }
 }
+
+fn is_unit_symbol_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '\u{00B0}'
+}
@@ -1,8 +1,10 @@
 use crate::lexer::position::Position;
 use crate::lexer::token_type::TokenType;
-use crate::shared::unit::definition::PrefixGroup;
+use crate::shared::unit::compound::CompoundUnit;
+use crate::shared::unit::definition::{PrefixGroup, UnitDef};
 use crate::shared::unit::prefixes::PREFIXES;
-use crate::shared::unit::units::UNITS;
+use crate::shared::unit::registry::UnitRegistry;
+use crate::shared::unit::units::{UNITS, find_unit};
 use bitflags::bitflags;
 use std::fmt;
 
@@ -68,10 +70,15 @@ impl Token {
         }
     }
 
+    #[allow(clippy::collapsible_if)]
     pub fn get_unit_suffix(&self) -> &str {
         if !self.token_type.is_unit() {
             return "—";
         }
+        // Литерал DMS уже переведен в градусы
+        if self.token_type == TokenType::UnitDms {
+            return "°";
+        }
 
         if let Some(ref lit) = self.literal {
             if self.lexeme.starts_with(lit) {
@@ -100,7 +107,7 @@ impl Token {
                 UNITS
                     .iter()
                     .filter(|u| u.parts.is_none())
-                    .find(|u| suffix.ends_with(u.symbol))
+                    .find(|u| suffix.ends_with(u.symbol.as_ref()))
             });
 
         let def = match unit_def {
@@ -124,7 +131,23 @@ impl Token {
         }
 
         // 3. Для атомарных юнитов (кг, см, dam3)
-        self.strip_prefix(suffix, def.symbol, &def.numerator_group)
+        self.strip_prefix(suffix, &def.symbol, &def.numerator_group)
+    }
+
+    /// Определение единицы, к которой относится суффикс (без учета приставки)
+    pub fn get_unit_def(&self) -> Option<&'static UnitDef> {
+        if !self.token_type.is_unit() {
+            return None;
+        }
+        find_unit(&self.get_unit_origin_suffix())
+    }
+
+    /// Суффикс как составная единица (`kg·m/s2`) по встроенному реестру
+    pub fn get_compound_unit(&self) -> Option<CompoundUnit> {
+        if !self.token_type.is_unit() {
+            return None;
+        }
+        CompoundUnit::parse(self.get_unit_suffix(), &UnitRegistry::new()).ok()
     }
 
     fn strip_prefix(&self, current: &str, base: &str, group: &PrefixGroup) -> String {
@@ -218,6 +218,7 @@ pub enum TokenType {
     From,      // from \\ ImportSource
     Include,   // include \\ IncludeFile
     New,       // new \\ NewInstance
+    UnitDecl,  // unit \\ UnitDeclaration (контекстное)
 
     // ─────────────────────────────────────────────
     // Типовая система
@@ -282,6 +283,7 @@ pub enum TokenType {
     DollarEqual, // $= \\ SpecialAssign
 
     Unit,
+    UnitDms, // 45°30′15″ \\ DegreesMinutesSeconds (литерал — десятичные градусы)
 
     UnitDegree,              // deg \\ AngleDegree
     UnitRadian,              // rad \\ AngleRadian
@@ -321,9 +323,10 @@ pub enum TokenType {
 }
 
 impl TokenType {
+    #[allow(clippy::match_like_matches_macro)]
     pub fn is_unit(&self) -> bool {
         match self {
-            TokenType::Unit => true,
+            TokenType::Unit | TokenType::UnitDms => true,
             _ => false,
         }
     }
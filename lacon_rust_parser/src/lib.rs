@@ -25,11 +25,17 @@
 //     }
 // }
 
+pub mod ast;
+pub mod diagnostics;
+pub mod interpreter;
 pub mod lexer;
+pub mod parser;
+pub mod semantic;
 pub mod shared;
 pub mod utils;
 
 #[test]
+#[allow(clippy::unnecessary_map_or)]
 fn test_complex_units_full_info() {
     use crate::lexer::scanner::Scanner;
     use crate::shared::unit::props::Formula;
@@ -97,6 +103,8 @@ mod tests {
     use std::io::Write;
 
     #[test]
+    // `source2` — запасной корпус: подставляется вместо `source` вручную
+    #[allow(unused_variables)]
     fn test_lexer_to_file() {
         let source = r#"
 				key = 1m/s6
@@ -330,6 +338,50 @@ const emptyItem = [value, , value]
             .unwrap();
         }
     }
+
+    #[test]
+    fn percent_unit_or_modulo() {
+        use crate::lexer::token_type::TokenType;
+
+        let kinds = |source: &str| -> Vec<TokenType> {
+            Scanner::new(source.to_string())
+                .scan_tokens()
+                .iter()
+                .map(|t| t.token_type.clone())
+                .filter(|t| matches!(t, TokenType::Unit | TokenType::Number | TokenType::Percent))
+                .collect()
+        };
+        use TokenType::{Number, Percent, Unit};
+
+        assert_eq!(kinds("10% + 5‰"), [Unit, Unit]);
+        assert_eq!(kinds("10%)"), [Unit]);
+        assert_eq!(kinds("10 % 3"), [Number, Percent, Number]);
+        assert_eq!(kinds("10%3"), [Number, Percent, Number]);
+        assert_eq!(kinds("10%(3)"), [Number, Percent, Number]);
+        assert_eq!(kinds("10%_n"), [Number, Percent]);
+        assert_eq!(kinds("200ppm"), [Unit]);
+    }
+
+    #[test]
+    fn star_multiplies_values_not_units() {
+        use crate::lexer::token_type::TokenType;
+
+        let kinds = |source: &str| -> Vec<TokenType> {
+            Scanner::new(source.to_string())
+                .scan_tokens()
+                .iter()
+                .map(|t| t.token_type.clone())
+                .filter(|t| !matches!(t, TokenType::SOF | TokenType::Newline | TokenType::EOF))
+                .collect()
+        };
+        use TokenType::{Identifier, Star, Unit};
+
+        // `t` и `s` — переменные, а не тонна и секунда
+        assert_eq!(kinds("3m*t"), [Unit, Star, Identifier]);
+        assert_eq!(kinds("2m*s"), [Unit, Star, Identifier]);
+        assert_eq!(kinds("2m·s"), [Unit]);
+        assert_eq!(kinds("1W/(m*K)"), [Unit]);
+    }
 }
 
 #[cfg(test)]
@@ -521,3 +573,74 @@ mod tests_prefixes {
         }
     }
 }
+
+#[cfg(test)]
+mod tests_unit_declarations {
+    use crate::lexer::scanner::Scanner;
+    use crate::lexer::token_type::TokenType;
+
+    fn unit_suffixes(source: &str) -> (Vec<String>, Scanner) {
+        let mut scanner = Scanner::new(source.to_string());
+        let suffixes = scanner
+            .scan_tokens()
+            .iter()
+            .filter(|t| t.token_type.is_unit())
+            .map(|t| t.get_unit_suffix().to_string())
+            .collect();
+        (suffixes, scanner)
+    }
+
+    #[test]
+    fn declared_units_are_lexed_for_rest_of_file() {
+        let source = r#"
+size = 3tile
+unit tile<Length> = 16px
+unit px@2x<Length> = 0.5px
+unit {
+    furlong<Length> = 201.168m
+    parsec<Length, SI> = 30856775814913.673km
+}
+size = 3tile
+hd = 10px@2x
+track = 2furlong
+galaxy = 30kparsec
+"#;
+        let (suffixes, scanner) = unit_suffixes(source);
+
+        assert!(scanner.errors.is_empty(), "{:?}", scanner.errors);
+        // До объявления "3tile" — это число и идентификатор, а не юнит
+        assert_eq!(
+            suffixes,
+            ["px", "px", "m", "km", "tile", "px@2x", "furlong", "kparsec"]
+        );
+
+        let registry = scanner.registry();
+        let tile = registry.find("tile").unwrap();
+        assert_eq!(tile.normalize(3.0), 48.0);
+        let (factor, parsec) = registry.resolve("kparsec").unwrap();
+        assert_eq!(factor, 1e3);
+        assert!((parsec.normalize(1.0) / 3.0857e16 - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn unit_stays_a_key_without_declaration() {
+        let mut scanner = Scanner::new("unit px\nunit: em\n".to_string());
+        let tokens = scanner.scan_tokens();
+        assert!(tokens.iter().all(|t| t.token_type != TokenType::UnitDecl));
+        assert!(scanner.errors.is_empty());
+    }
+
+    #[test]
+    fn imported_registry_and_errors() {
+        let (_, imported) = unit_suffixes("unit tile<Length> = 16px\n");
+        let mut scanner = Scanner::with_registry(
+            "a = 2tile\nunit tick<Time> = 2px\n".to_string(),
+            imported.registry().clone(),
+        );
+        let tokens = scanner.scan_tokens();
+
+        assert!(tokens.iter().any(|t| t.lexeme == "2tile"));
+        assert_eq!(scanner.errors.len(), 1);
+        assert!(scanner.errors[0].message.contains("Time"));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::lexer::error::{LexicalError, LexicalErrorType};
+use crate::lexer::position::Position;
+use crate::lexer::token_type::TokenType;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub message: String,
+    pub position: Position,
+    pub error_type: ParseErrorType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorType {
+    /// Токен не может стоять в этом месте
+    UnexpectedToken(TokenType),
+    ExpectedExpression,
+    ExpectedName,
+    /// Не закрыта скобка; в поле — ожидаемая закрывающая
+    UnclosedDelimiter(TokenType),
+    /// Ошибка сканера: незакрытая строка, неверный угол, объявление единицы
+    Lexical(LexicalErrorType),
+}
+
+impl From<LexicalError> for ParseError {
+    fn from(error: LexicalError) -> Self {
+        Self {
+            message: error.message,
+            position: error.position,
+            error_type: ParseErrorType::Lexical(error.error_type),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Parse Error at {}: {} ({:?})",
+            self.position, self.message, self.error_type
+        )
+    }
+}
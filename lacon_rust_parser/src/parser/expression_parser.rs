@@ -0,0 +1,414 @@
+use super::parser::Parser;
+use super::precedence::{Precedence, infix_operator};
+use crate::ast::expressions::{
+    BinaryOperator, ContextKeyword, Expression, ExpressionKind, Segment, UnaryOperator,
+};
+use crate::lexer::token::{Token, TokenFlags};
+use crate::lexer::token_type::TokenType;
+use crate::parser::error::ParseErrorType;
+use crate::utils::span::Span;
+
+impl Parser {
+    pub(super) fn parse_expression(&mut self) -> Option<Expression> {
+        self.parse_precedence(Precedence::Lowest)
+    }
+
+    /// Выражение из операторов не слабее `min`
+    fn parse_precedence(&mut self, min: Precedence) -> Option<Expression> {
+        let left = self.parse_prefix()?;
+        self.parse_infix(left, min)
+    }
+
+    /// Продолжение выражения после уже разобранного левого операнда
+    pub(super) fn parse_infix(
+        &mut self,
+        mut left: Expression,
+        min: Precedence,
+    ) -> Option<Expression> {
+        loop {
+            left = match self.peek().token_type {
+                TokenType::LeftParen if self.is_glued() => self.parse_call(left)?,
+                TokenType::LeftBracket if self.is_glued() => self.parse_index(left)?,
+                TokenType::Dot | TokenType::ColonColon => self.parse_member(left)?,
+                ref token_type => {
+                    let Some((operator, precedence)) = infix_operator(token_type) else {
+                        break;
+                    };
+                    if precedence < min {
+                        break;
+                    }
+                    self.advance();
+                    // `^` правоассоциативен: 2^3^2 = 2^(3^2)
+                    let right_min = if operator == BinaryOperator::Power {
+                        precedence
+                    } else {
+                        precedence.next()
+                    };
+                    let right = self.parse_precedence(right_min)?;
+                    let span = left.span.to(right.span);
+                    Expression::new(
+                        ExpressionKind::Binary {
+                            operator,
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        },
+                        span,
+                    )
+                }
+            };
+        }
+        Some(left)
+    }
+
+    fn parse_prefix(&mut self) -> Option<Expression> {
+        let token = self.peek().clone();
+        let span = Span::of_token(&token);
+        let simple = |parser: &mut Parser, kind| {
+            parser.advance();
+            Some(Expression::new(kind, span))
+        };
+
+        match token.token_type {
+            TokenType::Number if self.at_bare_hex() => self.parse_bare_hex(),
+            TokenType::Number | TokenType::NumberInfinity => {
+                let value = self.number_value(&token)?;
+                simple(self, ExpressionKind::Number(value))
+            }
+            TokenType::Unit | TokenType::UnitDms => {
+                let value = self.number_value(&token)?;
+                let suffix = token.get_unit_suffix().to_string();
+                simple(self, ExpressionKind::Unit { value, suffix })
+            }
+            TokenType::String
+            | TokenType::SingleQuotedString
+            | TokenType::GraveQuotedString
+            | TokenType::MultilineString => self.parse_string(),
+            TokenType::Identifier | TokenType::DollarLeftBrace => {
+                let name = self.parse_name()?;
+                let span = name.span;
+                Some(Expression::new(ExpressionKind::Identifier(name), span))
+            }
+            TokenType::This => simple(self, ExpressionKind::Context(ContextKeyword::This)),
+            TokenType::Parent => simple(self, ExpressionKind::Context(ContextKeyword::Parent)),
+            TokenType::Root => simple(self, ExpressionKind::Context(ContextKeyword::Root)),
+            TokenType::Origin => simple(self, ExpressionKind::Context(ContextKeyword::Origin)),
+            TokenType::Super => simple(self, ExpressionKind::Context(ContextKeyword::Super)),
+            TokenType::Here => simple(self, ExpressionKind::Context(ContextKeyword::Here)),
+            TokenType::True => simple(self, ExpressionKind::Boolean(true)),
+            TokenType::False => simple(self, ExpressionKind::Boolean(false)),
+            TokenType::Nil => simple(self, ExpressionKind::Nil),
+            TokenType::None => simple(self, ExpressionKind::None),
+            TokenType::Undefined => simple(self, ExpressionKind::Undefined),
+            TokenType::Minus | TokenType::Plus | TokenType::Bang | TokenType::Not => {
+                self.advance();
+                let operator = match token.token_type {
+                    TokenType::Minus => UnaryOperator::Negate,
+                    TokenType::Plus => UnaryOperator::Plus,
+                    _ => UnaryOperator::Not,
+                };
+                // -x^2 = -(x^2)
+                let operand = self.parse_precedence(Precedence::Power)?;
+                let span = span.to(operand.span);
+                Some(Expression::new(
+                    ExpressionKind::Unary {
+                        operator,
+                        operand: Box::new(operand),
+                    },
+                    span,
+                ))
+            }
+            TokenType::LeftParen => self.parse_group(),
+            TokenType::LeftBracket => self.parse_array(),
+            TokenType::LeftBrace => {
+                let block = self.parse_brace_block()?;
+                let span = block.span;
+                Some(Expression::new(ExpressionKind::Dictionary(block), span))
+            }
+            TokenType::Hash => self.parse_color(),
+            _ => {
+                let message = if token.token_type == TokenType::EOF {
+                    "Expected an expression, found end of file".to_string()
+                } else {
+                    format!("Expected an expression, found '{}'", token.lexeme)
+                };
+                self.error_at(&token, ParseErrorType::ExpectedExpression, &message);
+                None
+            }
+        }
+    }
+
+    fn number_value(&mut self, token: &Token) -> Option<f64> {
+        let literal = token.literal.as_deref().unwrap_or(&token.lexeme);
+        let value = parse_number(literal);
+        if value.is_none() {
+            self.error_at(
+                token,
+                ParseErrorType::UnexpectedToken(token.token_type.clone()),
+                &format!("Invalid number '{}'", literal),
+            );
+        }
+        value
+    }
+
+    /// Шестнадцатеричное число без `0x` (`1FBC`): сканер делит его
+    /// на десятичные цифры и идентификатор вплотную
+    fn at_bare_hex(&self) -> bool {
+        let (digits, letters) = (self.peek(), self.peek_next());
+        digits.lexeme.chars().all(|c| c.is_ascii_digit())
+            && letters.token_type == TokenType::Identifier
+            && !letters.flags.contains(TokenFlags::HAS_PRECEDING_WHITESPACE)
+            && letters.lexeme.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    fn parse_bare_hex(&mut self) -> Option<Expression> {
+        let digits = self.advance();
+        let letters = self.advance();
+        let text = format!("{}{}", digits.lexeme, letters.lexeme);
+        let span = self.span_from(digits.position);
+        match u64::from_str_radix(&text, 16) {
+            Ok(value) => Some(Expression::new(ExpressionKind::Number(value as f64), span)),
+            Err(_) => {
+                self.error_at(
+                    &digits,
+                    ParseErrorType::UnexpectedToken(TokenType::Number),
+                    &format!("Invalid number '{}'", text),
+                );
+                None
+            }
+        }
+    }
+
+    /// Строка со вставками: сканер выдает части строки между `${...}` отдельно
+    fn parse_string(&mut self) -> Option<Expression> {
+        let first = self.advance();
+        let mut segments = Vec::new();
+        if let Some(text) = first.literal.as_ref().filter(|text| !text.is_empty()) {
+            segments.push(Segment::Text(text.clone()));
+        }
+
+        while self.check(&TokenType::DollarLeftBrace) && self.is_glued() {
+            self.advance();
+            let expression = self.parse_expression()?;
+            self.expect(TokenType::RightBrace, "Expected '}' after interpolation")?;
+            segments.push(Segment::Interpolation(expression));
+
+            if self.peek().token_type == first.token_type {
+                let rest = self.advance();
+                if let Some(text) = rest.literal.filter(|text| !text.is_empty()) {
+                    segments.push(Segment::Text(text));
+                }
+            }
+        }
+        Some(Expression::new(
+            ExpressionKind::String(segments),
+            self.span_from(first.position),
+        ))
+    }
+
+    /// `(a)` — группировка, `(a, b)` — кортеж
+    fn parse_group(&mut self) -> Option<Expression> {
+        let open = self.advance();
+        self.nesting += 1;
+        let mut items = Vec::new();
+        let mut tuple = false;
+        while !self.check(&TokenType::RightParen) {
+            items.push(self.parse_expression()?);
+            if !self.check(&TokenType::Comma) {
+                break;
+            }
+            self.advance();
+            tuple = true;
+        }
+        let close = self.close(TokenType::RightParen, &open);
+        self.nesting -= 1;
+        close?;
+
+        let span = self.span_from(open.position);
+        if !tuple && items.len() == 1 {
+            let mut inner = items.pop().unwrap();
+            inner.span = span;
+            return Some(inner);
+        }
+        Some(Expression::new(ExpressionKind::Tuple(items), span))
+    }
+
+    /// Пропущенный элемент (`[a, , b]`) сохраняется как `Hole`
+    fn parse_array(&mut self) -> Option<Expression> {
+        let open = self.advance();
+        self.nesting += 1;
+        let mut items = Vec::new();
+        let mut expecting_item = true;
+        loop {
+            match self.peek().token_type {
+                TokenType::RightBracket | TokenType::EOF => break,
+                TokenType::Comma => {
+                    let comma = self.advance();
+                    if expecting_item {
+                        items.push(Expression::new(
+                            ExpressionKind::Hole,
+                            Span::point(comma.position),
+                        ));
+                    }
+                    expecting_item = true;
+                }
+                _ if expecting_item => {
+                    let Some(item) = self.parse_expression() else {
+                        self.nesting -= 1;
+                        return None;
+                    };
+                    items.push(item);
+                    expecting_item = false;
+                }
+                _ => break,
+            }
+        }
+        let close = self.close(TokenType::RightBracket, &open);
+        self.nesting -= 1;
+        close?;
+        Some(Expression::new(
+            ExpressionKind::Array(items),
+            self.span_from(open.position),
+        ))
+    }
+
+    fn parse_call(&mut self, callee: Expression) -> Option<Expression> {
+        let open = self.advance();
+        self.nesting += 1;
+        let mut arguments = Vec::new();
+        while !self.check(&TokenType::RightParen) {
+            let Some(argument) = self.parse_expression() else {
+                self.nesting -= 1;
+                return None;
+            };
+            arguments.push(argument);
+            if !self.check(&TokenType::Comma) {
+                break;
+            }
+            self.advance();
+        }
+        let close = self.close(TokenType::RightParen, &open);
+        self.nesting -= 1;
+        close?;
+        let span = self.span_from(callee.span.start);
+        Some(Expression::new(
+            ExpressionKind::Call {
+                callee: Box::new(callee),
+                arguments,
+            },
+            span,
+        ))
+    }
+
+    fn parse_index(&mut self, object: Expression) -> Option<Expression> {
+        let open = self.advance();
+        self.nesting += 1;
+        let index = self.parse_expression();
+        let close = self.close(TokenType::RightBracket, &open);
+        self.nesting -= 1;
+        let index = index?;
+        close?;
+        let span = self.span_from(object.span.start);
+        Some(Expression::new(
+            ExpressionKind::Index {
+                object: Box::new(object),
+                index: Box::new(index),
+            },
+            span,
+        ))
+    }
+
+    /// `a.b`, `Align::center`; после точки допустимо и ключевое слово (`.type`)
+    fn parse_member(&mut self, object: Expression) -> Option<Expression> {
+        let path = self.advance().token_type == TokenType::ColonColon;
+        let token = self.peek().clone();
+        let is_word = !token.lexeme.is_empty()
+            && token
+                .lexeme
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        if !is_word {
+            self.error_at(
+                &token,
+                ParseErrorType::ExpectedName,
+                &format!("Expected a member name, found '{}'", token.lexeme),
+            );
+            return None;
+        }
+        self.advance();
+        let span = self.span_from(object.span.start);
+        Some(Expression::new(
+            ExpressionKind::Member {
+                object: Box::new(object),
+                member: token.lexeme,
+                path,
+            },
+            span,
+        ))
+    }
+
+    /// `#dbebed`: части цвета стоят вплотную к `#`
+    fn parse_color(&mut self) -> Option<Expression> {
+        let hash = self.advance();
+        let mut color = String::from("#");
+        while self.is_glued()
+            && matches!(
+                self.peek().token_type,
+                TokenType::Identifier | TokenType::Number | TokenType::Unit
+            )
+        {
+            color.push_str(&self.advance().lexeme);
+        }
+        if color.len() == 1 {
+            let token = self.peek().clone();
+            self.error_at(
+                &token,
+                ParseErrorType::ExpectedExpression,
+                "Expected a color after '#'",
+            );
+            return None;
+        }
+        Some(Expression::new(
+            ExpressionKind::Color(color),
+            self.span_from(hash.position),
+        ))
+    }
+
+    /// Закрывающая скобка; иначе ошибка с позицией открывающей
+    fn close(&mut self, closing: TokenType, open: &Token) -> Option<Token> {
+        if self.check(&closing) {
+            return Some(self.advance());
+        }
+        let message = format!("Unclosed '{}'", open.lexeme);
+        self.error_at(open, ParseErrorType::UnclosedDelimiter(closing), &message);
+        None
+    }
+}
+
+/// Значение числового литерала с учетом системы счисления:
+/// `0x4E3`, `0b1011`, `0o071`, `0tL1FF` (base32hex), `0cZYX` (Crockford), `20_000`
+pub fn parse_number(literal: &str) -> Option<f64> {
+    let text = literal.replace('_', "");
+    if text.eq_ignore_ascii_case("infinity") {
+        return Some(f64::INFINITY);
+    }
+    let prefix = text.get(..2).map(|p| p.to_ascii_lowercase());
+    let (radix, digits) = match prefix.as_deref() {
+        Some("0x") => (16, &text[2..]),
+        Some("0b") => (2, &text[2..]),
+        Some("0o") => (8, &text[2..]),
+        Some("0t") => (32, &text[2..]),
+        Some("0c") => return crockford(&text[2..]),
+        _ => return text.parse().ok(),
+    };
+    u64::from_str_radix(digits, radix).ok().map(|n| n as f64)
+}
+
+fn crockford(digits: &str) -> Option<f64> {
+    const ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let mut value: u64 = 0;
+    for c in digits.chars() {
+        let digit = ALPHABET.find(c.to_ascii_uppercase())? as u64;
+        value = value.checked_mul(32)?.checked_add(digit)?;
+    }
+    (!digits.is_empty()).then_some(value as f64)
+}
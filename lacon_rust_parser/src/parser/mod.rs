@@ -0,0 +1,10 @@
+pub mod error;
+pub mod expression_parser;
+#[allow(clippy::module_inception)]
+pub mod parser;
+pub mod precedence;
+pub mod statement_parser;
+pub mod type_parser;
+
+pub use error::{ParseError, ParseErrorType};
+pub use parser::{Parser, parse};
@@ -0,0 +1,751 @@
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, Statement};
+use crate::lexer::position::Position;
+use crate::lexer::scanner::Scanner;
+use crate::lexer::token::{Token, TokenFlags};
+use crate::lexer::token_type::TokenType;
+use crate::parser::error::{ParseError, ParseErrorType};
+use crate::utils::span::Span;
+
+// Разбор идет по токенам сканера. Блоки задаются фигурными скобками или отступом
+// (Indent/Dedent). Внутри `(...)` и `[...]` переводы строк и отступы не значимы,
+// внутри `{...}` записи разделяются переводами строк, а отступы игнорируются.
+// Ошибка в записи не прерывает разбор: парсер пропускает остаток строки.
+
+/// Чем заканчивается последовательность записей
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BlockEnd {
+    Eof,
+    Brace,
+    Dedent,
+}
+
+pub struct Parser {
+    tokens: Vec<Token>,
+    current: usize,
+    /// Глубина `(...)` и `[...]`
+    pub(super) nesting: usize,
+    /// Конец последнего прочитанного токена
+    last_end: Position,
+    /// Тип последнего прочитанного токена: после Dedent запись уже закончена
+    last_type: TokenType,
+    pub errors: Vec<ParseError>,
+}
+
+/// Сканирование и разбор документа. Ошибки сканера и парсера — в одном
+/// списке по порядку в тексте.
+pub fn parse(source: &str) -> (Program, Vec<ParseError>) {
+    let mut scanner = Scanner::new(source.to_string());
+    let tokens = scanner.scan_tokens().clone();
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse_program();
+
+    let mut errors: Vec<ParseError> = scanner.errors.into_iter().map(ParseError::from).collect();
+    errors.append(&mut parser.errors);
+    errors.sort_by_key(|error| error.position.offset);
+    (program, errors)
+}
+
+impl Parser {
+    pub fn new(mut tokens: Vec<Token>) -> Self {
+        tokens.retain(|t| t.token_type != TokenType::SOF);
+        if tokens.last().is_none_or(|t| t.token_type != TokenType::EOF) {
+            let end = tokens.last().map_or(Position::start(), |t| t.position);
+            tokens.push(Token::eof(end));
+        }
+        Self {
+            tokens,
+            current: 0,
+            nesting: 0,
+            last_end: Position::start(),
+            last_type: TokenType::SOF,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        let start = self.peek().position;
+        let statements = self.parse_statements(BlockEnd::Eof);
+        Program {
+            body: Block {
+                statements,
+                span: Span::new(start, self.last_end.max(start)),
+            },
+        }
+    }
+
+    /// Записи до конца блока; закрывающий токен не поглощается
+    pub(super) fn parse_statements(&mut self, end: BlockEnd) -> Vec<Statement> {
+        self.parse_sequence(end, Self::parse_statement)
+    }
+
+    /// Элементы по одному на строку: записи блока или поля схемы
+    fn parse_sequence<T>(&mut self, end: BlockEnd, item: fn(&mut Self) -> Option<T>) -> Vec<T> {
+        let mut items = Vec::new();
+        // Отступы без владельца (строка с лишним отступом) — их Dedent не закрывает блок
+        let mut stray_indents = 0usize;
+
+        loop {
+            match self.peek().token_type {
+                TokenType::Newline | TokenType::Semicolon => {
+                    self.advance();
+                }
+                TokenType::Indent => {
+                    self.advance();
+                    if end != BlockEnd::Brace {
+                        stray_indents += 1;
+                    }
+                }
+                TokenType::Dedent => {
+                    if end == BlockEnd::Dedent && stray_indents == 0 {
+                        break;
+                    }
+                    stray_indents = stray_indents.saturating_sub(1);
+                    self.advance();
+                }
+                TokenType::RightBrace if end == BlockEnd::Brace => break,
+                TokenType::RightBrace => {
+                    let token = self.advance();
+                    self.error_at(
+                        &token,
+                        ParseErrorType::UnexpectedToken(TokenType::RightBrace),
+                        "Unexpected '}'",
+                    );
+                }
+                TokenType::EOF => break,
+                // `unit tile<Length> = 16px`, `unit { ... }` — единицы уже
+                // зарегистрированы сканером, в дереве им места нет
+                TokenType::UnitDecl => {
+                    self.advance();
+                    self.synchronize();
+                }
+                _ => match item(self) {
+                    Some(parsed) => {
+                        items.push(parsed);
+                        if !self.at_statement_end() {
+                            let token = self.peek().clone();
+                            self.error_at(
+                                &token,
+                                ParseErrorType::UnexpectedToken(token.token_type.clone()),
+                                &format!("Expected end of line, found '{}'", token.lexeme),
+                            );
+                            self.synchronize();
+                        }
+                    }
+                    None => self.synchronize(),
+                },
+            }
+        }
+        items
+    }
+
+    /// `{ ... }` с записями внутри
+    pub(super) fn parse_brace_block(&mut self) -> Option<Block> {
+        let (statements, span) = self.parse_brace_sequence(Self::parse_statement)?;
+        Some(Block { statements, span })
+    }
+
+    fn parse_brace_sequence<T>(
+        &mut self,
+        item: fn(&mut Self) -> Option<T>,
+    ) -> Option<(Vec<T>, Span)> {
+        let open = self.expect(TokenType::LeftBrace, "Expected '{'")?;
+        let saved = std::mem::replace(&mut self.nesting, 0);
+        let items = self.parse_sequence(BlockEnd::Brace, item);
+        self.nesting = saved;
+
+        if self.check(&TokenType::RightBrace) {
+            self.advance();
+        } else {
+            self.error_at(
+                &open,
+                ParseErrorType::UnclosedDelimiter(TokenType::RightBrace),
+                "Unclosed '{'",
+            );
+        }
+        Some((items, Span::new(open.position, self.last_end)))
+    }
+
+    /// Перевод строки и следующая строка с большим отступом
+    pub(super) fn at_indented_block(&self) -> bool {
+        if self.nesting > 0 || self.peek().token_type != TokenType::Newline {
+            return false;
+        }
+        self.tokens[self.current..]
+            .iter()
+            .find(|t| t.token_type != TokenType::Newline)
+            .is_some_and(|t| t.token_type == TokenType::Indent)
+    }
+
+    pub(super) fn parse_indented_block(&mut self) -> Block {
+        let (statements, span) = self.parse_indented_sequence(Self::parse_statement);
+        Block { statements, span }
+    }
+
+    fn parse_indented_sequence<T>(&mut self, item: fn(&mut Self) -> Option<T>) -> (Vec<T>, Span) {
+        while self.check(&TokenType::Newline) {
+            self.advance();
+        }
+        let start = self.advance().position; // Indent
+        let items = self.parse_sequence(BlockEnd::Dedent, item);
+        if self.check(&TokenType::Dedent) {
+            self.advance();
+        }
+        (items, Span::new(start, self.last_end.max(start)))
+    }
+
+    /// Тело контейнера или генератора: скобки или отступ
+    pub(super) fn parse_body(&mut self) -> Option<Block> {
+        let (statements, span) = self.parse_body_sequence(Self::parse_statement)?;
+        Some(Block { statements, span })
+    }
+
+    /// Тело схемы или контейнера: элементы в скобках или отступом
+    pub(super) fn parse_body_sequence<T>(
+        &mut self,
+        item: fn(&mut Self) -> Option<T>,
+    ) -> Option<(Vec<T>, Span)> {
+        if self.at_indented_block() {
+            return Some(self.parse_indented_sequence(item));
+        }
+        self.parse_brace_sequence(item)
+    }
+
+    pub(super) fn at_statement_end(&self) -> bool {
+        self.last_type == TokenType::Dedent
+            || matches!(
+                self.peek().token_type,
+                TokenType::Newline
+                    | TokenType::Semicolon
+                    | TokenType::EOF
+                    | TokenType::RightBrace
+                    | TokenType::Dedent
+            )
+    }
+
+    /// Пропуск до конца строки; вложенные скобки пропускаются целиком
+    fn synchronize(&mut self) {
+        self.nesting = 0;
+        let mut depth = 0usize;
+        loop {
+            match self.peek().token_type {
+                TokenType::EOF => return,
+                TokenType::Newline | TokenType::Semicolon if depth == 0 => return,
+                TokenType::RightBrace | TokenType::Dedent if depth == 0 => return,
+                TokenType::LeftBrace => depth += 1,
+                TokenType::RightBrace => depth -= 1,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    // --- Курсор ---
+
+    /// Индекс следующего значимого токена
+    fn peek_index(&self) -> usize {
+        let last = self.tokens.len() - 1;
+        let mut index = self.current.min(last);
+        while index < last {
+            let skip = match self.tokens[index].token_type {
+                TokenType::Error => true,
+                TokenType::Newline | TokenType::Indent | TokenType::Dedent => self.nesting > 0,
+                _ => false,
+            };
+            if !skip {
+                break;
+            }
+            index += 1;
+        }
+        index
+    }
+
+    pub(super) fn peek(&self) -> &Token {
+        &self.tokens[self.peek_index()]
+    }
+
+    /// Токен после следующего значимого (без учета вложенности)
+    pub(super) fn peek_next(&self) -> &Token {
+        self.peek_nth(1)
+    }
+
+    /// `n`-й токен после следующего значимого: `peek_nth(0)` — он сам
+    pub(super) fn peek_nth(&self, n: usize) -> &Token {
+        let index = (self.peek_index() + n).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    pub(super) fn advance(&mut self) -> Token {
+        let index = self.peek_index();
+        let token = self.tokens[index].clone();
+        if token.token_type != TokenType::EOF {
+            self.current = index + 1;
+            self.last_end = Span::of_token(&token).end;
+            self.last_type = token.token_type.clone();
+        }
+        token
+    }
+
+    /// Делит следующий токен на два односимвольных: `>>` на `>` и `>`
+    pub(super) fn split_token(&mut self, first: TokenType, second: TokenType) {
+        let index = self.peek_index();
+        let token = &mut self.tokens[index];
+        let mut rest = token.clone();
+        let (head, tail) = token.lexeme.split_at(1);
+        rest.lexeme = tail.to_string();
+        rest.token_type = second;
+        rest.length = token.length - 1;
+        rest.position.column += 1;
+        rest.position.offset += 1;
+        rest.flags
+            .remove(TokenFlags::HAS_PRECEDING_WHITESPACE | TokenFlags::AT_LINE_START);
+        token.lexeme = head.to_string();
+        token.token_type = first;
+        token.length = 1;
+        self.tokens.insert(index + 1, rest);
+    }
+
+    pub(super) fn check(&self, token_type: &TokenType) -> bool {
+        &self.peek().token_type == token_type
+    }
+
+    /// Следующий токен стоит вплотную к предыдущему
+    pub(super) fn is_glued(&self) -> bool {
+        !self
+            .peek()
+            .flags
+            .contains(TokenFlags::HAS_PRECEDING_WHITESPACE)
+    }
+
+    pub(super) fn expect(&mut self, token_type: TokenType, message: &str) -> Option<Token> {
+        if self.check(&token_type) {
+            return Some(self.advance());
+        }
+        let token = self.peek().clone();
+        self.error_at(
+            &token,
+            ParseErrorType::UnexpectedToken(token.token_type.clone()),
+            message,
+        );
+        None
+    }
+
+    pub(super) fn span_from(&self, start: Position) -> Span {
+        Span::new(start, self.last_end.max(start))
+    }
+
+    pub(super) fn error_at(&mut self, token: &Token, error_type: ParseErrorType, message: &str) {
+        self.errors.push(ParseError {
+            message: message.to_string(),
+            position: token.position,
+            error_type,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::expressions::{BinaryOperator, Expression, ExpressionKind, Segment};
+    use crate::ast::statements::{
+        AssignOperator, DeclarationKind, EnumValue, Modifiers, Separator, StatementKind,
+        TypeAliasKind,
+    };
+    use crate::ast::types::{ObjectKind, TypeKind};
+    use crate::lexer::error::LexicalErrorType;
+
+    fn parse_ok(source: &str) -> Program {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        program
+    }
+
+    #[test]
+    fn declarations_and_properties() {
+        let program = parse_ok(
+            "let speed<Speed> = 278ft/s\nglobal const const-name a - b\nkey: 1m\nsecond-word=fall\nopacity -1.0\ntarget-node\n",
+        );
+        let kinds: Vec<&StatementKind> = program.body.statements.iter().map(|s| &s.kind).collect();
+        assert_eq!(kinds.len(), 6);
+
+        let StatementKind::Variable(speed) = kinds[0] else {
+            panic!("{:?}", kinds[0]);
+        };
+        assert_eq!(speed.kind, DeclarationKind::Variable);
+        assert_eq!(speed.name.as_static(), Some("speed"));
+        assert!(speed.annotation.is_some());
+        assert!(matches!(
+            speed.value.as_ref().unwrap().kind,
+            ExpressionKind::Unit { value: 278.0, ref suffix } if suffix == "ft/s"
+        ));
+
+        let StatementKind::Variable(global) = kinds[1] else {
+            panic!("{:?}", kinds[1]);
+        };
+        assert_eq!(global.kind, DeclarationKind::Constant);
+        assert_eq!(global.modifiers, Modifiers::GLOBAL);
+        assert!(matches!(
+            global.value.as_ref().unwrap().kind,
+            ExpressionKind::Binary {
+                operator: BinaryOperator::Subtract,
+                ..
+            }
+        ));
+
+        let separators: Vec<Separator> = kinds[2..]
+            .iter()
+            .map(|kind| match kind {
+                StatementKind::Property(p) => p.separator,
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            separators,
+            [
+                Separator::Colon,
+                Separator::Equal,
+                Separator::Whitespace,
+                Separator::None
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_declarations_are_skipped() {
+        let program = parse_ok(
+            r#"
+unit tile<Length> = 16px
+unit px@2x<Length> = 0.5px
+unit {
+    furlong<Length> = 201.168m
+    parsec<Length, SI> = 30856775814913.673km
+}
+size = 3tile
+track = 2furlong
+"#,
+        );
+        let names: Vec<&str> = program
+            .body
+            .statements
+            .iter()
+            .map(|s| match &s.kind {
+                StatementKind::Property(p) => p.name.as_static().unwrap(),
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        assert_eq!(names, ["size", "track"]);
+    }
+
+    #[test]
+    fn generators_and_blocks() {
+        let program = parse_ok(
+            r#"
+generate (0x4E3 .. -10) as local let code-point {
+	yield unicode-${code-point} {
+		char: code-point
+	}
+}
+spread (['c', 's'], ['a', 'b']) as let (letter-case, suffix) {
+	local const code-points = [1, , 2]
+	yield hel_${letter-case}_a {
+		symbol {
+			letter: `hel_${letter-case}`
+		}
+	}
+}
+container App
+    styles {
+        width: 100%
+    }
+    logic
+        opacity 0
+"#,
+        );
+        let statements = &program.body.statements;
+        assert_eq!(statements.len(), 3);
+
+        let StatementKind::Generator(generate) = &statements[0].kind else {
+            panic!("{:?}", statements[0]);
+        };
+        let binding = generate.binding.as_ref().unwrap();
+        assert_eq!(binding.modifiers, Modifiers::LOCAL);
+        assert_eq!(binding.names[0].as_static(), Some("code-point"));
+        let StatementKind::Yield(yielded) = &generate.body.statements[0].kind else {
+            panic!("{:?}", generate.body);
+        };
+        assert_eq!(yielded.name.as_static(), None);
+        assert!(matches!(
+            yielded.name.segments[1],
+            Segment::Interpolation(_)
+        ));
+
+        let StatementKind::Generator(spread) = &statements[1].kind else {
+            panic!("{:?}", statements[1]);
+        };
+        assert_eq!(spread.binding.as_ref().unwrap().names.len(), 2);
+        let StatementKind::Variable(local) = &spread.body.statements[0].kind else {
+            panic!("{:?}", spread.body);
+        };
+        let Some(ExpressionKind::Array(items)) = local.value.as_ref().map(|v| &v.kind) else {
+            panic!("{:?}", local);
+        };
+        assert!(matches!(items[1].kind, ExpressionKind::Hole));
+
+        let StatementKind::Container(app) = &statements[2].kind else {
+            panic!("{:?}", statements[2]);
+        };
+        assert_eq!(app.body.statements.len(), 2);
+    }
+
+    #[test]
+    fn schemas_and_annotated_keys() {
+        let program = parse_ok(
+            "struct Button extends Control implements Sized, Themed {\n    label<String>\n    icon?<String>\n    align<String> in [start, center, end] = center\n    type = \"push\"\n}\ninterface Sized\n    width<Length>\nok<Button> {\n    label: OK\n}\nlimit<Length>: 4px\na<b\n",
+        );
+        let kinds: Vec<&StatementKind> = program.body.statements.iter().map(|s| &s.kind).collect();
+        assert_eq!(kinds.len(), 5);
+
+        let StatementKind::Schema(button) = kinds[0] else {
+            panic!("{:?}", kinds[0]);
+        };
+        assert_eq!(button.kind, ObjectKind::Struct);
+        assert_eq!(button.extends.len(), 1);
+        assert_eq!(button.implements.len(), 2);
+        let fields: Vec<(&str, bool, usize, bool)> = button
+            .fields
+            .iter()
+            .map(|f| {
+                let name = f.name.as_static().unwrap();
+                (name, f.optional, f.allowed.len(), f.default.is_some())
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("label", false, 0, false),
+                ("icon", true, 0, false),
+                ("align", false, 3, true),
+                ("type", false, 0, true)
+            ]
+        );
+
+        let StatementKind::Schema(sized) = kinds[1] else {
+            panic!("{:?}", kinds[1]);
+        };
+        assert_eq!(sized.kind, ObjectKind::Interface);
+        assert_eq!(sized.fields.len(), 1);
+
+        for kind in &kinds[2..4] {
+            let StatementKind::Property(property) = kind else {
+                panic!("{:?}", kind);
+            };
+            assert!(property.annotation.is_some());
+        }
+        assert!(matches!(
+            kinds[4],
+            StatementKind::Expression(Expression {
+                kind: ExpressionKind::Binary {
+                    operator: BinaryOperator::Less,
+                    ..
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn enums_with_values() {
+        let program = parse_ok(
+            "enum Align { start, center, end }
+private enum Gap {
+    small = 4px,
+    large: 16px,
+}
+enum Arrow
+    left = U+2190
+    right = U+2192
+",
+        );
+        let decls: Vec<_> = program
+            .body
+            .statements
+            .iter()
+            .map(|s| match &s.kind {
+                StatementKind::Enum(decl) => decl,
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        assert_eq!(decls.len(), 3);
+        assert_eq!(decls[0].member_names(), ["start", "center", "end"]);
+        assert!(decls[0].members.iter().all(|m| m.value.is_none()));
+
+        assert_eq!(decls[1].modifiers, Modifiers::PRIVATE);
+        assert!(matches!(
+            decls[1].members[1].value,
+            Some(EnumValue::Expression(Expression {
+                kind: ExpressionKind::Unit { .. },
+                ..
+            }))
+        ));
+        assert_eq!(decls[2].members[0].value, Some(EnumValue::CodePoint('←')));
+        assert_eq!(decls[2].members[1].value, Some(EnumValue::CodePoint('→')));
+
+        let (_, errors) = parse(
+            "enum Bad { x = U+110000 }
+",
+        );
+        assert_eq!(errors[0].message, "Invalid code point 'U+110000'");
+    }
+
+    #[test]
+    fn type_aliases_and_generic_annotations() {
+        let program = parse_ok(
+            "type Dim = Length | Percent\nprivate alias Px = Length\nlet grid<Array<Array<Px>>> = [[1px]]\nfades<Dictionary<String, Time>> { in: 200ms }\ntype: \"box\"\ncount >>= 1\nless = a<b\n",
+        );
+        let kinds: Vec<&StatementKind> = program.body.statements.iter().map(|s| &s.kind).collect();
+        assert_eq!(kinds.len(), 7);
+
+        let StatementKind::TypeAlias(dim) = kinds[0] else {
+            panic!("{:?}", kinds[0]);
+        };
+        assert_eq!(dim.kind, TypeAliasKind::Type);
+        assert!(matches!(&dim.annotation.kind, TypeKind::Union(members) if members.len() == 2));
+        assert_eq!(dim.annotation.to_string(), "Length | Percent");
+        let StatementKind::TypeAlias(px) = kinds[1] else {
+            panic!("{:?}", kinds[1]);
+        };
+        assert_eq!(px.kind, TypeAliasKind::Alias);
+        assert_eq!(px.modifiers, Modifiers::PRIVATE);
+
+        let StatementKind::Variable(grid) = kinds[2] else {
+            panic!("{:?}", kinds[2]);
+        };
+        let annotation = grid.annotation.as_ref().unwrap();
+        assert_eq!(annotation.to_string(), "Array<Array<Px>>");
+        assert_eq!(annotation.span.end.column, 27);
+        let StatementKind::Property(fades) = kinds[3] else {
+            panic!("{:?}", kinds[3]);
+        };
+        assert_eq!(
+            fades.annotation.as_ref().unwrap().to_string(),
+            "Dictionary<String, Time>"
+        );
+        assert_eq!(fades.separator, Separator::Block);
+
+        assert!(
+            matches!(kinds[4], StatementKind::Property(p) if p.name.as_static() == Some("type"))
+        );
+        assert!(matches!(
+            kinds[5],
+            StatementKind::Assignment(a) if a.operator == AssignOperator::ShiftRight
+        ));
+        assert!(matches!(kinds[6], StatementKind::Property(p) if p.annotation.is_none()));
+    }
+
+    #[test]
+    fn keyword_keys_and_bare_hex() {
+        let program = parse_ok(
+            "box {\n    type = \"box\"\n    enum = 3\n    type button\n    import: none\n}\nlocal const code-points = [1FBC, 1FB3]\n",
+        );
+        let StatementKind::Property(entry) = &program.body.statements[0].kind else {
+            panic!("{:?}", program.body.statements[0]);
+        };
+        let Some(ExpressionKind::Dictionary(block)) = entry.value.as_ref().map(|v| &v.kind) else {
+            panic!("{:?}", entry.value);
+        };
+        let keys: Vec<(Option<&str>, &Separator)> = block
+            .statements
+            .iter()
+            .map(|statement| match &statement.kind {
+                StatementKind::Property(p) => (p.name.as_static(), &p.separator),
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (Some("type"), &Separator::Equal),
+                (Some("enum"), &Separator::Equal),
+                (Some("type"), &Separator::Whitespace),
+                (Some("import"), &Separator::Colon),
+            ]
+        );
+
+        let StatementKind::Variable(points) = &program.body.statements[1].kind else {
+            panic!("{:?}", program.body.statements[1]);
+        };
+        let Some(ExpressionKind::Array(items)) = points.value.as_ref().map(|v| &v.kind) else {
+            panic!("{:?}", points.value);
+        };
+        let values: Vec<&ExpressionKind> = items.iter().map(|item| &item.kind).collect();
+        assert_eq!(
+            values,
+            vec![
+                &ExpressionKind::Number(0x1FBC as f64),
+                &ExpressionKind::Number(0x1FB3 as f64)
+            ]
+        );
+    }
+
+    #[test]
+    fn append_and_nested_modification() {
+        let program = parse_ok(
+            "array + Item-3\ndictionary > key-2 + Item-3\ndictionary > new-key value\nparent-3 > parent-2 > child {}\n",
+        );
+        let forms: Vec<(&str, Vec<&str>, AssignOperator)> = program
+            .body
+            .statements
+            .iter()
+            .map(|s| match &s.kind {
+                StatementKind::Assignment(a) => (
+                    a.target.as_static().unwrap(),
+                    a.path.iter().map(|p| p.as_static().unwrap()).collect(),
+                    a.operator,
+                ),
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            forms,
+            [
+                ("array", vec![], AssignOperator::Push),
+                ("dictionary", vec!["key-2"], AssignOperator::Push),
+                ("dictionary", vec!["new-key"], AssignOperator::Set),
+                ("parent-3", vec!["parent-2", "child"], AssignOperator::Set),
+            ]
+        );
+    }
+
+    #[test]
+    fn recovers_after_errors() {
+        let (program, errors) = parse("a = (1 + \nb = 2\nc = ]\nd = 3\n");
+        assert!(!errors.is_empty());
+        let names: Vec<&str> = program
+            .body
+            .statements
+            .iter()
+            .filter_map(|s| match &s.kind {
+                StatementKind::Property(p) => p.name.as_static(),
+                _ => None,
+            })
+            .collect();
+        assert!(names.contains(&"d"), "{:?}", names);
+    }
+
+    #[test]
+    fn reports_lexical_errors() {
+        let (_, errors) = parse("let a = 10°75′\nlet b = \"open\n");
+        let types: Vec<&ParseErrorType> = errors.iter().map(|e| &e.error_type).collect();
+        // За незакрытой строкой следует и ошибка парсера
+        assert_eq!(
+            types[..2],
+            [
+                &ParseErrorType::Lexical(LexicalErrorType::InvalidAngle),
+                &ParseErrorType::Lexical(LexicalErrorType::UnterminatedString),
+            ],
+            "{:?}",
+            errors
+        );
+        assert_eq!(errors[1].position.line, 2);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::ast::expressions::BinaryOperator;
+use crate::lexer::token_type::TokenType;
+
+/// Сила связывания инфиксных операторов, от слабой к сильной
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Lowest,
+    Coalesce,   // ??
+    Or,         // or ||
+    And,        // and &&
+    Equality,   // == != ≣ ~=
+    Comparison, // < <= > >=
+    Range,      // ..
+    Tolerance,  // ± ∓ — `5m + 1m ± 2cm` допуск для суммы
+    Term,       // + - ∸
+    Factor,     // * / // %
+    Power,      // ^ (правоассоциативный)
+    Unary,
+}
+
+impl Precedence {
+    /// Следующий уровень: правый операнд левоассоциативного оператора
+    pub fn next(self) -> Self {
+        match self {
+            Precedence::Lowest => Precedence::Coalesce,
+            Precedence::Coalesce => Precedence::Or,
+            Precedence::Or => Precedence::And,
+            Precedence::And => Precedence::Equality,
+            Precedence::Equality => Precedence::Comparison,
+            Precedence::Comparison => Precedence::Range,
+            Precedence::Range => Precedence::Tolerance,
+            Precedence::Tolerance => Precedence::Term,
+            Precedence::Term => Precedence::Factor,
+            Precedence::Factor => Precedence::Power,
+            Precedence::Power | Precedence::Unary => Precedence::Unary,
+        }
+    }
+}
+
+/// Бинарный оператор и его приоритет для токена
+pub fn infix_operator(token_type: &TokenType) -> Option<(BinaryOperator, Precedence)> {
+    let operator = match token_type {
+        TokenType::QuestionQuestion => (BinaryOperator::Coalesce, Precedence::Coalesce),
+        TokenType::Or | TokenType::PipePipe => (BinaryOperator::Or, Precedence::Or),
+        TokenType::And | TokenType::AmpersandAmpersand => (BinaryOperator::And, Precedence::And),
+        TokenType::EqualEqual => (BinaryOperator::Equal, Precedence::Equality),
+        TokenType::BangEqual => (BinaryOperator::NotEqual, Precedence::Equality),
+        TokenType::EqualEqualEqual => (BinaryOperator::StrictEqual, Precedence::Equality),
+        TokenType::RegExEqual => (BinaryOperator::Matches, Precedence::Equality),
+        TokenType::Less => (BinaryOperator::Less, Precedence::Comparison),
+        TokenType::LessEqual => (BinaryOperator::LessEqual, Precedence::Comparison),
+        TokenType::Greater => (BinaryOperator::Greater, Precedence::Comparison),
+        TokenType::GreaterEqual => (BinaryOperator::GreaterEqual, Precedence::Comparison),
+        TokenType::DotDot => (BinaryOperator::Range, Precedence::Range),
+        TokenType::PlusMinus | TokenType::MinusPlus => {
+            (BinaryOperator::Tolerance, Precedence::Tolerance)
+        }
+        TokenType::Plus => (BinaryOperator::Add, Precedence::Term),
+        TokenType::Minus => (BinaryOperator::Subtract, Precedence::Term),
+        TokenType::DotMinus => (BinaryOperator::Monus, Precedence::Term),
+        TokenType::Star => (BinaryOperator::Multiply, Precedence::Factor),
+        TokenType::Slash => (BinaryOperator::Divide, Precedence::Factor),
+        TokenType::SlashSlash => (BinaryOperator::IntegerDivide, Precedence::Factor),
+        TokenType::Percent => (BinaryOperator::Modulo, Precedence::Factor),
+        TokenType::Caret => (BinaryOperator::Power, Precedence::Power),
+        _ => return None,
+    };
+    Some(operator)
+}
@@ -0,0 +1,434 @@
+use super::parser::Parser;
+use super::precedence::{Precedence, infix_operator};
+use crate::ast::expressions::{Expression, ExpressionKind, Name, Segment};
+use crate::ast::statements::{
+    AssignOperator, Assignment, Binding, ContainerDecl, DeclarationKind, Generator, GeneratorKind,
+    ImportDecl, Modifiers, Property, Separator, Statement, StatementKind, VariableDecl,
+};
+use crate::lexer::token::TokenFlags;
+use crate::lexer::token_type::TokenType;
+use crate::parser::error::ParseErrorType;
+use crate::utils::span::Span;
+
+impl Parser {
+    pub(super) fn parse_statement(&mut self) -> Option<Statement> {
+        let start = self.peek().position;
+        let modifiers = self.parse_modifiers();
+
+        let kind = match self.peek().token_type {
+            TokenType::Variable | TokenType::Constant => self.parse_variable(modifiers)?,
+            TokenType::Container => self.parse_container(modifiers)?,
+            TokenType::Spread | TokenType::Generate => self.parse_generator()?,
+            TokenType::Yield => self.parse_yield()?,
+            TokenType::Import if !self.at_keyword_key() => self.parse_import()?,
+            TokenType::Enum if !self.at_keyword_key() => self.parse_enum(modifiers)?,
+            TokenType::Type | TokenType::Alias
+                if self.peek_next().token_type == TokenType::Identifier
+                    && self.peek_nth(2).token_type == TokenType::Equal =>
+            {
+                self.parse_type_alias(modifiers)?
+            }
+            // `type button`: ключ со значением через пробел
+            TokenType::Type | TokenType::Alias => self.parse_entry(modifiers)?,
+            TokenType::Structure | TokenType::Interface if !self.at_keyword_key() => {
+                StatementKind::Schema(self.parse_object_type()?)
+            }
+            TokenType::Identifier | TokenType::DollarLeftBrace => self.parse_entry(modifiers)?,
+            _ if self.at_keyword_key() => self.parse_entry(modifiers)?,
+            _ => StatementKind::Expression(self.parse_expression()?),
+        };
+        Some(Statement {
+            kind,
+            span: self.span_from(start),
+        })
+    }
+
+    pub(super) fn parse_modifiers(&mut self) -> Modifiers {
+        let mut modifiers = Modifiers::empty();
+        while let Some(modifier) = Modifiers::from_token(&self.peek().token_type) {
+            self.advance();
+            modifiers |= modifier;
+        }
+        modifiers
+    }
+
+    /// Ключевое слово в роли ключа: `type: "box"`, `enum = 3`, `default := 0`
+    fn at_keyword_key(&self) -> bool {
+        self.at_keyword()
+            && matches!(
+                self.peek_next().token_type,
+                TokenType::Colon | TokenType::Equal | TokenType::ColonEqual
+            )
+    }
+
+    fn at_keyword(&self) -> bool {
+        let token = self.peek();
+        token.token_type != TokenType::Identifier
+            && !token.lexeme.is_empty()
+            && token.lexeme.chars().all(|c| c.is_alphabetic())
+    }
+
+    /// Имя ключа или переменной: части вплотную друг к другу
+    pub(super) fn parse_name(&mut self) -> Option<Name> {
+        let start = self.peek().position;
+        let mut name = Name {
+            segments: Vec::new(),
+            span: Span::point(start),
+        };
+
+        loop {
+            if !name.segments.is_empty() && !self.is_glued() {
+                break;
+            }
+            match self.peek().token_type {
+                TokenType::Identifier => {
+                    let token = self.advance();
+                    name.segments.push(Segment::Text(token.lexeme));
+                }
+                TokenType::DollarLeftBrace => {
+                    self.advance();
+                    let expression = self.parse_expression()?;
+                    self.expect(TokenType::RightBrace, "Expected '}' after interpolation")?;
+                    name.segments.push(Segment::Interpolation(expression));
+                }
+                _ => break,
+            }
+        }
+
+        if name.segments.is_empty() {
+            let token = self.peek().clone();
+            self.error_at(
+                &token,
+                ParseErrorType::ExpectedName,
+                &format!("Expected a name, found '{}'", token.lexeme),
+            );
+            return None;
+        }
+        name.span = self.span_from(start);
+        Some(name)
+    }
+
+    fn parse_key_name(&mut self) -> Option<Name> {
+        if self.at_keyword() {
+            let token = self.advance();
+            return Some(Name::new(token.lexeme.clone(), Span::of_token(&token)));
+        }
+        self.parse_name()
+    }
+
+    /// `let x<T> = v`, `const x v`, `local x = v`
+    fn parse_variable(&mut self, modifiers: Modifiers) -> Option<StatementKind> {
+        let kind = match self.advance().token_type {
+            TokenType::Constant => DeclarationKind::Constant,
+            _ => DeclarationKind::Variable,
+        };
+        let name = self.parse_name()?;
+        self.parse_variable_rest(kind, modifiers, name)
+    }
+
+    fn parse_variable_rest(
+        &mut self,
+        kind: DeclarationKind,
+        modifiers: Modifiers,
+        name: Name,
+    ) -> Option<StatementKind> {
+        let annotation = if self.check(&TokenType::Less) && self.is_glued() {
+            Some(self.parse_annotation()?)
+        } else {
+            None
+        };
+        let value = match self.peek().token_type {
+            TokenType::Equal | TokenType::Colon | TokenType::ColonEqual => {
+                self.advance();
+                Some(self.parse_value()?)
+            }
+            _ if self.at_indented_block() => Some(self.parse_value()?),
+            _ if self.at_statement_end() => None,
+            _ => Some(self.parse_value()?),
+        };
+        Some(StatementKind::Variable(VariableDecl {
+            kind,
+            modifiers,
+            name,
+            annotation,
+            value,
+        }))
+    }
+
+    /// Значение ключа: выражение, `{ ... }` или блок отступом
+    fn parse_value(&mut self) -> Option<Expression> {
+        if self.at_indented_block() {
+            let block = self.parse_indented_block();
+            let span = block.span;
+            return Some(Expression::new(ExpressionKind::Dictionary(block), span));
+        }
+        self.parse_expression()
+    }
+
+    /// Запись, начинающаяся с имени: ключ, изменение значения или выражение
+    fn parse_entry(&mut self, modifiers: Modifiers) -> Option<StatementKind> {
+        let name = self.parse_key_name()?;
+        if modifiers.declares_binding() {
+            return self.parse_variable_rest(DeclarationKind::Variable, modifiers, name);
+        }
+
+        if let Some(operator) = assign_operator(&self.peek().token_type) {
+            return self.parse_assignment(name, Vec::new(), operator);
+        }
+        if self.check(&TokenType::Plus) {
+            return self.parse_assignment(name, Vec::new(), AssignOperator::Push);
+        }
+        if self.check(&TokenType::Greater) {
+            return self.parse_nested_assignment(name);
+        }
+
+        let annotation = if self.at_annotation() {
+            Some(self.parse_annotation()?)
+        } else {
+            None
+        };
+        let (separator, value) = match self.peek().token_type {
+            TokenType::Colon => {
+                self.advance();
+                (Separator::Colon, Some(self.parse_value()?))
+            }
+            TokenType::Equal | TokenType::ColonEqual => {
+                self.advance();
+                (Separator::Equal, Some(self.parse_value()?))
+            }
+            TokenType::LeftBrace => (Separator::Block, Some(self.parse_value()?)),
+            _ if self.at_indented_block() => (Separator::Block, Some(self.parse_value()?)),
+            _ if self.at_statement_end() => (Separator::None, None),
+            _ if annotation.is_none() && self.continues_expression() => {
+                let span = name.span;
+                let left = Expression::new(ExpressionKind::Identifier(name), span);
+                let expression = self.parse_infix(left, Precedence::Lowest)?;
+                return Some(StatementKind::Expression(expression));
+            }
+            _ => (Separator::Whitespace, Some(self.parse_expression()?)),
+        };
+        Some(StatementKind::Property(Property {
+            modifiers,
+            name,
+            annotation,
+            separator,
+            value,
+        }))
+    }
+
+    /// Значение после оператора изменения; оператор еще не поглощен
+    fn parse_assignment(
+        &mut self,
+        target: Name,
+        path: Vec<Name>,
+        operator: AssignOperator,
+    ) -> Option<StatementKind> {
+        self.advance();
+        let value = match operator {
+            AssignOperator::Increment | AssignOperator::Decrement => None,
+            _ => Some(self.parse_expression()?),
+        };
+        Some(StatementKind::Assignment(Assignment {
+            target,
+            path,
+            operator,
+            value,
+        }))
+    }
+
+    /// `dictionary > key value`, `parent > child > key + item`, `child > key {}`
+    fn parse_nested_assignment(&mut self, target: Name) -> Option<StatementKind> {
+        let mut path = Vec::new();
+        while self.check(&TokenType::Greater) {
+            self.advance();
+            path.push(self.parse_key_name()?);
+        }
+
+        if let Some(operator) = assign_operator(&self.peek().token_type) {
+            return self.parse_assignment(target, path, operator);
+        }
+        if self.check(&TokenType::Plus) {
+            return self.parse_assignment(target, path, AssignOperator::Push);
+        }
+        let value = match self.peek().token_type {
+            TokenType::Colon | TokenType::Equal | TokenType::ColonEqual => {
+                self.advance();
+                Some(self.parse_value()?)
+            }
+            TokenType::LeftBrace => Some(self.parse_value()?),
+            _ if self.at_indented_block() => Some(self.parse_value()?),
+            _ if self.at_statement_end() => None,
+            _ => Some(self.parse_expression()?),
+        };
+        Some(StatementKind::Assignment(Assignment {
+            target,
+            path,
+            operator: AssignOperator::Set,
+            value,
+        }))
+    }
+
+    /// После имени идет оператор, а не значение. `a - b` — выражение,
+    /// `opacity -1.0` (минус вплотную к числу) — ключ со значением.
+    fn continues_expression(&self) -> bool {
+        let token = self.peek();
+        let glued = self.is_glued();
+        match token.token_type {
+            TokenType::Dot | TokenType::ColonColon => true,
+            TokenType::LeftParen | TokenType::LeftBracket => glued,
+            TokenType::Minus => {
+                glued
+                    || self
+                        .peek_next()
+                        .flags
+                        .contains(TokenFlags::HAS_PRECEDING_WHITESPACE)
+            }
+            ref other => infix_operator(other).is_some(),
+        }
+    }
+
+    /// `container App` + тело в скобках или отступом
+    fn parse_container(&mut self, modifiers: Modifiers) -> Option<StatementKind> {
+        self.advance();
+        let name = self.parse_name()?;
+        let body = self.parse_body()?;
+        Some(StatementKind::Container(ContainerDecl {
+            modifiers,
+            name,
+            body,
+        }))
+    }
+
+    /// `spread <источник> as [local] [let|const] <имя | (имена)> { ... }`
+    fn parse_generator(&mut self) -> Option<StatementKind> {
+        let kind = match self.advance().token_type {
+            TokenType::Generate => GeneratorKind::Generate,
+            _ => GeneratorKind::Spread,
+        };
+        let source = self.parse_expression()?;
+
+        let binding = if self.check(&TokenType::As) {
+            let start = self.advance().position;
+            let modifiers = self.parse_modifiers();
+            let kind = match self.peek().token_type {
+                TokenType::Constant => {
+                    self.advance();
+                    DeclarationKind::Constant
+                }
+                TokenType::Variable => {
+                    self.advance();
+                    DeclarationKind::Variable
+                }
+                _ => DeclarationKind::Variable,
+            };
+            let names = if self.check(&TokenType::LeftParen) {
+                self.advance();
+                let mut names = vec![self.parse_name()?];
+                while self.check(&TokenType::Comma) {
+                    self.advance();
+                    names.push(self.parse_name()?);
+                }
+                self.expect(TokenType::RightParen, "Expected ')' after binding names")?;
+                names
+            } else {
+                vec![self.parse_name()?]
+            };
+            Some(Binding {
+                kind,
+                modifiers,
+                names,
+                span: self.span_from(start),
+            })
+        } else {
+            None
+        };
+
+        let body = self.parse_body()?;
+        Some(StatementKind::Generator(Generator {
+            kind,
+            source,
+            binding,
+            body,
+        }))
+    }
+
+    /// `import a, b from "path"`, `import "path"`
+    fn parse_import(&mut self) -> Option<StatementKind> {
+        self.advance();
+        let mut names = Vec::new();
+        if !self.at_string() {
+            names.push(self.parse_name()?);
+            while self.check(&TokenType::Comma) {
+                self.advance();
+                names.push(self.parse_name()?);
+            }
+            self.expect(TokenType::From, "Expected 'from' after imported names")?;
+        }
+        if !self.at_string() {
+            let token = self.peek().clone();
+            self.error_at(
+                &token,
+                ParseErrorType::UnexpectedToken(token.token_type.clone()),
+                &format!("Expected a file path, found '{}'", token.lexeme),
+            );
+            return None;
+        }
+        let source = self.parse_expression()?;
+        Some(StatementKind::Import(ImportDecl { names, source }))
+    }
+
+    fn at_string(&self) -> bool {
+        matches!(
+            self.peek().token_type,
+            TokenType::String
+                | TokenType::SingleQuotedString
+                | TokenType::GraveQuotedString
+                | TokenType::MultilineString
+        )
+    }
+
+    /// `yield name { ... }`, `yield name value`
+    fn parse_yield(&mut self) -> Option<StatementKind> {
+        self.advance();
+        let name = self.parse_name()?;
+        let (separator, value) = match self.peek().token_type {
+            TokenType::Colon | TokenType::Equal => {
+                let separator = if self.advance().token_type == TokenType::Colon {
+                    Separator::Colon
+                } else {
+                    Separator::Equal
+                };
+                (separator, Some(self.parse_value()?))
+            }
+            TokenType::LeftBrace => (Separator::Block, Some(self.parse_value()?)),
+            _ if self.at_indented_block() => (Separator::Block, Some(self.parse_value()?)),
+            _ if self.at_statement_end() => (Separator::None, None),
+            _ => (Separator::Whitespace, Some(self.parse_expression()?)),
+        };
+        Some(StatementKind::Yield(Property {
+            modifiers: Modifiers::empty(),
+            name,
+            annotation: None,
+            separator,
+            value,
+        }))
+    }
+}
+
+fn assign_operator(token_type: &TokenType) -> Option<AssignOperator> {
+    let operator = match token_type {
+        TokenType::PlusEqual => AssignOperator::Add,
+        TokenType::MinusEqual => AssignOperator::Subtract,
+        TokenType::StarEqual => AssignOperator::Multiply,
+        TokenType::SlashEqual => AssignOperator::Divide,
+        TokenType::PercentEqual => AssignOperator::Modulo,
+        TokenType::SlashSlashEqual => AssignOperator::IntegerDivide,
+        TokenType::DotEqual => AssignOperator::Append,
+        TokenType::LessLessEqual => AssignOperator::ShiftLeft,
+        TokenType::GreaterGreaterEqual => AssignOperator::ShiftRight,
+        TokenType::PlusPlus => AssignOperator::Increment,
+        TokenType::MinusMinus => AssignOperator::Decrement,
+        _ => return None,
+    };
+    Some(operator)
+}
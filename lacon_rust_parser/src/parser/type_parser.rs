@@ -0,0 +1,317 @@
+use super::parser::Parser;
+use crate::ast::expressions::{ExpressionKind, Name};
+use crate::ast::statements::{
+    EnumDecl, EnumMember, EnumValue, Modifiers, StatementKind, TypeAlias, TypeAliasKind,
+};
+use crate::ast::types::{FieldType, ObjectKind, ObjectType, TypeAnnotation, TypeKind};
+use crate::lexer::token::{Token, TokenFlags};
+use crate::lexer::token_type::TokenType;
+use crate::parser::error::ParseErrorType;
+use crate::utils::span::Span;
+
+impl Parser {
+    /// `<` вплотную к имени ключа и закрытая аннотация: `ok<Button> { ... }`,
+    /// `sizes<Array<Length>>: [...]`. Без закрывающей `>` это сравнение,
+    /// а не аннотация.
+    pub(super) fn at_annotation(&self) -> bool {
+        if !self.check(&TokenType::Less) || !self.is_glued() {
+            return false;
+        }
+        let mut depth = 1usize;
+        let mut expect_name = true;
+        for n in 1.. {
+            let token = self.peek_nth(n);
+            match token.token_type {
+                TokenType::Identifier if expect_name => expect_name = false,
+                TokenType::Less | TokenType::Comma | TokenType::Pipe if !expect_name => {
+                    if token.token_type == TokenType::Less {
+                        depth += 1;
+                    }
+                    expect_name = true;
+                }
+                TokenType::Greater | TokenType::GreaterGreater if !expect_name => {
+                    let closed = if token.token_type == TokenType::Greater {
+                        1
+                    } else {
+                        2
+                    };
+                    if closed > depth {
+                        return false;
+                    }
+                    depth -= closed;
+                    if depth == 0 {
+                        return true;
+                    }
+                }
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// `<Speed>` после имени объявления
+    pub(super) fn parse_annotation(&mut self) -> Option<TypeAnnotation> {
+        let open = self.expect(TokenType::Less, "Expected '<'")?;
+        let annotation = self.parse_type()?;
+        self.expect_closing_angle("Expected '>' after type")?;
+        Some(TypeAnnotation {
+            kind: annotation.kind,
+            span: self.span_from(open.position),
+        })
+    }
+
+    /// `Length | Percent`; без `|` — один тип
+    pub(super) fn parse_type(&mut self) -> Option<TypeAnnotation> {
+        let start = self.peek().position;
+        let first = self.parse_type_member()?;
+        if !self.check(&TokenType::Pipe) {
+            return Some(first);
+        }
+        let mut members = vec![first];
+        while self.check(&TokenType::Pipe) {
+            self.advance();
+            members.push(self.parse_type_member()?);
+        }
+        Some(TypeAnnotation {
+            kind: TypeKind::Union(members),
+            span: self.span_from(start),
+        })
+    }
+
+    /// `Length` или `Dictionary<String, Time>`
+    fn parse_type_member(&mut self) -> Option<TypeAnnotation> {
+        let token = self.peek().clone();
+        if token.token_type != TokenType::Identifier {
+            self.error_at(
+                &token,
+                ParseErrorType::ExpectedName,
+                &format!("Expected a type name, found '{}'", token.lexeme),
+            );
+            return None;
+        }
+        self.advance();
+        if !(self.check(&TokenType::Less) && self.is_glued()) {
+            return Some(TypeAnnotation {
+                kind: TypeKind::Named(token.lexeme),
+                span: self.span_from(token.position),
+            });
+        }
+
+        self.advance();
+        let mut arguments = vec![self.parse_type()?];
+        while self.check(&TokenType::Comma) {
+            self.advance();
+            arguments.push(self.parse_type()?);
+        }
+        self.expect_closing_angle("Expected '>' after type arguments")?;
+        Some(TypeAnnotation {
+            kind: TypeKind::Generic {
+                name: token.lexeme,
+                arguments,
+            },
+            span: self.span_from(token.position),
+        })
+    }
+
+    /// `>`; `>>` в `Array<Array<Length>>` закрывает две аннотации сразу
+    fn expect_closing_angle(&mut self, message: &str) -> Option<Token> {
+        if self.check(&TokenType::GreaterGreater) {
+            self.split_token(TokenType::Greater, TokenType::Greater);
+        }
+        self.expect(TokenType::Greater, message)
+    }
+
+    /// `type Dim = Length | Percent`, `alias Px = Length`
+    pub(super) fn parse_type_alias(&mut self, modifiers: Modifiers) -> Option<StatementKind> {
+        let kind = match self.advance().token_type {
+            TokenType::Alias => TypeAliasKind::Alias,
+            _ => TypeAliasKind::Type,
+        };
+        let name = self.parse_name()?;
+        self.expect(TokenType::Equal, "Expected '=' after type name")?;
+        let annotation = self.parse_type()?;
+        Some(StatementKind::TypeAlias(TypeAlias {
+            kind,
+            modifiers,
+            name,
+            annotation,
+        }))
+    }
+
+    /// `struct Name [extends A, B] [implements I, J]` + поля в скобках или отступом
+    pub(super) fn parse_object_type(&mut self) -> Option<ObjectType> {
+        let keyword = self.advance();
+        let kind = match keyword.token_type {
+            TokenType::Interface => ObjectKind::Interface,
+            _ => ObjectKind::Struct,
+        };
+        let name = self.parse_name()?;
+
+        let mut extends = Vec::new();
+        let mut implements = Vec::new();
+        loop {
+            let list = match self.peek().token_type {
+                TokenType::Extends => &mut extends,
+                TokenType::Implements => &mut implements,
+                _ => break,
+            };
+            self.advance();
+            list.push(self.parse_type()?);
+            while self.check(&TokenType::Comma) {
+                self.advance();
+                list.push(self.parse_type()?);
+            }
+        }
+
+        let (fields, _) = self.parse_body_sequence(Self::parse_field)?;
+        Some(ObjectType {
+            kind,
+            name,
+            extends,
+            implements,
+            fields,
+            span: self.span_from(keyword.position),
+        })
+    }
+
+    /// `name[?][<Type>] [in [a, b]] [= default]`
+    fn parse_field(&mut self) -> Option<FieldType> {
+        let start = self.peek().position;
+        let name = self.parse_field_name()?;
+
+        let optional = self.check(&TokenType::Question) && self.is_glued();
+        if optional {
+            self.advance();
+        }
+        let annotation = if self.check(&TokenType::Less) {
+            Some(self.parse_annotation()?)
+        } else {
+            None
+        };
+
+        let allowed = if self.check(&TokenType::In) {
+            self.advance();
+            if !self.check(&TokenType::LeftBracket) {
+                self.expect(TokenType::LeftBracket, "Expected '[' with allowed values")?;
+            }
+            match self.parse_expression()?.kind {
+                ExpressionKind::Array(items) => items,
+                _ => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+
+        let default = match self.peek().token_type {
+            TokenType::Equal | TokenType::Colon => {
+                self.advance();
+                Some(self.parse_expression()?)
+            }
+            _ => None,
+        };
+
+        Some(FieldType {
+            name,
+            optional,
+            annotation,
+            allowed,
+            default,
+            span: self.span_from(start),
+        })
+    }
+
+    /// `enum Name` + члены через запятую или по одному на строке
+    pub(super) fn parse_enum(&mut self, modifiers: Modifiers) -> Option<StatementKind> {
+        self.advance();
+        let name = self.parse_name()?;
+        let (lines, _) = self.parse_body_sequence(Self::parse_enum_line)?;
+        Some(StatementKind::Enum(EnumDecl {
+            modifiers,
+            name,
+            members: lines.into_iter().flatten().collect(),
+        }))
+    }
+
+    /// `start, center, end`; запятая в конце строки допустима
+    fn parse_enum_line(&mut self) -> Option<Vec<EnumMember>> {
+        let mut members = vec![self.parse_enum_member()?];
+        while self.check(&TokenType::Comma) {
+            self.advance();
+            if self.at_statement_end() {
+                break;
+            }
+            members.push(self.parse_enum_member()?);
+        }
+        Some(members)
+    }
+
+    fn parse_enum_member(&mut self) -> Option<EnumMember> {
+        let start = self.peek().position;
+        let name = self.parse_field_name()?;
+        let value = match self.peek().token_type {
+            TokenType::Equal | TokenType::Colon => {
+                self.advance();
+                Some(self.parse_enum_value()?)
+            }
+            _ => None,
+        };
+        Some(EnumMember {
+            name,
+            value,
+            span: self.span_from(start),
+        })
+    }
+
+    /// `U+2190` — кодовая точка (сканер делит ее на `U`, `+` и цифры;
+    /// `00B5` при этом читается как число с единицей), остальное — выражение
+    fn parse_enum_value(&mut self) -> Option<EnumValue> {
+        let token = self.peek().clone();
+        let code_point = token.token_type == TokenType::Identifier
+            && token.lexeme == "U"
+            && self.peek_nth(1).token_type == TokenType::Plus
+            && !self
+                .peek_nth(1)
+                .flags
+                .contains(TokenFlags::HAS_PRECEDING_WHITESPACE);
+        if !code_point {
+            return Some(EnumValue::Expression(self.parse_expression()?));
+        }
+
+        self.advance();
+        self.advance();
+        let mut digits = String::new();
+        while self.is_glued()
+            && !self.peek().lexeme.is_empty()
+            && self.peek().lexeme.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            digits.push_str(&self.advance().lexeme);
+        }
+        match u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+        {
+            Some(character) => Some(EnumValue::CodePoint(character)),
+            None => {
+                self.error_at(
+                    &token,
+                    ParseErrorType::ExpectedExpression,
+                    &format!("Invalid code point 'U+{}'", digits),
+                );
+                None
+            }
+        }
+    }
+
+    /// Имя поля; ключевые слова тоже подходят: `type<String>`
+    fn parse_field_name(&mut self) -> Option<Name> {
+        let token = self.peek();
+        if token.token_type != TokenType::Identifier
+            && !token.lexeme.is_empty()
+            && token.lexeme.chars().all(|c| c.is_alphabetic())
+        {
+            let token = self.advance();
+            return Some(Name::new(token.lexeme.clone(), Span::of_token(&token)));
+        }
+        self.parse_name()
+    }
+}
@@ -0,0 +1,834 @@
+// Разрешение имен. Каждое использование имени связывается с объявлением
+// в таблице символов; имена, не найденные нигде, — ошибка.
+//
+// Правила видимости:
+// 1. Документ, контейнер и словарь — декларативные области. Документ — набор
+//    определений, а не программа: ключи, `let` и `const` видны во всей области,
+//    в том числе выше объявления (`a = b + 1` до `b = 2`). Порядок вычисления
+//    определяется зависимостями, а не порядком строк.
+// 2. Тело генератора выполняется по шагам, поэтому область последовательная:
+//    `local code-point = ...` видно только в записях после объявления,
+//    а само значение такой записи видит внешнее имя (`local x = x + 1`).
+//    Привязки `as let name` и неявный `index` видны во всем теле.
+// 3. Поиск идет от текущей области наружу; ближайшее объявление скрывает внешние.
+// 4. `global` помещает объявление в область документа независимо от вложенности.
+// 5. Имена со вставками (`winter-${a}`) известны только при вычислении и
+//    в таблицу не попадают; имена внутри вставок разрешаются как обычно.
+// 6. Идентификатор, который целиком является значением ключа (`const word snow`),
+//    может быть словом-строкой: если имя не найдено, ошибки нет.
+//    В остальных местах (операнды, аргументы) ненайденное имя — ошибка.
+// 7. Значение записи не видит ее собственное имя: в `code-point: `U+${code-point}``
+//    справа — внешний `code-point`, как и в `local x = x + 1`.
+// 8. Член объекта (`app.gap`, `this.width`) ищется только в теле объекта.
+//    `this` — ближайший словарь или контейнер (тело генератора пропускается),
+//    `parent` и `super` — объект, в который вложен `this`, `root` и `origin` —
+//    документ, `here` — текущая область, включая тело генератора.
+//    Член, которого нет в теле, не ошибка: его может добавить генератор.
+//    Исключение — перечисления: `Align.middle` без такого члена — ошибка;
+//    `Align::center` — то же, что `Align.center`.
+// 9. `internal` член виден только внутри своего объекта: `app.gap` снаружи
+//    `app` — ошибка. Остальные правила видимости — в visibility.rs.
+//
+// Зависимости между значениями и порядок вычисления — в dependency_graph.rs.
+//
+// Повторные объявления в одной области:
+// - `let`/`const`/контейнер с уже объявленным именем — ошибка, указывающая
+//   на первое объявление;
+// - ключ с именем `let` в той же области — присваивание (`let x = 1`, `x = 2`);
+//   для `const`, `final` и `static` это ошибка, как и `x += 1`, `x++`;
+// - повтор ключа ключом — дело линта повторяющихся ключей.
+// Объявление `let`/`const`, скрывающее привязку внешней области, допустимо,
+// если не выключено `AnalyzerOptions::allow_shadowing`.
+
+use super::dependency_graph::DependencyGraph;
+use super::error::{SemanticError, SemanticErrorType};
+use super::scope::{ScopeId, ScopeKind};
+use super::symbol_table::{Reference, Symbol, SymbolId, SymbolKind, SymbolTable};
+use super::visibility::Visibility;
+use crate::ast::expressions::{ContextKeyword, Expression, ExpressionKind, Name, Segment};
+use crate::ast::program::Program;
+use crate::ast::statements::{
+    Block, DeclarationKind, EnumValue, Generator, Modifiers, Statement, StatementKind,
+};
+use crate::interpreter::stdlib::units::constant;
+use crate::lexer::position::Position;
+use crate::utils::span::Span;
+
+/// Функции, доступные в любом документе
+const BUILTIN_FUNCTIONS: &[&str] = &["format", "select"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalyzerOptions {
+    /// Разрешено ли скрывать внешние `let`/`const` одноименными объявлениями
+    pub allow_shadowing: bool,
+}
+
+impl Default for AnalyzerOptions {
+    fn default() -> Self {
+        Self {
+            allow_shadowing: true,
+        }
+    }
+}
+
+/// Результат анализа документа
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub table: SymbolTable,
+    pub errors: Vec<SemanticError>,
+    /// Зависимости между значениями и порядок их вычисления
+    pub graph: DependencyGraph,
+}
+
+pub struct Analyzer {
+    table: SymbolTable,
+    errors: Vec<SemanticError>,
+    scope: ScopeId,
+    /// Запись, значение которой сейчас разбирается (правило 7)
+    defining: Option<SymbolId>,
+    options: AnalyzerOptions,
+}
+
+impl Analyzer {
+    pub fn analyze(program: &Program) -> Analysis {
+        Self::analyze_with(program, AnalyzerOptions::default())
+    }
+
+    pub fn analyze_with(program: &Program, options: AnalyzerOptions) -> Analysis {
+        let table = SymbolTable::new(program.body.span);
+        let mut analyzer = Analyzer {
+            scope: table.root(),
+            table,
+            errors: Vec::new(),
+            defining: None,
+            options,
+        };
+        analyzer.visit_block(&program.body);
+
+        let graph = DependencyGraph::build(&analyzer.table);
+        analyzer.errors.extend(graph.cycle_errors(&analyzer.table));
+        Analysis {
+            table: analyzer.table,
+            errors: analyzer.errors,
+            graph,
+        }
+    }
+
+    /// Объявления блока регистрируются до обхода записей (правила 1 и 2)
+    fn visit_block(&mut self, block: &Block) {
+        let symbols: Vec<Option<SymbolId>> = block
+            .statements
+            .iter()
+            .map(|statement| self.declare(statement))
+            .collect();
+        for (statement, symbol) in block.statements.iter().zip(symbols) {
+            self.visit_statement(statement, symbol);
+        }
+    }
+
+    fn declare(&mut self, statement: &Statement) -> Option<SymbolId> {
+        let (name, kind, modifiers) = match &statement.kind {
+            StatementKind::Variable(decl) => {
+                let kind = match decl.kind {
+                    DeclarationKind::Variable => SymbolKind::Variable,
+                    DeclarationKind::Constant => SymbolKind::Constant,
+                };
+                (&decl.name, kind, decl.modifiers)
+            }
+            StatementKind::Property(property) => {
+                (&property.name, SymbolKind::Key, property.modifiers)
+            }
+            StatementKind::Container(container) => {
+                (&container.name, SymbolKind::Container, container.modifiers)
+            }
+            StatementKind::Enum(decl) => (&decl.name, SymbolKind::Enum, decl.modifiers),
+            StatementKind::Import(import) => {
+                for name in &import.names {
+                    let end = statement.span.end;
+                    self.declare_name(
+                        name,
+                        SymbolKind::Import,
+                        Modifiers::empty(),
+                        self.scope,
+                        end,
+                    );
+                }
+                return None;
+            }
+            _ => return None,
+        };
+        let scope = if modifiers.contains(Modifiers::GLOBAL) {
+            self.table.root()
+        } else {
+            self.scope
+        };
+        self.declare_name(name, kind, modifiers, scope, statement.span.end)
+    }
+
+    fn declare_name(
+        &mut self,
+        name: &Name,
+        kind: SymbolKind,
+        modifiers: Modifiers,
+        scope: ScopeId,
+        visible_from: Position,
+    ) -> Option<SymbolId> {
+        let text = name.as_static()?;
+        let id = self.table.declare(Symbol {
+            name: text.to_string(),
+            kind,
+            modifiers,
+            span: name.span,
+            scope,
+            visible_from,
+            body: None,
+        });
+        if kind != SymbolKind::Implicit {
+            self.check_redeclaration(id);
+        }
+        Some(id)
+    }
+
+    fn check_redeclaration(&mut self, id: SymbolId) {
+        let symbol = self.table.symbol(id);
+        let original = Some(self.original_of(id))
+            .filter(|&other| other != id)
+            .map(|other| self.table.symbol(other));
+
+        let error = match original {
+            None => self.shadowing_error(symbol),
+            Some(original) if symbol.kind != SymbolKind::Key => Some(SemanticError::new(
+                SemanticErrorType::DuplicateDeclaration {
+                    name: symbol.name.clone(),
+                    original: original.span,
+                },
+                symbol.span,
+                format!("'{}' is already declared at {}", symbol.name, original.span),
+            )),
+            Some(original) if original.is_immutable() => {
+                Some(Self::immutable_error(original, symbol.span))
+            }
+            Some(_) => None,
+        };
+        self.errors.extend(error);
+    }
+
+    /// Первое объявление имени в области символа: присваивания ключом
+    /// (`x = 2` после `let x`) не меняют изменяемость привязки
+    fn original_of(&self, id: SymbolId) -> SymbolId {
+        let symbol = self.table.symbol(id);
+        self.table
+            .scope(symbol.scope)
+            .declared(&symbol.name)
+            .first()
+            .copied()
+            .unwrap_or(id)
+    }
+
+    /// Привязка скрывает `let`/`const` внешней области
+    fn shadowing_error(&self, symbol: &Symbol) -> Option<SemanticError> {
+        if self.options.allow_shadowing || !symbol.is_binding() {
+            return None;
+        }
+        let parent = self.table.scope(symbol.scope).parent?;
+        let outer =
+            self.table
+                .symbol(self.table.lookup(parent, &symbol.name, symbol.span.start)?);
+        outer.is_binding().then(|| {
+            SemanticError::new(
+                SemanticErrorType::ShadowedBinding {
+                    name: symbol.name.clone(),
+                    original: outer.span,
+                },
+                symbol.span,
+                format!(
+                    "'{}' shadows the binding declared at {}",
+                    symbol.name, outer.span
+                ),
+            )
+        })
+    }
+
+    fn immutable_error(original: &Symbol, span: Span) -> SemanticError {
+        SemanticError::new(
+            SemanticErrorType::ImmutableAssignment {
+                name: original.name.clone(),
+                original: original.span,
+            },
+            span,
+            format!(
+                "Cannot assign to '{}', it is immutable (declared at {})",
+                original.name, original.span
+            ),
+        )
+    }
+
+    fn visit_statement(&mut self, statement: &Statement, symbol: Option<SymbolId>) {
+        let outer = std::mem::replace(&mut self.defining, symbol);
+        match &statement.kind {
+            StatementKind::Variable(decl) => {
+                self.visit_name(&decl.name);
+                self.visit_value(decl.value.as_ref(), symbol);
+            }
+            StatementKind::Property(property) | StatementKind::Yield(property) => {
+                self.visit_name(&property.name);
+                self.visit_value(property.value.as_ref(), symbol);
+            }
+            StatementKind::Assignment(assignment) => {
+                match assignment.target.as_static() {
+                    Some(name) => {
+                        let target = self.resolve(name, assignment.target.span);
+                        if let Some(original) =
+                            target.map(|id| self.table.symbol(self.original_of(id)))
+                            && original.is_immutable()
+                        {
+                            let error = Self::immutable_error(original, assignment.target.span);
+                            self.errors.push(error);
+                        }
+                    }
+                    None => self.visit_name(&assignment.target),
+                }
+                if let Some(value) = &assignment.value {
+                    self.visit_expression(value);
+                }
+            }
+            StatementKind::Container(container) => {
+                let scope = self.enter(ScopeKind::Container, container.body.span);
+                self.visit_block(&container.body);
+                self.scope = self.table.scope(scope).parent.unwrap();
+                if let Some(symbol) = symbol {
+                    self.table.set_body(symbol, scope);
+                }
+            }
+            StatementKind::Generator(generator) => self.visit_generator(generator, statement.span),
+            StatementKind::Schema(schema) => {
+                // Имена схем — отдельное пространство типов, в таблицу не попадают
+                for field in &schema.fields {
+                    field
+                        .allowed
+                        .iter()
+                        .for_each(|item| self.visit_value(Some(item), None));
+                    self.visit_value(field.default.as_ref(), None);
+                }
+            }
+            StatementKind::Enum(decl) => {
+                let scope = self.enter(ScopeKind::Enum, statement.span);
+                for member in &decl.members {
+                    let end = member.span.end;
+                    self.declare_name(
+                        &member.name,
+                        SymbolKind::EnumMember,
+                        Modifiers::empty(),
+                        scope,
+                        end,
+                    );
+                    if let Some(EnumValue::Expression(value)) = &member.value {
+                        self.visit_expression(value);
+                    }
+                }
+                self.scope = self.table.scope(scope).parent.unwrap();
+                if let Some(symbol) = symbol {
+                    self.table.set_body(symbol, scope);
+                }
+            }
+            // Синонимы типов, как и схемы, в таблицу не попадают
+            StatementKind::TypeAlias(_) => {}
+            StatementKind::Import(import) => self.visit_expression(&import.source),
+            StatementKind::Expression(expression) => self.visit_expression(expression),
+        }
+        self.defining = outer;
+    }
+
+    fn visit_generator(&mut self, generator: &Generator, span: Span) {
+        self.visit_expression(&generator.source);
+
+        let scope = self.enter(ScopeKind::Generator, generator.body.span);
+        // Неявный `index` и привязки видны с начала тела
+        let start = generator.body.span.start;
+        let index = Name::new("index", span);
+        self.declare_name(
+            &index,
+            SymbolKind::Implicit,
+            Modifiers::empty(),
+            scope,
+            start,
+        );
+
+        if let Some(binding) = &generator.binding {
+            let kind = match binding.kind {
+                DeclarationKind::Variable => SymbolKind::Variable,
+                DeclarationKind::Constant => SymbolKind::Constant,
+            };
+            for name in &binding.names {
+                self.declare_name(name, kind, binding.modifiers, scope, start);
+            }
+        }
+        self.visit_block(&generator.body);
+        self.scope = self.table.scope(scope).parent.unwrap();
+    }
+
+    /// Значение ключа или объявления (правило 6 для слов-строк)
+    fn visit_value(&mut self, value: Option<&Expression>, symbol: Option<SymbolId>) {
+        let Some(value) = value else {
+            return;
+        };
+        if let Some(name) = value.as_identifier() {
+            if let Some(id) = self.lookup(name, value.span) {
+                self.reference(name, value.span, Some(id));
+            }
+            return;
+        }
+        if let (ExpressionKind::Dictionary(block), Some(symbol)) = (&value.kind, symbol) {
+            let scope = self.visit_dictionary(block);
+            self.table.set_body(symbol, scope);
+            return;
+        }
+        self.visit_expression(value);
+    }
+
+    fn visit_dictionary(&mut self, block: &Block) -> ScopeId {
+        let scope = self.enter(ScopeKind::Dictionary, block.span);
+        self.visit_block(block);
+        self.scope = self.table.scope(scope).parent.unwrap();
+        scope
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match &expression.kind {
+            ExpressionKind::Identifier(name) => match name.as_static() {
+                Some(text) => {
+                    self.resolve(text, name.span);
+                }
+                None => self.visit_name(name),
+            },
+            ExpressionKind::String(segments) => self.visit_segments(segments),
+            ExpressionKind::Unary { operand, .. } => self.visit_expression(operand),
+            ExpressionKind::Binary { left, right, .. } => {
+                self.visit_expression(left);
+                self.visit_expression(right);
+            }
+            ExpressionKind::Call { callee, arguments } => {
+                let builtin = callee
+                    .as_identifier()
+                    .is_some_and(|name| BUILTIN_FUNCTIONS.contains(&name));
+                if !builtin {
+                    self.visit_expression(callee);
+                }
+                for argument in arguments {
+                    self.visit_expression(argument);
+                }
+            }
+            ExpressionKind::Member { .. } => {
+                self.visit_member(expression);
+            }
+            ExpressionKind::Index { object, index } => {
+                self.visit_expression(object);
+                self.visit_expression(index);
+            }
+            ExpressionKind::Array(items) | ExpressionKind::Tuple(items) => {
+                for item in items {
+                    self.visit_expression(item);
+                }
+            }
+            ExpressionKind::Dictionary(block) => {
+                self.visit_dictionary(block);
+            }
+            ExpressionKind::Number(_)
+            | ExpressionKind::Unit { .. }
+            | ExpressionKind::Color(_)
+            | ExpressionKind::Boolean(_)
+            | ExpressionKind::Nil
+            | ExpressionKind::None
+            | ExpressionKind::Undefined
+            | ExpressionKind::Context(_)
+            | ExpressionKind::Hole => {}
+        }
+    }
+
+    /// Цепочка `this.size.width`, `app.gap`: член ищется в теле объекта слева
+    /// (правило 8). Возвращает символ, который обозначает выражение.
+    fn visit_member(&mut self, expression: &Expression) -> Option<SymbolId> {
+        match &expression.kind {
+            ExpressionKind::Member { object, member, .. } => {
+                let (owner, body) = match &object.kind {
+                    ExpressionKind::Context(keyword) => (None, self.context_scope(*keyword)),
+                    _ => {
+                        let owner = self.visit_member(object);
+                        (owner, owner.and_then(|owner| self.table.symbol(owner).body))
+                    }
+                };
+                let Some(symbol) = self
+                    .table
+                    .lookup_local(body?, member, expression.span.start)
+                else {
+                    self.check_enum_member(owner, member, expression.span);
+                    return None;
+                };
+                self.reference(member, expression.span, Some(symbol));
+                self.check_access(symbol, expression.span);
+                Some(symbol)
+            }
+            ExpressionKind::Identifier(name) => match name.as_static() {
+                Some(text) => self.resolve(text, name.span),
+                None => {
+                    self.visit_name(name);
+                    None
+                }
+            },
+            _ => {
+                self.visit_expression(expression);
+                None
+            }
+        }
+    }
+
+    /// Правило 8 для перечислений: членов у них не добавить, поэтому
+    /// неизвестный член — ошибка
+    fn check_enum_member(&mut self, owner: Option<SymbolId>, member: &str, span: Span) {
+        let Some(owner) = owner.map(|id| self.table.symbol(id)) else {
+            return;
+        };
+        if owner.kind != SymbolKind::Enum {
+            return;
+        }
+        self.errors.push(SemanticError::new(
+            SemanticErrorType::UnknownEnumMember {
+                name: owner.name.clone(),
+                member: member.to_string(),
+            },
+            span,
+            format!("'{}' is not a member of enum '{}'", member, owner.name),
+        ));
+    }
+
+    /// Правило 9: `internal` член доступен из его области и вложенных в нее
+    fn check_access(&mut self, id: SymbolId, span: Span) {
+        let symbol = self.table.symbol(id);
+        if Visibility::of(symbol) != Visibility::Internal {
+            return;
+        }
+        let mut scope = Some(self.scope);
+        while let Some(current) = scope {
+            if current == symbol.scope {
+                return;
+            }
+            scope = self.table.scope(current).parent;
+        }
+        self.errors.push(SemanticError::new(
+            SemanticErrorType::InaccessibleMember {
+                name: symbol.name.clone(),
+                original: symbol.span,
+            },
+            span,
+            format!(
+                "'{}' is internal and not visible outside its container",
+                symbol.name
+            ),
+        ));
+    }
+
+    /// Область, на которую указывает контекстное слово
+    fn context_scope(&self, keyword: ContextKeyword) -> Option<ScopeId> {
+        // Ближайший объект: тело генератора объектом не является
+        let object = |mut scope: ScopeId| {
+            while self.table.scope(scope).kind == ScopeKind::Generator {
+                scope = self.table.scope(scope).parent?;
+            }
+            Some(scope)
+        };
+        match keyword {
+            ContextKeyword::Here => Some(self.scope),
+            ContextKeyword::This => object(self.scope),
+            ContextKeyword::Parent | ContextKeyword::Super => {
+                object(self.table.scope(object(self.scope)?).parent?)
+            }
+            ContextKeyword::Root | ContextKeyword::Origin => Some(self.table.root()),
+        }
+    }
+
+    /// Вставки в имени (правило 5)
+    fn visit_name(&mut self, name: &Name) {
+        self.visit_segments(&name.segments);
+    }
+
+    fn visit_segments(&mut self, segments: &[Segment]) {
+        for segment in segments {
+            if let Segment::Interpolation(expression) = segment {
+                self.visit_expression(expression);
+            }
+        }
+    }
+
+    fn resolve(&mut self, name: &str, span: Span) -> Option<SymbolId> {
+        let symbol = self.lookup(name, span);
+        if symbol.is_none() && constant(name).is_some() {
+            // Физическая постоянная (c, g0, k_B), если имя не занято документом
+            return None;
+        }
+        if symbol.is_none() {
+            self.errors.push(SemanticError::new(
+                SemanticErrorType::UndefinedName(name.to_string()),
+                span,
+                format!("Undefined name '{}'", name),
+            ));
+        }
+        self.reference(name, span, symbol);
+        symbol
+    }
+
+    fn lookup(&self, name: &str, span: Span) -> Option<SymbolId> {
+        self.table
+            .lookup_except(self.scope, name, span.start, self.defining)
+    }
+
+    fn reference(&mut self, name: &str, span: Span, symbol: Option<SymbolId>) {
+        self.table.add_reference(Reference {
+            name: name.to_string(),
+            span,
+            scope: self.scope,
+            symbol,
+            owner: self.defining,
+        });
+    }
+
+    fn enter(&mut self, kind: ScopeKind, span: Span) -> ScopeId {
+        self.scope = self.table.add_scope(kind, self.scope, span);
+        self.scope
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn analyze(source: &str) -> Analysis {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        Analyzer::analyze(&program)
+    }
+
+    fn undefined(analysis: &Analysis) -> Vec<&str> {
+        analysis
+            .errors
+            .iter()
+            .filter_map(|e| match &e.error_type {
+                SemanticErrorType::UndefinedName(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Объявление, с которым связано использование в строке `line`
+    fn binding_of<'a>(analysis: &'a Analysis, name: &str, line: usize) -> Option<&'a Symbol> {
+        let table = &analysis.table;
+        table
+            .references()
+            .iter()
+            .find(|r| r.name == name && r.span.start.line == line)
+            .and_then(|r| r.symbol)
+            .map(|id| table.symbol(id))
+    }
+
+    #[test]
+    fn binds_uses_to_declarations() {
+        let analysis = analyze(
+            r#"area = width * height
+const width = 2m
+let height = 3m
+container App
+    styles {
+        padding: gap
+        gap = 4px
+    }
+    gap: 8px
+global const scale 2
+total = scale * missing
+"#,
+        );
+        assert_eq!(undefined(&analysis), ["missing"]);
+
+        // Ссылки вперед в декларативных областях
+        let width = binding_of(&analysis, "width", 1).unwrap();
+        assert_eq!(
+            (width.kind, width.span.start.line),
+            (SymbolKind::Constant, 2)
+        );
+        // Ближайшая область скрывает внешнюю
+        assert_eq!(binding_of(&analysis, "gap", 6).unwrap().span.start.line, 7);
+
+        let scale = binding_of(&analysis, "scale", 11).unwrap();
+        assert_eq!(scale.scope, analysis.table.root());
+
+        let app = analysis
+            .table
+            .symbols()
+            .iter()
+            .find(|s| s.name == "App")
+            .unwrap();
+        let body = analysis.table.scope(app.body.unwrap());
+        assert_eq!(body.kind, ScopeKind::Container);
+    }
+
+    #[test]
+    fn generator_locals_are_sequential() {
+        let analysis = analyze(
+            r#"spread ['aries', 'taurus'] as let name {
+	before = code-point + 1
+	local code-point = format("{:04X}", 0x2648 + index)
+	yield sign-${name}-text {
+		code-point: `U+${code-point}`
+		pos: index
+	}
+}
+generate (0x4E3 .. 0x4E5) as local let code-point {
+	yield unicode-${code-point} {
+		char code-point
+	}
+}
+after = name + 1
+"#,
+        );
+        // Локальное имя генератора не видно выше объявления и снаружи
+        assert_eq!(undefined(&analysis), ["code-point", "name"]);
+
+        let name = binding_of(&analysis, "name", 4).unwrap();
+        assert_eq!(name.kind, SymbolKind::Variable);
+        let local = binding_of(&analysis, "code-point", 5).unwrap();
+        assert_eq!(local.span.start.line, 3);
+        assert_eq!(
+            binding_of(&analysis, "index", 6).unwrap().kind,
+            SymbolKind::Implicit
+        );
+
+        let binding = binding_of(&analysis, "code-point", 10).unwrap();
+        assert_eq!(binding.modifiers, Modifiers::LOCAL);
+        assert_eq!(
+            analysis.table.scope(binding.scope).kind,
+            ScopeKind::Generator
+        );
+    }
+
+    #[test]
+    fn bare_words_and_builtins() {
+        let analysis = analyze(
+            "const first-word snow\nconst winter-${first-word} #dbebed\nenergy = mass * c^2\nmass = 1kg\nlabel = name-${unknown}\n",
+        );
+        assert_eq!(undefined(&analysis), ["unknown"]);
+        assert!(binding_of(&analysis, "first-word", 2).is_some());
+    }
+
+    fn error_types(analysis: &Analysis) -> Vec<&SemanticErrorType> {
+        analysis.errors.iter().map(|e| &e.error_type).collect()
+    }
+
+    #[test]
+    fn rejects_redeclaration_and_mutation() {
+        let analysis = analyze(
+            r#"const temperature<Temperature> = 25°C
+const temperature<Temperature> = 25K
+let count = 1
+count = 2
+count += 1
+const limit = 10
+limit = 20
+limit++
+final let ratio = 0.5
+ratio *= 2
+const arr [1, 2]
+arr + 3
+const palette { primary 1 }
+palette > primary 2
+palette > shade > light + 1
+box {
+    count: 3
+    const limit = 5
+}
+"#,
+        );
+        let original = |line: usize| {
+            analysis
+                .table
+                .symbols()
+                .iter()
+                .find(|s| s.span.start.line == line)
+                .unwrap()
+                .span
+        };
+        let immutable = |name: &str, line| SemanticErrorType::ImmutableAssignment {
+            name: name.to_string(),
+            original: original(line),
+        };
+        assert_eq!(
+            error_types(&analysis),
+            [
+                &SemanticErrorType::DuplicateDeclaration {
+                    name: "temperature".to_string(),
+                    original: original(1),
+                },
+                &immutable("limit", 6),
+                &immutable("limit", 6),
+                &immutable("ratio", 9),
+                &immutable("arr", 11),
+                &immutable("palette", 13),
+                &immutable("palette", 13),
+            ]
+        );
+        assert_eq!(
+            analysis.errors[0].message,
+            "'temperature' is already declared at 1:7"
+        );
+        assert_eq!(analysis.errors[1].span.start.line, 7);
+    }
+
+    #[test]
+    fn shadowing_is_configurable() {
+        let (program, _) = parse(
+            "let gap = 4px
+box {
+    let gap = 8px
+    inner: gap
+}
+",
+        );
+        assert!(Analyzer::analyze(&program).errors.is_empty());
+
+        let strict = AnalyzerOptions {
+            allow_shadowing: false,
+        };
+        let analysis = Analyzer::analyze_with(&program, strict);
+        assert_eq!(analysis.errors.len(), 1);
+        assert!(matches!(
+            &analysis.errors[0].error_type,
+            SemanticErrorType::ShadowedBinding { name, original }
+                if name == "gap" && original.start.line == 1
+        ));
+    }
+
+    #[test]
+    fn checks_enum_members() {
+        let analysis = analyze(
+            r#"enum Align { start, center, end }
+enum Gap { small = 4px, large = small * 4 }
+a: Align.center
+b: Align::end
+c: Align.middle
+d: Align::top
+"#,
+        );
+        let messages: Vec<&str> = analysis.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'middle' is not a member of enum 'Align'",
+                "'top' is not a member of enum 'Align'",
+            ]
+        );
+        assert_eq!(analysis.errors[0].span.start.line, 5);
+
+        let binding = binding_of(&analysis, "end", 4).unwrap();
+        assert_eq!(binding.kind, SymbolKind::EnumMember);
+        let binding = binding_of(&analysis, "small", 2).unwrap();
+        assert_eq!(binding.kind, SymbolKind::EnumMember);
+    }
+}
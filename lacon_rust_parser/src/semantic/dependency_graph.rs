@@ -0,0 +1,207 @@
+// Граф зависимостей между значениями: `area = width * height` зависит
+// от `width` и `height`. Ребро идет от объявления к символу, на который
+// ссылается его значение (в том числе через `this.x`, `parent.x`, `app.gap`).
+//
+// Объекты (словари и контейнеры) ленивы: ссылка на сам объект не требует
+// вычислить его члены, поэтому ребра к символам с телом не строятся,
+// а `box { inner: box.gap }` циклом не считается. Цикл — это значение,
+// которое через цепочку ссылок зависит от самого себя: `a = b + 1`, `b = a`.
+//
+// Порядок вычисления — обход в глубину в порядке объявлений: зависимости
+// раньше зависимых, при прочих равных — порядок строк. Поэтому ссылки
+// вперед (`a = b + 1` до `b = 2`) работают без перестановок в документе.
+
+use super::error::{SemanticError, SemanticErrorType};
+use super::symbol_table::{SymbolId, SymbolTable};
+
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// Для каждого символа — символы, от которых зависит его значение
+    edges: Vec<Vec<SymbolId>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    New,
+    Visiting,
+    Done,
+}
+
+impl DependencyGraph {
+    pub fn build(table: &SymbolTable) -> Self {
+        let mut edges = vec![Vec::new(); table.symbols().len()];
+        for reference in table.references() {
+            let (Some(owner), Some(target)) = (reference.owner, reference.symbol) else {
+                continue;
+            };
+            if table.symbol(target).body.is_some() {
+                continue;
+            }
+            let list: &mut Vec<SymbolId> = &mut edges[owner.0];
+            if !list.contains(&target) {
+                list.push(target);
+            }
+        }
+        Self { edges }
+    }
+
+    pub fn dependencies(&self, symbol: SymbolId) -> &[SymbolId] {
+        &self.edges[symbol.0]
+    }
+
+    /// Циклы в порядке обнаружения; каждый — путь от первого символа
+    /// обратно к нему: `[a, b, a]`
+    pub fn cycles(&self) -> Vec<Vec<SymbolId>> {
+        let mut cycles = Vec::new();
+        self.walk(|_| {}, |cycle| cycles.push(cycle));
+        cycles
+    }
+
+    /// Порядок вычисления: каждый символ после своих зависимостей.
+    /// Символы в циклах тоже входят в порядок, но их значения не определены.
+    pub fn evaluation_order(&self) -> Vec<SymbolId> {
+        let mut order = Vec::new();
+        self.walk(|id| order.push(id), |_| {});
+        order
+    }
+
+    /// Ошибки для всех циклов, с полным путем в сообщении
+    pub fn cycle_errors(&self, table: &SymbolTable) -> Vec<SemanticError> {
+        self.cycles()
+            .into_iter()
+            .map(|cycle| {
+                let path: Vec<String> = cycle
+                    .iter()
+                    .map(|&id| table.symbol(id).name.clone())
+                    .collect();
+                let message = format!("Reference cycle: {}", path.join(" -> "));
+                SemanticError::new(
+                    SemanticErrorType::ReferenceCycle { path },
+                    table.symbol(cycle[0]).span,
+                    message,
+                )
+            })
+            .collect()
+    }
+
+    /// Обход в глубину: `finish` — символ со всеми зависимостями,
+    /// `cycle` — найденное обратное ребро
+    fn walk(&self, mut finish: impl FnMut(SymbolId), mut cycle: impl FnMut(Vec<SymbolId>)) {
+        let mut marks = vec![Mark::New; self.edges.len()];
+        let mut stack: Vec<SymbolId> = Vec::new();
+
+        for root in 0..self.edges.len() {
+            if marks[root] != Mark::New {
+                continue;
+            }
+            // Итеративно: (символ, индекс следующей зависимости)
+            let mut frames = vec![(SymbolId(root), 0)];
+            marks[root] = Mark::Visiting;
+            stack.push(SymbolId(root));
+
+            while let Some((id, next)) = frames.last_mut() {
+                let id = *id;
+                let Some(&target) = self.edges[id.0].get(*next) else {
+                    frames.pop();
+                    stack.pop();
+                    marks[id.0] = Mark::Done;
+                    finish(id);
+                    continue;
+                };
+                *next += 1;
+
+                match marks[target.0] {
+                    Mark::New => {
+                        marks[target.0] = Mark::Visiting;
+                        stack.push(target);
+                        frames.push((target, 0));
+                    }
+                    Mark::Visiting => {
+                        let start = stack.iter().position(|&s| s == target).unwrap();
+                        let mut path = stack[start..].to_vec();
+                        path.push(target);
+                        cycle(path);
+                    }
+                    Mark::Done => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use crate::semantic::analyzer::{Analysis, Analyzer};
+    use crate::semantic::error::SemanticErrorType;
+    use crate::semantic::symbol_table::SymbolId;
+
+    fn analyze(source: &str) -> Analysis {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        Analyzer::analyze(&program)
+    }
+
+    fn names(analysis: &Analysis, ids: &[SymbolId]) -> Vec<String> {
+        ids.iter()
+            .map(|&id| analysis.table.symbol(id).name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn orders_forward_references() {
+        let analysis = analyze(
+            r#"area = width * height
+const width = 2m
+let height = width + 1m
+box {
+    w: 10px
+    h: this.w * 2
+    inner {
+        gap: parent.h / 4
+    }
+}
+scale = box.inner.gap
+"#,
+        );
+        assert!(analysis.errors.is_empty(), "{:?}", analysis.errors);
+
+        let order = names(&analysis, &analysis.graph.evaluation_order());
+        let position = |name: &str| order.iter().position(|n| n == name).unwrap();
+        assert!(position("width") < position("height"));
+        assert!(position("height") < position("area"));
+        assert!(position("w") < position("h"));
+        assert!(position("h") < position("gap"));
+        assert!(position("gap") < position("scale"));
+    }
+
+    #[test]
+    fn reports_cycles_with_the_path() {
+        let analysis = analyze(
+            r#"a = b + 1
+b = c * 2
+c = a
+box {
+    width: this.width + 1
+    inner: box.width
+}
+"#,
+        );
+        let cycles: Vec<&SemanticErrorType> = analysis
+            .errors
+            .iter()
+            .map(|e| &e.error_type)
+            .filter(|t| matches!(t, SemanticErrorType::ReferenceCycle { .. }))
+            .collect();
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(
+            analysis.errors[0].message,
+            "Reference cycle: a -> b -> c -> a"
+        );
+        assert_eq!(analysis.errors[0].span.start.line, 1);
+        assert_eq!(
+            analysis.errors[1].message,
+            "Reference cycle: width -> width"
+        );
+    }
+}
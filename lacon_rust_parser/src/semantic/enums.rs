@@ -0,0 +1,161 @@
+// Перечисления: `enum Align { start, center, end }`,
+// `enum Gap { small = 4px, large = 16px }`, `enum Arrow { left = U+2190 }`.
+//
+// В JSON член попадает именем или явным значением — по `EnumFormat`;
+// член без явного значения всегда выводится именем. Ссылки на члены
+// (`Align.center`, `Align::center`) проверяет analyzer.rs, поля и аннотации
+// с типом-перечислением — validator.rs.
+
+use crate::ast::expressions::{ExpressionKind, Segment, UnaryOperator};
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, EnumDecl, EnumValue, StatementKind};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Как члены перечислений выводятся в JSON
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumFormat {
+    /// `"center"`
+    #[default]
+    Name,
+    /// `"4px"`, `8`, `"←"`
+    Value,
+}
+
+/// JSON-значение члена: `"small"`, `"4px"`, `16`, `true`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum EnumJson {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+}
+
+/// Перечисления документа и контейнеров по именам
+#[derive(Debug, Clone, Default)]
+pub struct Enums<'a> {
+    declarations: HashMap<&'a str, &'a EnumDecl>,
+}
+
+impl<'a> Enums<'a> {
+    pub fn collect(program: &'a Program) -> Self {
+        let mut enums = Self::default();
+        enums.collect_block(&program.body);
+        enums
+    }
+
+    fn collect_block(&mut self, block: &'a Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                // Повторное объявление — ошибка анализатора; здесь остается первое
+                StatementKind::Enum(decl) => {
+                    if let Some(name) = decl.name.as_static() {
+                        self.declarations.entry(name).or_insert(decl);
+                    }
+                }
+                StatementKind::Container(container) => self.collect_block(&container.body),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&'a EnumDecl> {
+        self.declarations.get(name).copied()
+    }
+
+    /// JSON-значение `name.member`; `None` — нет такого члена или значение
+    /// известно только при вычислении
+    pub fn to_json(&self, name: &str, member: &str, format: EnumFormat) -> Option<EnumJson> {
+        let member = self.get(name)?.member(member)?;
+        let text = member.name.as_static()?;
+        let value = match (format, &member.value) {
+            (EnumFormat::Name, _) | (_, None) => return Some(EnumJson::String(text.to_string())),
+            (EnumFormat::Value, Some(value)) => value,
+        };
+        let expression = match value {
+            EnumValue::CodePoint(character) => {
+                return Some(EnumJson::String(character.to_string()));
+            }
+            EnumValue::Expression(expression) => expression,
+        };
+        let json = match &expression.kind {
+            ExpressionKind::Number(number) => EnumJson::Number(*number),
+            ExpressionKind::Unary {
+                operator: UnaryOperator::Negate,
+                operand,
+            } => match operand.kind {
+                ExpressionKind::Number(number) => EnumJson::Number(-number),
+                _ => return None,
+            },
+            ExpressionKind::Unit { value, suffix } => {
+                EnumJson::String(format!("{}{}", value, suffix))
+            }
+            ExpressionKind::String(segments) => EnumJson::String(
+                segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => Some(text.as_str()),
+                        Segment::Interpolation(_) => None,
+                    })
+                    .collect::<Option<String>>()?,
+            ),
+            ExpressionKind::Color(color) => EnumJson::String(color.clone()),
+            ExpressionKind::Boolean(value) => EnumJson::Boolean(*value),
+            _ => return None,
+        };
+        Some(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn members_serialize_by_name_or_value() {
+        let (program, errors) = parse(
+            "enum Gap { small = 4px, large = 16px, none }\ncontainer Icons\n    enum Arrow { left = U+2190, up: \"up\" }\n    enum Latin { micro = U+00B5, copy = U+00A9, bang = U+00A1 }\n    enum Level { low = -1, on = true, computed = low * 2 }\n",
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+        let enums = Enums::collect(&program);
+        let json = |name, member, format| enums.to_json(name, member, format);
+
+        assert_eq!(
+            json("Gap", "small", EnumFormat::Name),
+            Some(EnumJson::String("small".to_string()))
+        );
+        assert_eq!(
+            json("Gap", "small", EnumFormat::Value),
+            Some(EnumJson::String("4px".to_string()))
+        );
+        assert_eq!(
+            json("Gap", "none", EnumFormat::Value),
+            Some(EnumJson::String("none".to_string()))
+        );
+        assert_eq!(
+            json("Arrow", "left", EnumFormat::Value),
+            Some(EnumJson::String("←".to_string()))
+        );
+        assert_eq!(
+            json("Arrow", "up", EnumFormat::Value),
+            Some(EnumJson::String("up".to_string()))
+        );
+        for (member, expected) in [("micro", "µ"), ("copy", "©"), ("bang", "¡")] {
+            assert_eq!(
+                json("Latin", member, EnumFormat::Value),
+                Some(EnumJson::String(expected.to_string()))
+            );
+        }
+        assert_eq!(
+            json("Level", "low", EnumFormat::Value),
+            Some(EnumJson::Number(-1.0))
+        );
+        assert_eq!(
+            json("Level", "on", EnumFormat::Value),
+            Some(EnumJson::Boolean(true))
+        );
+        assert_eq!(json("Level", "computed", EnumFormat::Value), None);
+        assert_eq!(json("Gap", "huge", EnumFormat::Name), None);
+    }
+}
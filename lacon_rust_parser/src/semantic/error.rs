@@ -0,0 +1,76 @@
+use crate::shared::unit::dimensions::Dimension;
+use crate::utils::span::Span;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticError {
+    pub message: String,
+    pub span: Span,
+    pub error_type: SemanticErrorType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SemanticErrorType {
+    /// Имя не объявлено ни в одной из объемлющих областей
+    UndefinedName(String),
+    /// Размерность значения не совпадает с аннотацией; `found` — как в сообщении
+    DimensionMismatch { expected: Dimension, found: String },
+    /// Повторное объявление в той же области; `original` — первое объявление
+    DuplicateDeclaration { name: String, original: Span },
+    /// Присваивание или изменение `const`, `final` или `static`
+    ImmutableAssignment { name: String, original: Span },
+    /// Объявление скрывает привязку внешней области (если это запрещено)
+    ShadowedBinding { name: String, original: Span },
+    /// Значение зависит от самого себя; путь начинается и кончается одним именем.
+    /// Так же сообщается о схемах, которые наследуют сами себя.
+    ReferenceCycle { path: Vec<String> },
+    /// Имя в аннотации или после `extends` не является ни типом, ни схемой
+    UnknownType(String),
+    /// `Dictionary<Time>`, `Length<Px>`: не то число аргументов типа
+    TypeArguments {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Тип значения не подходит под аннотацию или тип поля схемы
+    TypeMismatch { expected: String, found: String },
+    /// В словаре нет обязательного поля схемы
+    MissingField { schema: String, field: String },
+    /// Ключ словаря не объявлен в схеме
+    UnknownField { schema: String, field: String },
+    /// Значение не входит в список после `in`
+    DisallowedValue { value: String, allowed: Vec<String> },
+    /// `Align.middle`: в перечислении нет такого члена
+    UnknownEnumMember { name: String, member: String },
+    /// `internal` член используется снаружи своего контейнера
+    InaccessibleMember { name: String, original: Span },
+    /// Импортируемое имя закрыто (`private`, `internal`) или не объявлено
+    NotExported { name: String, source: String },
+    /// Файл из `import` не найден
+    UnresolvedImport(String),
+    /// После `extends` не схема (перечисление, размерность),
+    /// после `implements` не интерфейс
+    InvalidBase { schema: String, base: String },
+    /// struct не объявляет поле интерфейса из `implements`
+    UnimplementedField {
+        schema: String,
+        interface: String,
+        field: String,
+    },
+}
+
+impl SemanticError {
+    pub fn new(error_type: SemanticErrorType, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            error_type,
+        }
+    }
+}
+
+impl fmt::Display for SemanticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Semantic Error at {}: {}", self.span, self.message)
+    }
+}
@@ -0,0 +1,21 @@
+pub mod analyzer;
+pub mod dependency_graph;
+pub mod enums;
+pub mod error;
+pub mod scope;
+pub mod symbol_table;
+pub mod type_checker;
+pub mod type_inference;
+pub mod types;
+pub mod validator;
+pub mod visibility;
+
+pub use analyzer::{Analysis, Analyzer, AnalyzerOptions};
+pub use dependency_graph::DependencyGraph;
+pub use enums::{EnumFormat, EnumJson, Enums};
+pub use error::{SemanticError, SemanticErrorType};
+pub use type_checker::TypeChecker;
+pub use type_inference::{Inference, InferredType, TypeInference, ValueDimension};
+pub use types::{DeclaredType, TypeResolver};
+pub use validator::Validator;
+pub use visibility::{Exports, Visibility, check_imports};
@@ -0,0 +1,62 @@
+use super::symbol_table::SymbolId;
+use crate::utils::span::Span;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// Документ целиком
+    File,
+    /// `container App`
+    Container,
+    /// `{ ... }` или блок отступом у ключа
+    Dictionary,
+    /// Тело `spread`/`generate`
+    Generator,
+    /// Члены `enum`
+    Enum,
+}
+
+impl ScopeKind {
+    /// Декларативная область: имена видны во всей области, в том числе выше
+    /// объявления. В теле генератора записи выполняются по порядку,
+    /// и локальные имена видны только после объявления.
+    pub fn is_declarative(self) -> bool {
+        !matches!(self, ScopeKind::Generator)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub parent: Option<ScopeId>,
+    pub span: Span,
+    /// Символы в порядке объявления; повторное объявление — отдельный символ
+    pub symbols: Vec<SymbolId>,
+    /// Те же символы по именам, тоже в порядке объявления
+    names: HashMap<String, Vec<SymbolId>>,
+}
+
+impl Scope {
+    pub fn new(kind: ScopeKind, parent: Option<ScopeId>, span: Span) -> Self {
+        Self {
+            kind,
+            parent,
+            span,
+            symbols: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, id: SymbolId, name: &str) {
+        self.symbols.push(id);
+        self.names.entry(name.to_string()).or_default().push(id);
+    }
+
+    /// Все объявления имени в этой области
+    pub fn declared(&self, name: &str) -> &[SymbolId] {
+        self.names.get(name).map_or(&[], Vec::as_slice)
+    }
+}
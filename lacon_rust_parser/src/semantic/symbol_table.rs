@@ -0,0 +1,202 @@
+use super::scope::{Scope, ScopeId, ScopeKind};
+use crate::ast::statements::Modifiers;
+use crate::lexer::position::Position;
+use crate::utils::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SymbolId(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// `let`/`var`/`variable`, привязка генератора `as let x`
+    Variable,
+    /// `const`/`constant`
+    Constant,
+    /// Ключ словаря
+    Key,
+    Container,
+    /// Имя, которое язык объявляет сам: `index` в теле генератора
+    Implicit,
+    /// `import name from "path"`
+    Import,
+    /// `enum Align { ... }`; члены — в его теле
+    Enum,
+    EnumMember,
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub modifiers: Modifiers,
+    /// Участок имени в объявлении
+    pub span: Span,
+    pub scope: ScopeId,
+    /// В последовательной области — позиция, с которой имя видно
+    pub visible_from: Position,
+    /// Область, которую открывает символ: тело контейнера, словарь-значение
+    pub body: Option<ScopeId>,
+}
+
+impl Symbol {
+    /// `const`, импорт, перечисление, а также `final` и `static` с любым видом объявления
+    pub fn is_immutable(&self) -> bool {
+        matches!(
+            self.kind,
+            SymbolKind::Constant | SymbolKind::Import | SymbolKind::Enum | SymbolKind::EnumMember
+        ) || self
+            .modifiers
+            .intersects(Modifiers::FINAL | Modifiers::STATIC)
+    }
+
+    /// Привязка `let`/`const`, в отличие от ключа или контейнера
+    pub fn is_binding(&self) -> bool {
+        matches!(self.kind, SymbolKind::Variable | SymbolKind::Constant)
+    }
+}
+
+/// Использование имени. `symbol` — `None`, если имя не найдено.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: String,
+    pub span: Span,
+    pub scope: ScopeId,
+    pub symbol: Option<SymbolId>,
+    /// Объявление, в значении которого стоит использование
+    pub owner: Option<SymbolId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    scopes: Vec<Scope>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+}
+
+impl SymbolTable {
+    /// Таблица с областью документа
+    pub fn new(span: Span) -> Self {
+        Self {
+            scopes: vec![Scope::new(ScopeKind::File, None, span)],
+            symbols: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    pub fn root(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    pub fn add_scope(&mut self, kind: ScopeKind, parent: ScopeId, span: Span) -> ScopeId {
+        self.scopes.push(Scope::new(kind, Some(parent), span));
+        ScopeId(self.scopes.len() - 1)
+    }
+
+    pub fn declare(&mut self, symbol: Symbol) -> SymbolId {
+        let id = SymbolId(self.symbols.len());
+        self.scopes[symbol.scope.0].push(id, &symbol.name);
+        self.symbols.push(symbol);
+        id
+    }
+
+    pub fn set_body(&mut self, symbol: SymbolId, body: ScopeId) {
+        self.symbols[symbol.0].body = Some(body);
+    }
+
+    pub fn add_reference(&mut self, reference: Reference) {
+        self.references.push(reference);
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id.0]
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Поиск только в указанной области (см. правила видимости в analyzer.rs)
+    pub fn lookup_local(&self, scope: ScopeId, name: &str, at: Position) -> Option<SymbolId> {
+        self.lookup_local_except(scope, name, at, None)
+    }
+
+    fn lookup_local_except(
+        &self,
+        scope: ScopeId,
+        name: &str,
+        at: Position,
+        except: Option<SymbolId>,
+    ) -> Option<SymbolId> {
+        let scope = self.scope(scope);
+        let mut candidates = scope
+            .declared(name)
+            .iter()
+            .copied()
+            .filter(|&id| Some(id) != except);
+
+        if !scope.kind.is_declarative() {
+            return candidates.rfind(|&id| self.symbol(id).visible_from <= at);
+        }
+        // При повторном объявлении — последнее объявление выше использования,
+        // а для использований выше всех объявлений — первое
+        candidates
+            .clone()
+            .rfind(|&id| self.symbol(id).span.start <= at)
+            .or_else(|| candidates.next())
+    }
+
+    /// Поиск от области наружу; ближайшее объявление скрывает внешние
+    pub fn lookup(&self, scope: ScopeId, name: &str, at: Position) -> Option<SymbolId> {
+        self.lookup_except(scope, name, at, None)
+    }
+
+    /// Поиск без учета символа `except` — записи, значение которой разбирается
+    pub fn lookup_except(
+        &self,
+        scope: ScopeId,
+        name: &str,
+        at: Position,
+        except: Option<SymbolId>,
+    ) -> Option<SymbolId> {
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            if let Some(symbol) = self.lookup_local_except(id, name, at, except) {
+                return Some(symbol);
+            }
+            current = self.scope(id).parent;
+        }
+        None
+    }
+
+    /// Все использования символа
+    pub fn references_to(&self, symbol: SymbolId) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |r| r.symbol == Some(symbol))
+    }
+
+    /// Символ под позицией: объявление или использование (для hover)
+    pub fn symbol_at(&self, position: Position) -> Option<SymbolId> {
+        self.references
+            .iter()
+            .find(|r| r.span.contains(position))
+            .and_then(|r| r.symbol)
+            .or_else(|| {
+                (0..self.symbols.len())
+                    .map(SymbolId)
+                    .find(|&id| self.symbol(id).span.contains(position))
+            })
+    }
+}
@@ -0,0 +1,411 @@
+// Проверка размерностей в аннотациях: `let speed<Speed> = 278ft/s`,
+// `gap<Length>: 4px`.
+// Тип значения берется из вывода типов (type_inference.rs), поэтому
+// проверяются и выражения с именами: `let v<Speed> = distance / time`.
+// Составные аннотации (types.rs) проверяются поэлементно:
+// `let sizes<Array<Length>> = [4px, 2s]` — ошибка у `sizes[1]`,
+// `fades<Dictionary<String, Time>> { in: 4px }` — у `fades.in`; объединение
+// (`Length | Percent`) принимает значение любого из своих типов.
+// Примитивы (`let s<String> = 5`) проверяются так же. Значения неизвестного
+// типа здесь пропускаются; схемы и перечисления проверяет validator.rs.
+//
+// Аргументы встроенных функций проверяются по их сигнатурам:
+// `format(4px)` — ошибка, шаблон должен быть строкой.
+
+use super::error::{SemanticError, SemanticErrorType};
+use super::symbol_table::SymbolTable;
+use super::type_inference::{InferredType, TypeInference};
+use super::types::{DeclaredType, TypeResolver};
+use crate::ast::expressions::{Expression, ExpressionKind, Name, Segment};
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, StatementKind};
+use crate::ast::types::TypeAnnotation;
+use crate::shared::unit::registry::UnitRegistry;
+use crate::utils::span::Span;
+
+pub struct TypeChecker<'a> {
+    inference: TypeInference<'a>,
+    types: TypeResolver<'a>,
+    errors: Vec<SemanticError>,
+}
+
+/// Типы параметров встроенных функций; лишние аргументы не проверяются
+fn signature(name: &str) -> Option<Vec<DeclaredType>> {
+    match name {
+        "format" => Some(vec![DeclaredType::String]),
+        "select" => Some(vec![DeclaredType::Array(Box::new(DeclaredType::Auto))]),
+        _ => None,
+    }
+}
+
+impl<'a> TypeChecker<'a> {
+    pub fn check(
+        program: &'a Program,
+        table: &'a SymbolTable,
+        registry: &'a UnitRegistry,
+    ) -> Vec<SemanticError> {
+        let types = TypeResolver::collect(program);
+        let mut checker = TypeChecker {
+            inference: TypeInference::new(program, table, registry),
+            errors: types.check_aliases(),
+            types,
+        };
+        checker.visit_block(&program.body);
+        checker.errors
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Variable(decl) => {
+                    self.check_annotation(&decl.name, decl.annotation.as_ref(), &decl.value);
+                    self.visit_optional(&decl.value);
+                }
+                StatementKind::Property(property) | StatementKind::Yield(property) => {
+                    let annotation = property.annotation.as_ref();
+                    self.check_annotation(&property.name, annotation, &property.value);
+                    self.visit_optional(&property.value)
+                }
+                StatementKind::Assignment(assignment) => self.visit_optional(&assignment.value),
+                StatementKind::Container(container) => self.visit_block(&container.body),
+                StatementKind::Generator(generator) => {
+                    self.visit_expression(&generator.source);
+                    self.visit_block(&generator.body);
+                }
+                StatementKind::Schema(_)
+                | StatementKind::Enum(_)
+                | StatementKind::TypeAlias(_)
+                | StatementKind::Import(_) => {}
+                StatementKind::Expression(expression) => self.visit_expression(expression),
+            }
+        }
+    }
+
+    fn visit_optional(&mut self, value: &Option<Expression>) {
+        if let Some(value) = value {
+            self.visit_expression(value);
+        }
+    }
+
+    /// Поиск объявлений во вложенных словарях и вставках
+    fn visit_expression(&mut self, expression: &Expression) {
+        match &expression.kind {
+            ExpressionKind::Dictionary(block) => self.visit_block(block),
+            ExpressionKind::String(segments) => {
+                for segment in segments {
+                    if let Segment::Interpolation(inner) = segment {
+                        self.visit_expression(inner);
+                    }
+                }
+            }
+            ExpressionKind::Unary { operand, .. } => self.visit_expression(operand),
+            ExpressionKind::Binary { left, right, .. } => {
+                self.visit_expression(left);
+                self.visit_expression(right);
+            }
+            ExpressionKind::Call { callee, arguments } => {
+                self.visit_expression(callee);
+                arguments.iter().for_each(|a| self.visit_expression(a));
+                self.check_call(callee, arguments);
+            }
+            ExpressionKind::Member { object, .. } => self.visit_expression(object),
+            ExpressionKind::Index { object, index } => {
+                self.visit_expression(object);
+                self.visit_expression(index);
+            }
+            ExpressionKind::Array(items) | ExpressionKind::Tuple(items) => {
+                items.iter().for_each(|i| self.visit_expression(i))
+            }
+            _ => {}
+        }
+    }
+
+    fn check_annotation(
+        &mut self,
+        name: &Name,
+        annotation: Option<&TypeAnnotation>,
+        value: &Option<Expression>,
+    ) {
+        let Some(annotation) = annotation else {
+            return;
+        };
+        let Some(expected) = self.types.resolve(annotation, &mut self.errors) else {
+            return;
+        };
+        let Some(value) = value else {
+            return;
+        };
+        match expected {
+            DeclaredType::Schema(_) | DeclaredType::Enum(_) => {}
+            _ => {
+                let name = name.as_static().unwrap_or("value").to_string();
+                self.check_value(name, &annotation.to_string(), &expected, value);
+            }
+        }
+    }
+
+    /// Значение по типу; `shown` — тип в сообщении, как в аннотации
+    fn check_value(
+        &mut self,
+        name: String,
+        shown: &str,
+        expected: &DeclaredType,
+        value: &Expression,
+    ) {
+        match (expected, &value.kind) {
+            (DeclaredType::Array(element), ExpressionKind::Array(items)) => {
+                let shown = element.to_string();
+                for (index, item) in items.iter().enumerate() {
+                    if !matches!(item.kind, ExpressionKind::Hole) {
+                        self.check_value(format!("{}[{}]", name, index), &shown, element, item);
+                    }
+                }
+            }
+            (DeclaredType::Dictionary(_, element), ExpressionKind::Dictionary(block)) => {
+                let shown = element.to_string();
+                for statement in &block.statements {
+                    let StatementKind::Property(property) = &statement.kind else {
+                        continue;
+                    };
+                    if let (Some(key), Some(item)) = (property.name.as_static(), &property.value) {
+                        self.check_value(format!("{}.{}", name, key), &shown, element, item);
+                    }
+                }
+            }
+            (DeclaredType::Quantity { dimension, .. }, _) => {
+                let InferredType::Quantity(found) = self.inference.expression_type(value) else {
+                    return;
+                };
+                if !found.satisfies(*dimension) {
+                    self.errors.push(SemanticError::new(
+                        SemanticErrorType::DimensionMismatch {
+                            expected: *dimension,
+                            found: found.to_string(),
+                        },
+                        value.span,
+                        format!(
+                            "'{}' is declared as {} but its value is {}",
+                            name, shown, found
+                        ),
+                    ));
+                }
+            }
+            _ => {
+                let found = self.inference.value_type(value);
+                if found.is_known() && expected.accepts(&found) == Some(false) {
+                    let found = shown_type(&found);
+                    let message = format!(
+                        "'{}' is declared as {} but its value is {}",
+                        name, shown, found
+                    );
+                    self.mismatch(shown, found, value.span, message);
+                }
+            }
+        }
+    }
+
+    /// Аргументы встроенной функции; одноименное объявление ее скрывает
+    fn check_call(&mut self, callee: &Expression, arguments: &[Expression]) {
+        let Some(function) = callee.as_identifier() else {
+            return;
+        };
+        if self.inference.reference_of(callee).is_some() {
+            return;
+        }
+        let Some(parameters) = signature(function) else {
+            return;
+        };
+        for (index, (parameter, argument)) in parameters.iter().zip(arguments).enumerate() {
+            let found = self.inference.value_type(argument);
+            if found.is_known() && parameter.accepts(&found) == Some(false) {
+                let expected = parameter.to_string();
+                let found = shown_type(&found);
+                let message = format!(
+                    "'{}' expects {} as argument {} but it is {}",
+                    function,
+                    expected,
+                    index + 1,
+                    found
+                );
+                self.mismatch(&expected, found, argument.span, message);
+            }
+        }
+    }
+
+    fn mismatch(&mut self, expected: &str, found: String, span: Span, message: String) {
+        self.errors.push(SemanticError::new(
+            SemanticErrorType::TypeMismatch {
+                expected: expected.to_string(),
+                found,
+            },
+            span,
+            message,
+        ));
+    }
+}
+
+/// У величин — с единицей: `Time (s)`
+fn shown_type(found: &InferredType) -> String {
+    match found {
+        InferredType::Quantity(quantity) => quantity.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::semantic::analyzer::Analyzer;
+    use crate::shared::unit::dimensions::Dimension;
+
+    fn check(source: &str) -> Vec<SemanticError> {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let analysis = Analyzer::analyze(&program);
+        TypeChecker::check(&program, &analysis.table, &UnitRegistry::new())
+    }
+
+    #[test]
+    fn accepts_matching_dimensions() {
+        let errors = check(
+            "let speed<Speed> = 278ft/s\n\
+             const field-area<Area> = 73.14m2\n\
+             const vessel-volume<Volume> = 0.1kL\n\
+             let force<Force> = 2kg * 9.8m/s2\n\
+             let turn<Angle> = 90deg\n\
+             let share<Percent> = 0.5",
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn reports_the_found_unit() {
+        let errors = check("let t<Time> = 25pc\nlet a<Angle> = 50%\nlet d<Length> = 2m * 3s");
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[0].error_type,
+            SemanticErrorType::DimensionMismatch {
+                expected: Dimension::Time,
+                found: "Length (pc)".to_string(),
+            }
+        );
+        assert_eq!(
+            errors[0].message,
+            "'t' is declared as Time but its value is Length (pc)"
+        );
+        assert_eq!(errors[0].span.start.column, 15);
+        assert!(errors[1].message.contains("Percent (%)"));
+        // У произведения без имени символ не повторяется
+        assert_eq!(
+            errors[2].message,
+            "'d' is declared as Length but its value is m·s"
+        );
+    }
+
+    #[test]
+    fn checks_nested_and_inferred_declarations() {
+        let errors = check(
+            "container App\n    let gap<Length> = 2s\n    let other<Length> = base * 2\n    let word<String> = snow\nlet d = 100m\nlet v<Speed> = d / 9.58s\nlet w<Time> = d * 2",
+        );
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(errors[0].span.start.line, 2);
+        assert_eq!(
+            errors[1].message,
+            "'w' is declared as Time but its value is Length (m)"
+        );
+    }
+
+    #[test]
+    fn checks_annotated_keys() {
+        let errors = check("box {\n    gap<Length>: 2s\n    label<String>: 2s\n}");
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'gap' is declared as Length but its value is Time (s)",
+                "'label' is declared as String but its value is Time (s)",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_aliases_and_generic_annotations() {
+        let errors = check(
+            r#"type Dim = Length | Percent
+alias Px = Length
+type Cycle = Array<Cycle>
+let gap<Px> = 2s
+let sizes<Array<Dim>> = [4px, 50%, 2s]
+let pair<Dim> = true
+fades<Dictionary<String, Time>> {
+    in: 200ms
+    out: 4px
+}
+let words<Array<String>> = snow
+let grid<Array<Array<Px>>> = [[1px], [2deg]]
+let odd<Dictionary<Time>> = {}
+"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Type alias cycle: Cycle -> Cycle",
+                "'gap' is declared as Px but its value is Time (s)",
+                "'sizes[2]' is declared as Length | Percent but its value is Time (s)",
+                "'pair' is declared as Dim but its value is Boolean",
+                "'fades.out' is declared as Time but its value is Length (px)",
+                "'words' is declared as Array<String> but its value is String",
+                "'grid[1][0]' is declared as Length but its value is Angle (deg)",
+                "'Dictionary' expects 2 type arguments, found 1",
+            ]
+        );
+        assert_eq!(
+            errors[1].error_type,
+            SemanticErrorType::DimensionMismatch {
+                expected: Dimension::Length,
+                found: "Time (s)".to_string(),
+            }
+        );
+        assert_eq!(errors[2].span.start.column, 36);
+        assert_eq!(errors[4].span.start.line, 9);
+    }
+
+    #[test]
+    fn checks_primitive_annotations() {
+        let errors = check(
+            "let s<String> = 5
+let n<Number> = 5m
+let ok<Number> = 5
+let on<Boolean> = 2 > 1
+let c<Color> = #fff
+let any<Auto> = 5m
+",
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'s' is declared as String but its value is a plain number",
+                "'n' is declared as Number but its value is Length (m)",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_builtin_signatures() {
+        let errors = check(
+            "label = format(\"${1}px\", 1)\nbad = format(4px)\npick = select([1s, 2s])\nwrong = select(2s)\n",
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'format' expects String as argument 1 but it is Length (px)",
+                "'select' expects Array as argument 1 but it is Time (s)",
+            ]
+        );
+        assert_eq!(errors[0].span.start.column, 14);
+    }
+}
@@ -0,0 +1,737 @@
+// Вывод типов для объявлений без аннотаций: `let a = 2.5m/s2`,
+// `let speed = distance / time`.
+// Тип объявления — тип его значения; имена разрешаются по таблице символов,
+// поэтому `distance / time` получает размерность из объявлений `distance` и `time`.
+// Размерности складываются по правилам единиц: произведение и частное
+// складывают и вычитают векторы, сумма сохраняет размерность слагаемых.
+// Вызовы: `format(...)` — строка, `select(array)` — элемент массива.
+// В теле генератора привязка получает тип элемента источника, `index` — число.
+// Член перечисления (`Align.center`, `Align::center`) имеет тип перечисления.
+// Циклические определения (`a = b`, `b = a`) получают тип Unknown.
+
+use super::scope::ScopeId;
+use super::symbol_table::{SymbolId, SymbolKind, SymbolTable};
+use super::visibility::Visibility;
+use crate::ast::expressions::{BinaryOperator, Expression, ExpressionKind, UnaryOperator};
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, Generator, StatementKind};
+use crate::interpreter::stdlib::units::constant;
+use crate::lexer::position::Position;
+use crate::shared::unit::compound::CompoundUnit;
+use crate::shared::unit::dimensions::{Dimension, DimensionVector};
+use crate::shared::unit::registry::UnitRegistry;
+use crate::utils::span::Span;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Размерность числового значения
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueDimension {
+    /// Число без единицы
+    Number,
+    Unit {
+        vector: DimensionVector,
+        /// Размерность единицы литерала; у произведений — по вектору
+        named: Option<Dimension>,
+        /// Единица, как она записана: `pc`, `ft/s`, `m·s`
+        symbol: String,
+    },
+}
+
+impl ValueDimension {
+    /// Подходит ли значение под размерность из аннотации.
+    /// Безразмерные величины (углы, проценты) различаются по имени,
+    /// остальные — по вектору (`kg·m/s2` — это Force).
+    pub fn satisfies(&self, expected: Dimension) -> bool {
+        let vector = expected.vector();
+        match self {
+            ValueDimension::Number => vector.is_dimensionless(),
+            ValueDimension::Unit {
+                named: Some(named), ..
+            } if vector.is_dimensionless() => *named == expected,
+            ValueDimension::Unit { vector: found, .. } => *found == vector,
+        }
+    }
+
+    fn vector(&self) -> DimensionVector {
+        match self {
+            ValueDimension::Number => DimensionVector::NONE,
+            ValueDimension::Unit { vector, .. } => *vector,
+        }
+    }
+
+    /// Произведение или частное; `None`, если степень вектора не помещается в i8
+    fn combine(left: &Self, right: &Self, divide: bool) -> Option<Self> {
+        let vector = if divide {
+            left.vector().checked_div(right.vector())
+        } else {
+            left.vector().checked_mul(right.vector())
+        }?;
+        let symbol = match (left, right) {
+            (Self::Number, Self::Number) => return Some(Self::Number),
+            (Self::Unit { symbol, .. }, Self::Number) => symbol.clone(),
+            (Self::Number, Self::Unit { symbol, .. }) if divide => format!("1/{}", symbol),
+            (Self::Number, Self::Unit { symbol, .. }) => symbol.clone(),
+            (Self::Unit { symbol: l, .. }, Self::Unit { symbol: r, .. }) => {
+                format!("{}{}{}", l, if divide { "/" } else { "·" }, r)
+            }
+        };
+        // Умножение на число сохраняет единицу литерала (проценты остаются процентами)
+        let named = match (left, right) {
+            (Self::Unit { named, .. }, Self::Number) => *named,
+            (Self::Number, Self::Unit { named, .. }) if !divide => *named,
+            _ => Dimension::from_vector(vector),
+        };
+        Some(Self::Unit {
+            vector,
+            named,
+            symbol,
+        })
+    }
+
+    fn powi(&self, power: f64) -> Option<Self> {
+        match self {
+            Self::Number => Some(Self::Number),
+            Self::Unit { vector, symbol, .. } => {
+                if power.fract() != 0.0 || power.abs() > i8::MAX as f64 {
+                    return None;
+                }
+                let vector = vector.checked_powi(power as i8)?;
+                Some(Self::Unit {
+                    named: Dimension::from_vector(vector),
+                    vector,
+                    symbol: format!("({})^{}", symbol, power),
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValueDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueDimension::Number => write!(f, "a plain number"),
+            ValueDimension::Unit {
+                named: Some(named),
+                symbol,
+                ..
+            } => write!(f, "{:?} ({})", named, symbol),
+            // Без имени символ повторил бы вектор: `m·s (m·s)`
+            ValueDimension::Unit { vector, .. } => write!(f, "{}", vector),
+        }
+    }
+}
+
+/// Выведенный тип значения
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferredType {
+    /// Число или величина с единицей
+    Quantity(ValueDimension),
+    String,
+    Color,
+    Boolean,
+    Nil,
+    None,
+    Undefined,
+    /// Тип элементов; у разнородного массива — Unknown
+    Array(Box<InferredType>),
+    Tuple(Vec<InferredType>),
+    Dictionary,
+    /// Член перечисления: `Align.center`
+    Enum(String),
+    Unknown,
+}
+
+impl InferredType {
+    pub fn is_known(&self) -> bool {
+        *self != InferredType::Unknown
+    }
+
+    /// Общий тип элементов массива: величины одной размерности совпадают
+    fn unify(self, other: InferredType) -> InferredType {
+        match (&self, &other) {
+            (InferredType::Quantity(a), InferredType::Quantity(b)) if a.vector() == b.vector() => {
+                self
+            }
+            _ if self == other => self,
+            _ => InferredType::Unknown,
+        }
+    }
+
+    fn element(&self, position: Option<usize>) -> InferredType {
+        match (self, position) {
+            (InferredType::Array(element), None) => (**element).clone(),
+            (InferredType::Array(element), Some(i)) => element.element(Some(i)),
+            (InferredType::Tuple(items), Some(i)) => {
+                items.get(i).cloned().unwrap_or(InferredType::Unknown)
+            }
+            _ => InferredType::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for InferredType {
+    /// Имя типа для hover и JSON: `Velocity`, `Array<Length>`, `kg·m²`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InferredType::Quantity(ValueDimension::Number) => write!(f, "Number"),
+            InferredType::Quantity(ValueDimension::Unit {
+                named: Some(named), ..
+            }) => write!(f, "{:?}", named),
+            InferredType::Quantity(ValueDimension::Unit { vector, .. }) => write!(f, "{}", vector),
+            InferredType::String => write!(f, "String"),
+            InferredType::Color => write!(f, "Color"),
+            InferredType::Boolean => write!(f, "Boolean"),
+            InferredType::Nil => write!(f, "Nil"),
+            InferredType::None => write!(f, "None"),
+            InferredType::Undefined => write!(f, "Undefined"),
+            InferredType::Array(element) => write!(f, "Array<{}>", element),
+            InferredType::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(ToString::to_string).collect();
+                write!(f, "({})", items.join(", "))
+            }
+            InferredType::Dictionary => write!(f, "Dictionary"),
+            InferredType::Enum(name) => write!(f, "{}", name),
+            InferredType::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// JSON: имя типа строкой, как в hover
+impl Serialize for InferredType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Откуда символ берет значение
+#[derive(Debug, Clone, Copy)]
+enum Definition<'a> {
+    Value(&'a Expression),
+    /// Ключ без значения или тело контейнера
+    Body,
+    /// Привязка генератора: элемент источника, для `as (a, b)` — его часть
+    Element {
+        source: &'a Expression,
+        position: Option<usize>,
+    },
+}
+
+/// Типы всех символов документа
+#[derive(Debug, Clone, Default)]
+pub struct Inference {
+    types: Vec<InferredType>,
+}
+
+impl Inference {
+    pub fn type_of(&self, symbol: SymbolId) -> &InferredType {
+        &self.types[symbol.0]
+    }
+
+    /// Подсказка для символа под позицией: `speed: Velocity (ft/s)`
+    pub fn hover(&self, table: &SymbolTable, position: Position) -> Option<String> {
+        let id = table.symbol_at(position)?;
+        let name = &table.symbol(id).name;
+        Some(match self.type_of(id) {
+            InferredType::Quantity(ValueDimension::Unit { symbol, .. }) => {
+                format!("{}: {} ({})", name, self.type_of(id), symbol)
+            }
+            inferred => format!("{}: {}", name, inferred),
+        })
+    }
+
+    /// Типы по именам символов области документа — для JSON;
+    /// `private` и привязки `let`/`const` пропускаются
+    pub fn root_types<'t>(
+        &'t self,
+        table: &'t SymbolTable,
+    ) -> impl Iterator<Item = (&'t str, &'t InferredType)> {
+        table
+            .scope(table.root())
+            .symbols
+            .iter()
+            .filter(move |&&id| {
+                let symbol = table.symbol(id);
+                !symbol.is_binding() && Visibility::of(symbol).is_emitted()
+            })
+            .map(move |&id| (table.symbol(id).name.as_str(), self.type_of(id)))
+    }
+}
+
+pub struct TypeInference<'a> {
+    table: &'a SymbolTable,
+    registry: &'a UnitRegistry,
+    /// Объявления по участку имени (он же `Symbol::span`)
+    definitions: HashMap<Span, Definition<'a>>,
+    /// Использования имен, связанные с символами
+    references: HashMap<Span, SymbolId>,
+    types: Vec<Option<InferredType>>,
+    /// Символы, тип которых сейчас выводится: защита от циклов
+    resolving: Vec<SymbolId>,
+}
+
+impl<'a> TypeInference<'a> {
+    pub fn new(program: &'a Program, table: &'a SymbolTable, registry: &'a UnitRegistry) -> Self {
+        let mut inference = TypeInference {
+            table,
+            registry,
+            definitions: HashMap::new(),
+            references: table
+                .references()
+                .iter()
+                .filter_map(|r| Some((r.span, r.symbol?)))
+                .collect(),
+            types: vec![None; table.symbols().len()],
+            resolving: Vec::new(),
+        };
+        inference.collect_block(&program.body);
+        inference
+    }
+
+    /// Типы всех символов
+    pub fn infer(program: &Program, table: &SymbolTable, registry: &UnitRegistry) -> Inference {
+        let mut inference = TypeInference::new(program, table, registry);
+        let types = (0..table.symbols().len())
+            .map(|i| inference.symbol_type(SymbolId(i)))
+            .collect();
+        Inference { types }
+    }
+
+    fn collect_block(&mut self, block: &'a Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Variable(decl) => {
+                    self.define(decl.name.span, decl.value.as_ref());
+                }
+                StatementKind::Property(property) => {
+                    self.define(property.name.span, property.value.as_ref());
+                }
+                StatementKind::Yield(property) => {
+                    if let Some(value) = &property.value {
+                        self.collect_expression(value);
+                    }
+                }
+                StatementKind::Assignment(assignment) => {
+                    if let Some(value) = &assignment.value {
+                        self.collect_expression(value);
+                    }
+                }
+                StatementKind::Container(container) => {
+                    self.definitions
+                        .insert(container.name.span, Definition::Body);
+                    self.collect_block(&container.body);
+                }
+                StatementKind::Generator(generator) => self.collect_generator(generator),
+                StatementKind::Schema(schema) => {
+                    for default in schema.fields.iter().filter_map(|f| f.default.as_ref()) {
+                        self.collect_expression(default);
+                    }
+                }
+                StatementKind::Enum(_) | StatementKind::Import(_) => {}
+                StatementKind::TypeAlias(_) => {}
+                StatementKind::Expression(expression) => self.collect_expression(expression),
+            }
+        }
+    }
+
+    fn define(&mut self, name: Span, value: Option<&'a Expression>) {
+        let definition = match value {
+            Some(value) => {
+                self.collect_expression(value);
+                Definition::Value(value)
+            }
+            None => Definition::Body,
+        };
+        self.definitions.insert(name, definition);
+    }
+
+    fn collect_generator(&mut self, generator: &'a Generator) {
+        self.collect_expression(&generator.source);
+        if let Some(binding) = &generator.binding {
+            let single = binding.names.len() == 1;
+            for (i, name) in binding.names.iter().enumerate() {
+                self.definitions.insert(
+                    name.span,
+                    Definition::Element {
+                        source: &generator.source,
+                        position: (!single).then_some(i),
+                    },
+                );
+            }
+        }
+        self.collect_block(&generator.body);
+    }
+
+    /// Объявления во вложенных словарях
+    fn collect_expression(&mut self, expression: &'a Expression) {
+        match &expression.kind {
+            ExpressionKind::Dictionary(block) => self.collect_block(block),
+            ExpressionKind::Unary { operand, .. } => self.collect_expression(operand),
+            ExpressionKind::Binary { left, right, .. } => {
+                self.collect_expression(left);
+                self.collect_expression(right);
+            }
+            ExpressionKind::Call { arguments, .. } => {
+                arguments.iter().for_each(|a| self.collect_expression(a))
+            }
+            ExpressionKind::Array(items) | ExpressionKind::Tuple(items) => {
+                items.iter().for_each(|i| self.collect_expression(i))
+            }
+            _ => {}
+        }
+    }
+
+    pub fn symbol_type(&mut self, id: SymbolId) -> InferredType {
+        if let Some(known) = &self.types[id.0] {
+            return known.clone();
+        }
+        if self.resolving.contains(&id) {
+            return InferredType::Unknown;
+        }
+
+        self.resolving.push(id);
+        let symbol = self.table.symbol(id);
+        let inferred = match (symbol.kind, self.definitions.get(&symbol.span).copied()) {
+            (SymbolKind::Implicit, _) => InferredType::Quantity(ValueDimension::Number),
+            (SymbolKind::EnumMember, _) => self.enum_of(symbol.scope),
+            (SymbolKind::Container, _) | (_, Some(Definition::Body)) => {
+                if symbol.body.is_some() {
+                    InferredType::Dictionary
+                } else {
+                    InferredType::Unknown
+                }
+            }
+            (_, Some(Definition::Value(value))) => self.value_type(value),
+            (_, Some(Definition::Element { source, position })) => {
+                self.expression_type(source).element(position)
+            }
+            (_, None) => InferredType::Unknown,
+        };
+        self.resolving.pop();
+
+        self.types[id.0] = Some(inferred.clone());
+        inferred
+    }
+
+    /// Значение записи: ненайденное имя целиком — слово-строка
+    pub fn value_type(&mut self, value: &Expression) -> InferredType {
+        if let Some(name) = value.as_identifier()
+            && self.reference_of(value).is_none()
+            && constant(name).is_none()
+        {
+            return InferredType::String;
+        }
+        self.expression_type(value)
+    }
+
+    pub fn expression_type(&mut self, expression: &Expression) -> InferredType {
+        match &expression.kind {
+            ExpressionKind::Number(_) => InferredType::Quantity(ValueDimension::Number),
+            ExpressionKind::Unit { suffix, .. } => self
+                .unit_dimension(suffix)
+                .map_or(InferredType::Unknown, InferredType::Quantity),
+            ExpressionKind::String(_) => InferredType::String,
+            ExpressionKind::Color(_) => InferredType::Color,
+            ExpressionKind::Boolean(_) => InferredType::Boolean,
+            ExpressionKind::Nil => InferredType::Nil,
+            ExpressionKind::None => InferredType::None,
+            ExpressionKind::Undefined => InferredType::Undefined,
+            ExpressionKind::Identifier(name) => match self.references.get(&name.span) {
+                Some(&id) => self.symbol_type(id),
+                None => name
+                    .as_static()
+                    .and_then(constant)
+                    .map_or(InferredType::Unknown, |c| {
+                        InferredType::Quantity(ValueDimension::Unit {
+                            vector: c.dimension(),
+                            named: c.named_dimension(),
+                            symbol: c.unit.to_string(),
+                        })
+                    }),
+            },
+            ExpressionKind::Unary { operator, operand } => match operator {
+                UnaryOperator::Not => InferredType::Boolean,
+                UnaryOperator::Negate | UnaryOperator::Plus => self.expression_type(operand),
+            },
+            ExpressionKind::Binary {
+                operator,
+                left,
+                right,
+            } => self.binary_type(*operator, left, right),
+            ExpressionKind::Call { callee, arguments } => self.call_type(callee, arguments),
+            ExpressionKind::Member { object, member, .. } => self.member_type(object, member),
+            ExpressionKind::Index { object, index } => {
+                let position = match index.kind {
+                    ExpressionKind::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as usize),
+                    _ => None,
+                };
+                match self.expression_type(object) {
+                    InferredType::Tuple(items) => position
+                        .and_then(|i| items.get(i).cloned())
+                        .unwrap_or(InferredType::Unknown),
+                    InferredType::Array(element) => *element,
+                    _ => InferredType::Unknown,
+                }
+            }
+            ExpressionKind::Array(items) => {
+                let element = items
+                    .iter()
+                    .filter(|item| !matches!(item.kind, ExpressionKind::Hole))
+                    .map(|item| self.expression_type(item))
+                    .reduce(InferredType::unify)
+                    .unwrap_or(InferredType::Unknown);
+                InferredType::Array(Box::new(element))
+            }
+            ExpressionKind::Tuple(items) => {
+                InferredType::Tuple(items.iter().map(|i| self.expression_type(i)).collect())
+            }
+            ExpressionKind::Dictionary(_) => InferredType::Dictionary,
+            ExpressionKind::Context(_) | ExpressionKind::Hole => InferredType::Unknown,
+        }
+    }
+
+    /// Символ, с которым связан идентификатор
+    pub fn reference_of(&self, expression: &Expression) -> Option<SymbolId> {
+        match &expression.kind {
+            ExpressionKind::Identifier(name) => self.references.get(&name.span).copied(),
+            _ => None,
+        }
+    }
+
+    fn unit_dimension(&self, suffix: &str) -> Option<ValueDimension> {
+        if let Some((_, def)) = self.registry.resolve(suffix) {
+            return Some(ValueDimension::Unit {
+                vector: def.dimension.vector(),
+                named: Some(def.dimension),
+                symbol: suffix.to_string(),
+            });
+        }
+        let compound = CompoundUnit::parse(suffix, self.registry).ok()?;
+        Some(ValueDimension::Unit {
+            named: compound.named_dimension(),
+            vector: compound.dimension,
+            symbol: compound.symbol,
+        })
+    }
+
+    fn binary_type(
+        &mut self,
+        operator: BinaryOperator,
+        left: &Expression,
+        right: &Expression,
+    ) -> InferredType {
+        let left_type = self.expression_type(left);
+        let right_type = self.expression_type(right);
+        match operator {
+            // Сумма имеет размерность слагаемых; `2 + 3px` — как у единицы
+            BinaryOperator::Add
+            | BinaryOperator::Subtract
+            | BinaryOperator::Monus
+            | BinaryOperator::Modulo
+            | BinaryOperator::Tolerance => match (left_type, right_type) {
+                (InferredType::String, _) | (_, InferredType::String)
+                    if operator == BinaryOperator::Add =>
+                {
+                    InferredType::String
+                }
+                (
+                    InferredType::Quantity(ValueDimension::Number),
+                    right @ InferredType::Quantity(_),
+                ) => right,
+                (left @ InferredType::Quantity(_), InferredType::Quantity(_)) => left,
+                _ => InferredType::Unknown,
+            },
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::IntegerDivide => {
+                match (left_type, right_type) {
+                    (InferredType::Quantity(l), InferredType::Quantity(r)) => {
+                        ValueDimension::combine(&l, &r, operator != BinaryOperator::Multiply)
+                            .map_or(InferredType::Unknown, InferredType::Quantity)
+                    }
+                    _ => InferredType::Unknown,
+                }
+            }
+            BinaryOperator::Power => match (left_type, &right.kind) {
+                (InferredType::Quantity(base), ExpressionKind::Number(power)) => base
+                    .powi(*power)
+                    .map_or(InferredType::Unknown, InferredType::Quantity),
+                (InferredType::Quantity(ValueDimension::Number), _) => {
+                    InferredType::Quantity(ValueDimension::Number)
+                }
+                _ => InferredType::Unknown,
+            },
+            BinaryOperator::Range => InferredType::Array(Box::new(left_type)),
+            BinaryOperator::Coalesce => match left_type {
+                InferredType::Unknown | InferredType::Nil | InferredType::None => right_type,
+                left => left,
+            },
+            BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::StrictEqual
+            | BinaryOperator::Matches
+            | BinaryOperator::Less
+            | BinaryOperator::LessEqual
+            | BinaryOperator::Greater
+            | BinaryOperator::GreaterEqual
+            | BinaryOperator::And
+            | BinaryOperator::Or => InferredType::Boolean,
+        }
+    }
+
+    fn call_type(&mut self, callee: &Expression, arguments: &[Expression]) -> InferredType {
+        match callee.as_identifier() {
+            Some("format") => InferredType::String,
+            Some("select") => arguments.first().map_or(InferredType::Unknown, |a| {
+                self.expression_type(a).element(None)
+            }),
+            _ => InferredType::Unknown,
+        }
+    }
+
+    /// Тип членов перечисления — само перечисление
+    fn enum_of(&self, scope: ScopeId) -> InferredType {
+        self.table
+            .symbols()
+            .iter()
+            .find(|s| s.kind == SymbolKind::Enum && s.body == Some(scope))
+            .map_or(InferredType::Unknown, |s| {
+                InferredType::Enum(s.name.clone())
+            })
+    }
+
+    /// `app.gap`: член словаря или контейнера, объявленный в его теле
+    fn member_type(&mut self, object: &Expression, member: &str) -> InferredType {
+        let Some(id) = self.reference_of(object) else {
+            return InferredType::Unknown;
+        };
+        let Some(body) = self.table.symbol(id).body else {
+            return InferredType::Unknown;
+        };
+        let scope = self.table.scope(body);
+        match scope.declared(member).first().copied() {
+            Some(symbol) => self.symbol_type(symbol),
+            None => InferredType::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::semantic::analyzer::Analyzer;
+
+    /// Тип каждого символа: `имя: Тип`
+    fn infer(source: &str) -> Vec<String> {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let analysis = Analyzer::analyze(&program);
+        let registry = UnitRegistry::new();
+        let inference = TypeInference::infer(&program, &analysis.table, &registry);
+        analysis
+            .table
+            .symbols()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("{}: {}", s.name, inference.type_of(SymbolId(i))))
+            .collect()
+    }
+
+    #[test]
+    fn infers_dimensions_through_arithmetic() {
+        let types = infer(
+            r#"let distance = 100m
+let time = 9.58s
+let speed = distance / time
+let a = 2.5m/s2
+const mass = 70kg
+let force = mass * a
+let work = force * distance
+let area = distance ^ 2
+let energy = mass * c ^ 2
+let later = time + 2"#,
+        );
+        assert_eq!(
+            types,
+            [
+                "distance: Length",
+                "time: Time",
+                "speed: Velocity",
+                "a: Acceleration",
+                "mass: Mass",
+                "force: Force",
+                "work: Energy",
+                "area: Area",
+                "energy: Energy",
+                "later: Time",
+            ]
+        );
+    }
+
+    #[test]
+    fn infers_primitives_calls_and_generators() {
+        let types = infer(
+            r#"const signs = [0x2648, 0x2649]
+const word snow
+let ready = 2 > 1
+spread signs as let sign
+    local code-point = format("{:04X}", sign + index)
+    local step = index * 4px
+let pick = select([1s, 2s])
+let first = again
+let again = first"#,
+        );
+        assert_eq!(
+            types,
+            [
+                "signs: Array<Number>",
+                "word: String",
+                "ready: Boolean",
+                "pick: Time",
+                "first: Unknown",
+                "again: Unknown",
+                "index: Number",
+                "sign: Number",
+                "code-point: String",
+                "step: Length",
+            ]
+        );
+    }
+
+    #[test]
+    fn exponent_overflow_is_unknown() {
+        let types = infer(
+            r#"let a = 1m ^ 100
+let b = a * a
+let c = (1s * 1s) * (1s * 1s)
+let d = (c * c) * (c * c)
+let e = (d * d) * (d * d)
+let f = (e * e) * (e * e)"#,
+        );
+        assert_eq!(types[1], "b: Unknown");
+        assert_eq!(types[4], "e: s⁶⁴");
+        assert_eq!(types[5], "f: Unknown");
+    }
+
+    #[test]
+    fn hover_shows_type_and_unit() {
+        let source = "let speed = 278ft/s\napp\n    gap: 8px\nlet spacing = app.gap * 2";
+        let (program, _) = parse(source);
+        let analysis = Analyzer::analyze(&program);
+        let registry = UnitRegistry::new();
+        let inference = TypeInference::infer(&program, &analysis.table, &registry);
+
+        let hover = |line, column, offset| {
+            inference.hover(&analysis.table, Position::new(line, column, offset))
+        };
+        assert_eq!(hover(1, 6, 5).as_deref(), Some("speed: Velocity (ft/s)"));
+        assert_eq!(hover(4, 6, 42).as_deref(), Some("spacing: Length (px)"));
+        assert_eq!(hover(2, 1, 20).as_deref(), Some("app: Dictionary"));
+
+        let json: Vec<(&str, String)> = inference
+            .root_types(&analysis.table)
+            .map(|(name, t)| (name, t.to_string()))
+            .collect();
+        assert_eq!(json, [("app", "Dictionary".to_string())]);
+    }
+}
@@ -0,0 +1,459 @@
+// Типы аннотаций после раскрытия синонимов:
+//
+//     type Dim = Length | Percent
+//     alias Px = Length
+//     let sizes<Array<Dim>> = [4px, 50%]
+//     durations<Dictionary<String, Time>> { fade: 200ms }
+//
+// `type` и `alias` — одно и то же: синоним прозрачен, `let gap<Px>` проверяется
+// как `let gap<Length>`. Имена синонимов, как и схем, — отдельное пространство,
+// синоним виден во всем документе. Встроенные имена (примитивы, размерности)
+// синонимом не переопределяются.
+//
+// `Array` без аргументов — `Array<Auto>`, `Dictionary` — `Dictionary<String, Auto>`.
+// Ключи словаря — всегда строки, тип ключа записывается для читателя.
+
+use super::error::{SemanticError, SemanticErrorType};
+use super::type_inference::{InferredType, ValueDimension};
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, StatementKind, TypeAlias};
+use crate::ast::types::{TypeAnnotation, TypeKind};
+use crate::shared::unit::dimensions::Dimension;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclaredType {
+    Auto,
+    Number,
+    String,
+    Color,
+    Boolean,
+    Nil,
+    None,
+    Undefined,
+    /// `Length`, `Speed`; `name` — как в аннотации
+    Quantity {
+        dimension: Dimension,
+        name: String,
+    },
+    Array(Box<DeclaredType>),
+    /// Тип ключа и тип значения
+    Dictionary(Box<DeclaredType>, Box<DeclaredType>),
+    Schema(String),
+    Enum(String),
+    Union(Vec<DeclaredType>),
+}
+
+impl DeclaredType {
+    /// Подходит ли значение под тип; None — тип значения не известен заранее
+    pub fn accepts(&self, found: &InferredType) -> Option<bool> {
+        let accepted = match self {
+            DeclaredType::Auto => true,
+            DeclaredType::Number => *found == InferredType::Quantity(ValueDimension::Number),
+            DeclaredType::String => *found == InferredType::String,
+            DeclaredType::Color => *found == InferredType::Color,
+            DeclaredType::Boolean => *found == InferredType::Boolean,
+            DeclaredType::Nil => *found == InferredType::Nil,
+            DeclaredType::None => *found == InferredType::None,
+            DeclaredType::Undefined => *found == InferredType::Undefined,
+            DeclaredType::Quantity { dimension, .. } => {
+                matches!(found, InferredType::Quantity(q) if q.satisfies(*dimension))
+            }
+            DeclaredType::Array(element) => match found {
+                InferredType::Array(item) if item.is_known() => return element.accepts(item),
+                InferredType::Array(_) => true,
+                InferredType::Tuple(items) => {
+                    return items
+                        .iter()
+                        .filter(|item| item.is_known())
+                        .map(|item| element.accepts(item))
+                        .try_fold(true, |all, item| Some(all && item?));
+                }
+                _ => false,
+            },
+            DeclaredType::Dictionary(..) | DeclaredType::Schema(_) => {
+                *found == InferredType::Dictionary
+            }
+            DeclaredType::Enum(name) => match found {
+                InferredType::Enum(found) => found == name,
+                // Слово может быть именем члена: это проверяет validator.rs
+                InferredType::String => return None,
+                _ => false,
+            },
+            DeclaredType::Union(members) => {
+                let results: Vec<Option<bool>> = members.iter().map(|m| m.accepts(found)).collect();
+                if results.contains(&Some(true)) {
+                    true
+                } else if results.contains(&None) {
+                    return None;
+                } else {
+                    false
+                }
+            }
+        };
+        Some(accepted)
+    }
+}
+
+impl fmt::Display for DeclaredType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeclaredType::Auto => write!(f, "Auto"),
+            DeclaredType::Number => write!(f, "Number"),
+            DeclaredType::String => write!(f, "String"),
+            DeclaredType::Color => write!(f, "Color"),
+            DeclaredType::Boolean => write!(f, "Boolean"),
+            DeclaredType::Nil => write!(f, "Nil"),
+            DeclaredType::None => write!(f, "None"),
+            DeclaredType::Undefined => write!(f, "Undefined"),
+            DeclaredType::Quantity { name, .. } => write!(f, "{}", name),
+            DeclaredType::Array(element) => match **element {
+                DeclaredType::Auto => write!(f, "Array"),
+                _ => write!(f, "Array<{}>", element),
+            },
+            DeclaredType::Dictionary(key, value) => match (&**key, &**value) {
+                (DeclaredType::String, DeclaredType::Auto) => write!(f, "Dictionary"),
+                _ => write!(f, "Dictionary<{}, {}>", key, value),
+            },
+            DeclaredType::Schema(name) | DeclaredType::Enum(name) => write!(f, "{}", name),
+            DeclaredType::Union(members) => {
+                let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+        }
+    }
+}
+
+/// Имена типов документа: синонимы, схемы и перечисления
+#[derive(Debug, Clone, Default)]
+pub struct TypeResolver<'a> {
+    /// Синонимы в порядке объявления; повторно объявленные не попадают
+    aliases: Vec<&'a TypeAlias>,
+    by_name: HashMap<&'a str, usize>,
+    duplicates: Vec<&'a TypeAlias>,
+    schemas: HashSet<&'a str>,
+    enums: HashSet<&'a str>,
+}
+
+impl<'a> TypeResolver<'a> {
+    pub fn collect(program: &'a Program) -> Self {
+        let mut resolver = Self::default();
+        resolver.collect_block(&program.body);
+        resolver
+    }
+
+    fn collect_block(&mut self, block: &'a Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::TypeAlias(alias) => {
+                    let Some(name) = alias.name.as_static() else {
+                        continue;
+                    };
+                    if self.by_name.contains_key(name) {
+                        self.duplicates.push(alias);
+                        continue;
+                    }
+                    self.by_name.insert(name, self.aliases.len());
+                    self.aliases.push(alias);
+                }
+                StatementKind::Schema(schema) => {
+                    self.schemas.extend(schema.name.as_static());
+                }
+                StatementKind::Enum(decl) => {
+                    self.enums.extend(decl.name.as_static());
+                }
+                StatementKind::Container(container) => self.collect_block(&container.body),
+                StatementKind::Generator(generator) => self.collect_block(&generator.body),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn alias(&self, name: &str) -> Option<&'a TypeAlias> {
+        self.by_name.get(name).map(|&index| self.aliases[index])
+    }
+
+    /// Тип аннотации; ошибки самой аннотации (неизвестные имена, число
+    /// аргументов) — в `errors`. Об ошибках внутри синонимов сообщает
+    /// `check_aliases`, здесь такой синоним дает `None` молча.
+    pub fn resolve(
+        &self,
+        annotation: &TypeAnnotation,
+        errors: &mut Vec<SemanticError>,
+    ) -> Option<DeclaredType> {
+        self.resolve_in(annotation, &mut Vec::new(), errors)
+    }
+
+    fn resolve_in(
+        &self,
+        annotation: &TypeAnnotation,
+        path: &mut Vec<&'a str>,
+        errors: &mut Vec<SemanticError>,
+    ) -> Option<DeclaredType> {
+        let (name, arguments) = match &annotation.kind {
+            TypeKind::Named(name) => (name, &[][..]),
+            TypeKind::Generic { name, arguments } => (name, &arguments[..]),
+            TypeKind::Union(members) => {
+                // Все члены, чтобы сообщить обо всех ошибках сразу
+                let members: Vec<Option<DeclaredType>> = members
+                    .iter()
+                    .map(|member| self.resolve_in(member, path, errors))
+                    .collect();
+                return Some(DeclaredType::Union(
+                    members.into_iter().collect::<Option<_>>()?,
+                ));
+            }
+        };
+
+        let mut resolved = Vec::new();
+        for argument in arguments {
+            resolved.push(self.resolve_in(argument, path, errors));
+        }
+        let expected = match name.as_str() {
+            "Array" => 1,
+            "Dictionary" => 2,
+            _ => 0,
+        };
+        if !arguments.is_empty() && arguments.len() != expected {
+            let message = match expected {
+                0 => format!("'{}' takes no type arguments", name),
+                _ => format!(
+                    "'{}' expects {} type argument{}, found {}",
+                    name,
+                    expected,
+                    if expected == 1 { "" } else { "s" },
+                    arguments.len()
+                ),
+            };
+            errors.push(SemanticError::new(
+                SemanticErrorType::TypeArguments {
+                    name: name.clone(),
+                    expected,
+                    found: arguments.len(),
+                },
+                annotation.span,
+                message,
+            ));
+            return None;
+        }
+        let mut arguments = resolved
+            .into_iter()
+            .collect::<Option<Vec<_>>>()?
+            .into_iter();
+        let mut argument = |default| Box::new(arguments.next().unwrap_or(default));
+
+        let resolved = match name.as_str() {
+            "Auto" => DeclaredType::Auto,
+            "Number" => DeclaredType::Number,
+            "String" => DeclaredType::String,
+            "Color" => DeclaredType::Color,
+            "Boolean" => DeclaredType::Boolean,
+            "Nil" => DeclaredType::Nil,
+            "None" => DeclaredType::None,
+            "Undefined" => DeclaredType::Undefined,
+            "Array" => DeclaredType::Array(argument(DeclaredType::Auto)),
+            "Dictionary" => DeclaredType::Dictionary(
+                argument(DeclaredType::String),
+                argument(DeclaredType::Auto),
+            ),
+            _ => {
+                if let Some(dimension) = Dimension::from_name(name) {
+                    DeclaredType::Quantity {
+                        dimension,
+                        name: name.clone(),
+                    }
+                } else if let Some(alias) = self.alias(name) {
+                    let name = alias.name.as_static()?;
+                    if path.contains(&name) {
+                        return None;
+                    }
+                    path.push(name);
+                    let resolved = self.resolve_in(&alias.annotation, path, &mut Vec::new());
+                    path.pop();
+                    resolved?
+                } else if self.schemas.contains(name.as_str()) {
+                    DeclaredType::Schema(name.clone())
+                } else if self.enums.contains(name.as_str()) {
+                    DeclaredType::Enum(name.clone())
+                } else {
+                    errors.push(SemanticError::new(
+                        SemanticErrorType::UnknownType(name.clone()),
+                        annotation.span,
+                        format!("Unknown type '{}'", name),
+                    ));
+                    return None;
+                }
+            }
+        };
+        Some(resolved)
+    }
+
+    /// Повторные объявления, неизвестные имена и циклы в синонимах
+    pub fn check_aliases(&self) -> Vec<SemanticError> {
+        let mut errors = Vec::new();
+        for alias in &self.duplicates {
+            let name = alias.name.as_static().unwrap_or_default();
+            let original = self.alias(name).map_or(alias.name.span, |a| a.name.span);
+            errors.push(SemanticError::new(
+                SemanticErrorType::DuplicateDeclaration {
+                    name: name.to_string(),
+                    original,
+                },
+                alias.name.span,
+                format!("'{}' is already declared at {}", name, original),
+            ));
+        }
+
+        for (index, alias) in self.aliases.iter().enumerate() {
+            let name = alias.name.as_static().unwrap_or_default();
+            let mut path = vec![name];
+            if self.find_cycle(&alias.annotation, &mut path) {
+                // О цикле сообщается один раз — у синонима, объявленного первым
+                if path.iter().all(|n| self.by_name[n] >= index) {
+                    let path: Vec<String> = path.into_iter().map(String::from).collect();
+                    let message = format!("Type alias cycle: {}", path.join(" -> "));
+                    errors.push(SemanticError::new(
+                        SemanticErrorType::ReferenceCycle { path },
+                        alias.name.span,
+                        message,
+                    ));
+                }
+                continue;
+            }
+            self.resolve(&alias.annotation, &mut errors);
+        }
+        errors
+    }
+
+    /// Путь по синонимам в аннотации обратно к первому синониму пути
+    fn find_cycle(&self, annotation: &'a TypeAnnotation, path: &mut Vec<&'a str>) -> bool {
+        let (name, arguments) = match &annotation.kind {
+            TypeKind::Named(name) => (name, &[][..]),
+            TypeKind::Generic { name, arguments } => (name, &arguments[..]),
+            TypeKind::Union(members) => {
+                return members.iter().any(|member| self.find_cycle(member, path));
+            }
+        };
+        if arguments
+            .iter()
+            .any(|argument| self.find_cycle(argument, path))
+        {
+            return true;
+        }
+        if Dimension::from_name(name).is_some() {
+            return false;
+        }
+        let Some(alias) = self.alias(name) else {
+            return false;
+        };
+        if path[0] == name {
+            path.push(path[0]);
+            return true;
+        }
+        if path.contains(&name.as_str()) {
+            return false;
+        }
+        path.push(alias.name.as_static().unwrap_or_default());
+        if self.find_cycle(&alias.annotation, path) {
+            return true;
+        }
+        path.pop();
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn resolve(source: &str, annotation: &str) -> (Option<String>, Vec<String>) {
+        let (program, errors) = parse(&format!("{}let probe<{}> = nil\n", source, annotation));
+        assert!(errors.is_empty(), "{:?}", errors);
+        let resolver = TypeResolver::collect(&program);
+        let Some(StatementKind::Variable(probe)) = program.body.statements.last().map(|s| &s.kind)
+        else {
+            panic!("{:?}", program.body.statements.last());
+        };
+        let mut errors = Vec::new();
+        let resolved = resolver.resolve(probe.annotation.as_ref().unwrap(), &mut errors);
+        let messages = errors.into_iter().map(|e| e.message).collect();
+        (resolved.map(|t| t.to_string()), messages)
+    }
+
+    const ALIASES: &str = "type Dim = Length | Percent\nalias Px = Length\ntype Sizes = Array<Dim>\nenum Align { start, end }\n";
+
+    #[test]
+    fn expands_aliases_and_generics() {
+        let cases = [
+            ("Px", "Length"),
+            ("Sizes", "Array<Length | Percent>"),
+            ("Dictionary<String, Time>", "Dictionary<String, Time>"),
+            ("Array<Px> | Align", "Array<Length> | Align"),
+            ("Array<Array<Speed>>", "Array<Array<Speed>>"),
+            ("Dictionary", "Dictionary"),
+        ];
+        for (annotation, expected) in cases {
+            let (resolved, errors) = resolve(ALIASES, annotation);
+            assert!(errors.is_empty(), "{}: {:?}", annotation, errors);
+            assert_eq!(resolved.as_deref(), Some(expected), "{}", annotation);
+        }
+
+        let (resolved, errors) = resolve(ALIASES, "Dictionary<Time> | Length<Px> | Sizs");
+        assert_eq!(resolved, None);
+        assert_eq!(
+            errors,
+            [
+                "'Dictionary' expects 2 type arguments, found 1",
+                "'Length' takes no type arguments",
+                "Unknown type 'Sizs'",
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_inferred_values() {
+        let length = InferredType::Quantity(ValueDimension::Unit {
+            vector: Dimension::Length.vector(),
+            named: Some(Dimension::Length),
+            symbol: "px".to_string(),
+        });
+        let dim = DeclaredType::Union(vec![
+            DeclaredType::Quantity {
+                dimension: Dimension::Length,
+                name: "Length".to_string(),
+            },
+            DeclaredType::Enum("Align".to_string()),
+        ]);
+        assert_eq!(dim.accepts(&length), Some(true));
+        assert_eq!(dim.accepts(&InferredType::String), None);
+        assert_eq!(dim.accepts(&InferredType::Boolean), Some(false));
+
+        let sizes = DeclaredType::Array(Box::new(dim));
+        let array = |item| InferredType::Array(Box::new(item));
+        assert_eq!(sizes.accepts(&array(length)), Some(true));
+        assert_eq!(sizes.accepts(&array(InferredType::Color)), Some(false));
+        assert_eq!(sizes.accepts(&array(InferredType::Unknown)), Some(true));
+    }
+
+    #[test]
+    fn reports_alias_cycles_and_duplicates() {
+        let (program, errors) = parse(
+            "type A = B | Length\ntype B = Array<A>\nalias Self = Self\nalias Px = Length\nalias Px = Time\ntype Ok = Px | Pct\n",
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+        let errors = TypeResolver::collect(&program).check_aliases();
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'Px' is already declared at 4:7",
+                "Type alias cycle: A -> B -> A",
+                "Type alias cycle: Self -> Self",
+                "Unknown type 'Pct'",
+            ]
+        );
+        assert_eq!(errors[1].span.start.line, 1);
+    }
+}
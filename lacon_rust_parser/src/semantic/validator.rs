@@ -0,0 +1,836 @@
+// Проверка словарей по схемам:
+//
+//     struct Button extends Control implements Sized {
+//         label<String>
+//         width<Length> = 120px
+//         icon?<String>
+//         align<String> in [start, center, end] = center
+//     }
+//     ok<Button> { label: "OK"; width: 80px }
+//
+// В словаре с аннотацией-схемой сообщается об отсутствующих обязательных полях
+// (без `?` и без значения по умолчанию), о лишних ключах и о значениях не того
+// типа. Тип поля — размерность (`Length`), примитив (`String`, `Number`, `Color`,
+// `Boolean`, `Array`, `Dictionary`, `Auto` — любой) или другая схема; вложенный
+// словарь в поле со схемой проверяется так же. Значения, тип которых известен
+// только при вычислении, пропускаются. Словарь с генераторами или ключами со
+// вставками получает поля при вычислении, поэтому отсутствующие поля в нем
+// не сообщаются.
+//
+// `extends` наследует поля базовой схемы, свои поля заменяют одноименные;
+// базой может быть только схема. `implements` ничего не наследует: struct сам
+// объявляет поля интерфейса, и после `implements` допустимы только интерфейсы.
+// Одно имя поля дважды в одной схеме — ошибка.
+// Имена схем — отдельное пространство, схема видна во всем документе.
+// Типы полей разбирает types.rs, поэтому в полях работают синонимы
+// (`width<Px>`), `Array<Length>` и объединения.
+//
+// Тип поля может быть перечислением (`align<Align>`): значение — член
+// перечисления (`Align.center`) или слово/строка с именем члена (`center`).
+//
+// Схемы и перечисления внутри составных типов проверяются поэлементно:
+// `nodes<Array<Node>>`, `Dictionary<String, Node>`, `Node | Nil`. Член
+// объединения выбирается по типу значения, если подходит только один.
+
+use super::enums::Enums;
+use super::error::{SemanticError, SemanticErrorType};
+use super::symbol_table::SymbolTable;
+use super::type_inference::{InferredType, TypeInference};
+use super::types::{DeclaredType, TypeResolver};
+use crate::ast::expressions::{Expression, ExpressionKind, Name, Segment};
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, StatementKind};
+use crate::ast::types::{FieldType, ObjectKind, ObjectType, TypeAnnotation};
+use crate::shared::unit::registry::UnitRegistry;
+use crate::utils::span::Span;
+use std::collections::HashMap;
+
+pub struct Validator<'a> {
+    /// Схемы в порядке объявления; повторно объявленные не попадают
+    schemas: Vec<&'a ObjectType>,
+    by_name: HashMap<&'a str, usize>,
+    enums: Enums<'a>,
+    types: TypeResolver<'a>,
+    inference: TypeInference<'a>,
+    errors: Vec<SemanticError>,
+}
+
+impl<'a> Validator<'a> {
+    pub fn validate(
+        program: &'a Program,
+        table: &'a SymbolTable,
+        registry: &'a UnitRegistry,
+    ) -> Vec<SemanticError> {
+        let mut validator = Validator {
+            schemas: Vec::new(),
+            by_name: HashMap::new(),
+            enums: Enums::collect(program),
+            types: TypeResolver::collect(program),
+            inference: TypeInference::new(program, table, registry),
+            errors: Vec::new(),
+        };
+        validator.collect(&program.body);
+        for index in 0..validator.schemas.len() {
+            validator.check_schema(index);
+        }
+        validator.visit_block(&program.body);
+        validator.errors
+    }
+
+    pub fn schema(&self, name: &str) -> Option<&'a ObjectType> {
+        self.by_name.get(name).map(|&index| self.schemas[index])
+    }
+
+    /// Поля схемы вместе с унаследованными через `extends`
+    pub fn fields(&self, schema: &'a ObjectType) -> Vec<&'a FieldType> {
+        let mut fields = Vec::new();
+        let mut seen: Vec<&str> = schema.name.as_static().into_iter().collect();
+        self.collect_fields(schema, &mut seen, &mut fields);
+        fields
+    }
+
+    fn collect_fields(
+        &self,
+        schema: &'a ObjectType,
+        seen: &mut Vec<&'a str>,
+        fields: &mut Vec<&'a FieldType>,
+    ) {
+        for base in &schema.extends {
+            let Some(name) = base.name() else {
+                continue;
+            };
+            if seen.contains(&name) {
+                continue;
+            }
+            if let Some(base) = self.schema(name) {
+                seen.push(name);
+                self.collect_fields(base, seen, fields);
+            }
+        }
+        for field in &schema.fields {
+            match fields
+                .iter()
+                .position(|f| f.name.as_static() == field.name.as_static())
+            {
+                Some(index) => fields[index] = field,
+                None => fields.push(field),
+            }
+        }
+    }
+
+    /// Схемы из документа и контейнеров
+    fn collect(&mut self, block: &'a Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Schema(schema) => self.declare(schema),
+                StatementKind::Container(container) => self.collect(&container.body),
+                StatementKind::Generator(generator) => self.collect(&generator.body),
+                _ => {}
+            }
+        }
+    }
+
+    fn declare(&mut self, schema: &'a ObjectType) {
+        let Some(name) = schema.name.as_static() else {
+            return;
+        };
+        if let Some(&index) = self.by_name.get(name) {
+            let original = self.schemas[index].name.span;
+            self.errors.push(SemanticError::new(
+                SemanticErrorType::DuplicateDeclaration {
+                    name: name.to_string(),
+                    original,
+                },
+                schema.name.span,
+                format!("'{}' is already declared at {}", name, original),
+            ));
+            return;
+        }
+        self.by_name.insert(name, self.schemas.len());
+        self.schemas.push(schema);
+    }
+
+    /// Имена типов, наследование, значения по умолчанию и `implements`
+    fn check_schema(&mut self, index: usize) {
+        let schema = self.schemas[index];
+        let name = schema.name.as_static().unwrap_or_default();
+        for base in &schema.extends {
+            self.check_base(name, base, false);
+        }
+        for base in &schema.implements {
+            self.check_base(name, base, true);
+        }
+        for (position, field) in schema.fields.iter().enumerate() {
+            if let Some(annotation) = &field.annotation {
+                self.check_known(annotation);
+            }
+            let field_name = field.name.as_static();
+            let original = schema.fields[..position]
+                .iter()
+                .find(|f| f.name.as_static() == field_name);
+            if let (Some(field_name), Some(original)) = (field_name, original) {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorType::DuplicateDeclaration {
+                        name: field_name.to_string(),
+                        original: original.name.span,
+                    },
+                    field.name.span,
+                    format!(
+                        "'{}' is already declared at {}",
+                        field_name, original.name.span
+                    ),
+                ));
+            }
+        }
+
+        let mut path = vec![name];
+        if self.find_cycle(schema, &mut path) {
+            // О цикле сообщается один раз — у схемы, объявленной первой
+            if path.iter().all(|n| self.by_name[n] >= index) {
+                let path: Vec<String> = path.into_iter().map(String::from).collect();
+                let message = format!("Inheritance cycle: {}", path.join(" -> "));
+                self.errors.push(SemanticError::new(
+                    SemanticErrorType::ReferenceCycle { path },
+                    schema.name.span,
+                    message,
+                ));
+            }
+            return;
+        }
+
+        for field in &schema.fields {
+            if let Some(default) = &field.default {
+                self.check_field(field, default, field.name.span);
+            }
+        }
+
+        let fields = self.fields(schema);
+        let mut checked = Vec::new();
+        for interface in &schema.implements {
+            let Some(interface) = interface.name() else {
+                continue;
+            };
+            // `implements I, I` сообщает о каждом поле один раз
+            if checked.contains(&interface) {
+                continue;
+            }
+            checked.push(interface);
+            let Some(required) = self
+                .schema(interface)
+                .filter(|s| s.kind == ObjectKind::Interface)
+            else {
+                continue;
+            };
+            for field in self.fields(required) {
+                let declared = fields
+                    .iter()
+                    .any(|f| f.name.as_static() == field.name.as_static());
+                if declared {
+                    continue;
+                }
+                let field = field.name.as_static().unwrap_or_default();
+                self.errors.push(SemanticError::new(
+                    SemanticErrorType::UnimplementedField {
+                        schema: name.to_string(),
+                        interface: interface.to_string(),
+                        field: field.to_string(),
+                    },
+                    schema.name.span,
+                    format!(
+                        "'{}' does not declare '{}' required by '{}'",
+                        name, field, interface
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Путь по `extends` обратно к первой схеме пути
+    fn find_cycle(&self, schema: &'a ObjectType, path: &mut Vec<&'a str>) -> bool {
+        for base in &schema.extends {
+            let Some(name) = base.name() else {
+                continue;
+            };
+            if path[0] == name {
+                path.push(name);
+                return true;
+            }
+            if path.contains(&name) {
+                continue;
+            }
+            let Some(next) = self.schema(name) else {
+                continue;
+            };
+            path.push(name);
+            if self.find_cycle(next, path) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    /// `extends` — только схема, `implements` — только интерфейс
+    fn check_base(&mut self, name: &str, base: &TypeAnnotation, interface: bool) {
+        let found = match self.types.resolve(base, &mut self.errors) {
+            None => return,
+            Some(DeclaredType::Schema(base)) => match self.schema(&base) {
+                Some(schema) if interface && schema.kind != ObjectKind::Interface => {
+                    format!("struct '{}'", base)
+                }
+                _ => return,
+            },
+            Some(DeclaredType::Enum(base)) => format!("enum '{}'", base),
+            Some(_) => format!("'{}'", base),
+        };
+        let message = if interface {
+            format!(
+                "'{}' can only implement interfaces, {} is not one",
+                name, found
+            )
+        } else {
+            format!("'{}' cannot extend {}", name, found)
+        };
+        self.errors.push(SemanticError::new(
+            SemanticErrorType::InvalidBase {
+                schema: name.to_string(),
+                base: base.to_string(),
+            },
+            base.span,
+            message,
+        ));
+    }
+
+    fn check_known(&mut self, annotation: &TypeAnnotation) {
+        self.types.resolve(annotation, &mut self.errors);
+    }
+
+    // --- Словари с аннотацией ---
+
+    fn visit_block(&mut self, block: &'a Block) {
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Variable(decl) => {
+                    self.check_annotated(&decl.name, decl.annotation.as_ref(), decl.value.as_ref());
+                    self.visit_optional(decl.value.as_ref());
+                }
+                StatementKind::Property(property) | StatementKind::Yield(property) => {
+                    let annotation = property.annotation.as_ref();
+                    self.check_annotated(&property.name, annotation, property.value.as_ref());
+                    self.visit_optional(property.value.as_ref());
+                }
+                StatementKind::Container(container) => self.visit_block(&container.body),
+                StatementKind::Generator(generator) => self.visit_block(&generator.body),
+                StatementKind::Assignment(_)
+                | StatementKind::Schema(_)
+                | StatementKind::Enum(_)
+                | StatementKind::TypeAlias(_)
+                | StatementKind::Import(_)
+                | StatementKind::Expression(_) => {}
+            }
+        }
+    }
+
+    fn visit_optional(&mut self, value: Option<&'a Expression>) {
+        let Some(value) = value else {
+            return;
+        };
+        match &value.kind {
+            ExpressionKind::Dictionary(block) => self.visit_block(block),
+            ExpressionKind::Array(items) | ExpressionKind::Tuple(items) => items
+                .iter()
+                .for_each(|item| self.visit_optional(Some(item))),
+            _ => {}
+        }
+    }
+
+    /// Остальные аннотации (и неизвестные имена в них) проверяет
+    /// type_checker.rs, здесь — схемы и перечисления
+    fn check_annotated(
+        &mut self,
+        key: &Name,
+        annotation: Option<&TypeAnnotation>,
+        value: Option<&'a Expression>,
+    ) {
+        let (Some(annotation), Some(value)) = (annotation, value) else {
+            return;
+        };
+        let key = key.as_static().unwrap_or("value");
+        let schema = match self.types.resolve(annotation, &mut Vec::new()) {
+            Some(DeclaredType::Enum(name)) => return self.check_enum(&name, value, key),
+            Some(DeclaredType::Schema(name)) => self.schema(&name),
+            Some(expected) => return self.check_nested(&expected, value, annotation.span, key),
+            None => None,
+        };
+        let Some(schema) = schema else {
+            return;
+        };
+        match &value.kind {
+            ExpressionKind::Dictionary(block) => {
+                self.validate_block(schema, block, annotation.span)
+            }
+            _ => {
+                let found = self.inference.value_type(value);
+                if found.is_known() && found != InferredType::Dictionary {
+                    self.mismatch(&annotation.to_string(), &found, value.span, key);
+                }
+            }
+        }
+    }
+
+    /// Ключи словаря по полям схемы; `at` — место для отсутствующих полей
+    pub fn validate_block(&mut self, schema: &'a ObjectType, block: &'a Block, at: Span) {
+        let name = schema.name.as_static().unwrap_or_default();
+        let fields = self.fields(schema);
+        let mut present = Vec::new();
+        let mut dynamic = false;
+
+        for statement in &block.statements {
+            match &statement.kind {
+                StatementKind::Property(property) => {
+                    let Some(key) = property.name.as_static() else {
+                        dynamic = true;
+                        continue;
+                    };
+                    present.push(key);
+                    match fields.iter().find(|f| f.name.as_static() == Some(key)) {
+                        Some(field) => {
+                            if let Some(value) = &property.value {
+                                self.check_field(field, value, property.name.span);
+                            }
+                        }
+                        None => self.errors.push(SemanticError::new(
+                            SemanticErrorType::UnknownField {
+                                schema: name.to_string(),
+                                field: key.to_string(),
+                            },
+                            property.name.span,
+                            format!("Field '{}' is not declared in '{}'", key, name),
+                        )),
+                    }
+                }
+                StatementKind::Generator(_) | StatementKind::Yield(_) => dynamic = true,
+                _ => {}
+            }
+        }
+        if dynamic {
+            return;
+        }
+
+        for field in fields.iter().filter(|f| f.is_required()) {
+            let field = field.name.as_static().unwrap_or_default();
+            if present.contains(&field) {
+                continue;
+            }
+            self.errors.push(SemanticError::new(
+                SemanticErrorType::MissingField {
+                    schema: name.to_string(),
+                    field: field.to_string(),
+                },
+                at,
+                format!("Missing field '{}' required by '{}'", field, name),
+            ));
+        }
+    }
+
+    /// Тип и допустимые значения поля; вложенный словарь — по схеме поля,
+    /// `at` — ключ, у которого сообщается об отсутствующих полях
+    fn check_field(&mut self, field: &'a FieldType, value: &'a Expression, at: Span) {
+        let name = field.name.as_static().unwrap_or("value");
+        // Неизвестные имена в типе поля сообщены при проверке схемы
+        let expected = field.annotation.as_ref().and_then(|annotation| {
+            let expected = self.types.resolve(annotation, &mut Vec::new())?;
+            Some((annotation, expected))
+        });
+        if let Some((annotation, expected)) = expected {
+            let empty = matches!(
+                self.inference.value_type(value),
+                InferredType::Nil | InferredType::None | InferredType::Undefined
+            );
+            match &expected {
+                _ if field.optional && empty => {}
+                DeclaredType::Enum(enum_name) => self.check_enum(enum_name, value, name),
+                _ => {
+                    let found = self.inference.value_type(value);
+                    if found.is_known() && expected.accepts(&found) == Some(false) {
+                        self.mismatch(&annotation.to_string(), &found, value.span, name);
+                    }
+                    self.check_nested(&expected, value, at, name);
+                }
+            }
+        }
+
+        if field.allowed.is_empty() || self.inference.reference_of(value).is_some() {
+            return;
+        }
+        let Some(literal) = literal(value) else {
+            return;
+        };
+        let allowed: Vec<String> = field.allowed.iter().filter_map(self::literal).collect();
+        if !allowed.contains(&literal) {
+            self.errors.push(SemanticError::new(
+                SemanticErrorType::DisallowedValue {
+                    value: literal.clone(),
+                    allowed: allowed.clone(),
+                },
+                value.span,
+                format!(
+                    "'{}' must be one of {}, found '{}'",
+                    name,
+                    allowed.join(", "),
+                    literal
+                ),
+            ));
+        }
+    }
+
+    /// Словари по схеме и имена членов перечислений внутри значения составного
+    /// типа. Несовпадение типов элементов здесь не сообщается: у полей схемы
+    /// его находит `accepts`, у объявлений — type_checker.rs.
+    fn check_nested(
+        &mut self,
+        expected: &DeclaredType,
+        value: &'a Expression,
+        at: Span,
+        name: &str,
+    ) {
+        match (expected, &value.kind) {
+            (DeclaredType::Schema(schema), ExpressionKind::Dictionary(block)) => {
+                if let Some(schema) = self.schema(schema) {
+                    self.validate_block(schema, block, at);
+                }
+            }
+            (DeclaredType::Enum(enum_name), _) => {
+                self.check_enum_literal(enum_name, value, name);
+            }
+            (DeclaredType::Array(element), ExpressionKind::Array(items)) => {
+                for item in items.iter().filter(|i| i.kind != ExpressionKind::Hole) {
+                    self.check_nested(element, item, item.span, name);
+                }
+            }
+            (DeclaredType::Dictionary(_, element), ExpressionKind::Dictionary(block)) => {
+                for statement in &block.statements {
+                    let StatementKind::Property(property) = &statement.kind else {
+                        continue;
+                    };
+                    if let (Some(key), Some(item)) = (property.name.as_static(), &property.value) {
+                        self.check_nested(element, item, property.name.span, key);
+                    }
+                }
+            }
+            (DeclaredType::Union(members), _) => {
+                let found = self.inference.value_type(value);
+                let candidates: Vec<&DeclaredType> = members
+                    .iter()
+                    .filter(|member| member.accepts(&found) != Some(false))
+                    .collect();
+                if let [member] = candidates[..] {
+                    self.check_nested(member, value, at, name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Значение с типом-перечислением: член перечисления или имя члена
+    fn check_enum(&mut self, expected: &str, value: &'a Expression, name: &str) {
+        if self.check_enum_literal(expected, value, name) {
+            return;
+        }
+        let found = self.inference.value_type(value);
+        if found.is_known() && found != InferredType::Enum(expected.to_string()) {
+            self.mismatch(expected, &found, value.span, name);
+        }
+    }
+
+    /// Слово или строка с именем члена; false — значение записано иначе
+    fn check_enum_literal(&mut self, expected: &str, value: &'a Expression, name: &str) -> bool {
+        let decl = self.enums.get(expected).unwrap();
+        if self.inference.reference_of(value).is_none()
+            && let Some(literal) = literal(value)
+            && matches!(
+                value.kind,
+                ExpressionKind::Identifier(_) | ExpressionKind::String(_)
+            )
+        {
+            if decl.member(&literal).is_some() {
+                return true;
+            }
+            let allowed: Vec<String> = decl.member_names().into_iter().map(String::from).collect();
+            self.errors.push(SemanticError::new(
+                SemanticErrorType::DisallowedValue {
+                    value: literal.clone(),
+                    allowed: allowed.clone(),
+                },
+                value.span,
+                format!(
+                    "'{}' must be one of {}, found '{}'",
+                    name,
+                    allowed.join(", "),
+                    literal
+                ),
+            ));
+            return true;
+        }
+        false
+    }
+
+    fn mismatch(&mut self, expected: &str, found: &InferredType, span: Span, name: &str) {
+        // У величин — с единицей, как в type_checker.rs: `Time (s)`
+        let found = match found {
+            InferredType::Quantity(quantity) => quantity.to_string(),
+            other => other.to_string(),
+        };
+        self.errors.push(SemanticError::new(
+            SemanticErrorType::TypeMismatch {
+                expected: expected.to_string(),
+                found: found.clone(),
+            },
+            span,
+            format!(
+                "'{}' is declared as {} but its value is {}",
+                name, expected, found
+            ),
+        ));
+    }
+}
+
+/// Значение для сравнения со списком `in`: слово, строка без вставок, число
+fn literal(expression: &Expression) -> Option<String> {
+    match &expression.kind {
+        ExpressionKind::Identifier(name) => name.as_static().map(str::to_string),
+        ExpressionKind::String(segments) => segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => Some(text.as_str()),
+                Segment::Interpolation(_) => None,
+            })
+            .collect(),
+        ExpressionKind::Number(value) => Some(value.to_string()),
+        ExpressionKind::Unit { value, suffix } => Some(format!("{}{}", value, suffix)),
+        ExpressionKind::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::semantic::analyzer::Analyzer;
+
+    fn validate(source: &str) -> Vec<SemanticError> {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let analysis = Analyzer::analyze(&program);
+        Validator::validate(&program, &analysis.table, &UnitRegistry::new())
+    }
+
+    const BUTTON: &str = r#"struct Button {
+    label<String>
+    width<Length> = 120px
+    icon?<String>
+    align<String> in [start, center, end] = center
+    padding<Insets> = { top: 2px; bottom: 2px }
+}
+struct Insets {
+    top<Length>
+    bottom<Length>
+}
+"#;
+
+    #[test]
+    fn reports_missing_extra_and_mistyped_fields() {
+        let errors = validate(&format!(
+            "{}ok<Button> {{\n    width: 2s\n    colour: #fff\n    align: middle\n    icon: nil\n}}\nclose<Button> {{\n    label: Close\n    padding {{ top: 1px }}\n}}\n",
+            BUTTON
+        ));
+        let types: Vec<&SemanticErrorType> = errors.iter().map(|e| &e.error_type).collect();
+        assert_eq!(errors.len(), 5, "{:#?}", errors);
+
+        assert_eq!(
+            *types[0],
+            SemanticErrorType::TypeMismatch {
+                expected: "Length".to_string(),
+                found: "Time (s)".to_string(),
+            }
+        );
+        assert_eq!(
+            errors[0].message,
+            "'width' is declared as Length but its value is Time (s)"
+        );
+        assert_eq!(errors[0].span.start.line, 13);
+        assert_eq!(
+            errors[1].message,
+            "Field 'colour' is not declared in 'Button'"
+        );
+        assert_eq!(errors[1].span.start.line, 14);
+        assert_eq!(
+            errors[2].message,
+            "'align' must be one of start, center, end, found 'middle'"
+        );
+        assert_eq!(
+            *types[3],
+            SemanticErrorType::MissingField {
+                schema: "Button".to_string(),
+                field: "label".to_string(),
+            }
+        );
+        assert_eq!(errors[3].span.start.line, 12);
+        assert_eq!(errors[3].span.start.column, 3);
+        assert_eq!(
+            errors[4].message,
+            "Missing field 'bottom' required by 'Insets'"
+        );
+        assert_eq!(errors[4].span.start.line, 20);
+    }
+
+    #[test]
+    fn checks_inheritance_and_interfaces() {
+        let errors = validate(
+            r#"interface Sized {
+    width<Length>
+    height?<Length>
+}
+struct Control {
+    visible<Boolean> = true
+    id<String>
+}
+struct Slider extends Control implements Sized {
+    width<Length> = 10em
+    step<Nmber> = 1
+}
+struct Loop extends Cycle {}
+struct Cycle extends Loop {}
+enum Align { start, end }
+struct Knob extends Align implements Control, Sized, Sized {
+    width<Length>
+    width<Length>
+}
+let volume<Slider> = {
+    id: "volume"
+    step: 2
+}
+let brightness<Slider> = 50%
+"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Unknown type 'Nmber'",
+                "'Slider' does not declare 'height' required by 'Sized'",
+                "Inheritance cycle: Loop -> Cycle -> Loop",
+                "'Knob' cannot extend enum 'Align'",
+                "'Knob' can only implement interfaces, struct 'Control' is not one",
+                "'width' is already declared at 17:5",
+                "'Knob' does not declare 'height' required by 'Sized'",
+                "'brightness' is declared as Slider but its value is Percent (%)",
+            ]
+        );
+        assert_eq!(errors[0].span.start.line, 11);
+        assert_eq!(errors[1].span.start.line, 9);
+        assert_eq!(errors[5].span.start.line, 18);
+    }
+
+    #[test]
+    fn checks_enum_typed_fields() {
+        let errors = validate(
+            r#"enum Align { start, center, end }
+enum Side { left, right }
+struct Label {
+    align<Align> = start
+    side?<Side>
+}
+a<Label> { align: Align.center; side: nil }
+b<Label> { align: "end"; side: Side::left }
+c<Label> { align: middle }
+d<Label> { align: Side.left; side: 4px }
+let e<Align> = Align::end
+let f<Align> = top
+let g<Alignment> = start
+"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'align' must be one of start, center, end, found 'middle'",
+                "'align' is declared as Align but its value is Side",
+                "'side' is declared as Side but its value is Length (px)",
+                "'f' must be one of start, center, end, found 'top'",
+            ],
+        );
+        assert_eq!(errors[0].span.start.line, 9);
+    }
+
+    #[test]
+    fn resolves_aliased_field_types() {
+        let errors = validate(
+            r#"alias Px = Length
+type Side = Align | Px
+enum Align { start, end }
+struct Grid {
+    gap<Px> = 2s
+    sizes<Array<Px>>
+    side<Side> = true
+    cells<Dictionary<String, Px>>
+    cols?<Array<Colum>>
+}
+g<Grid> { sizes: [1px, 2px]; cells: { a: 1px } }
+h<Grid> { sizes: 4px; cells: [] }
+"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Unknown type 'Colum'",
+                "'gap' is declared as Px but its value is Time (s)",
+                "'side' is declared as Side but its value is Boolean",
+                "'sizes' is declared as Array<Px> but its value is Length (px)",
+                "'cells' is declared as Dictionary<String, Px> but its value is Array<Unknown>",
+            ]
+        );
+    }
+
+    #[test]
+    fn validates_schemas_inside_generic_types() {
+        let errors = validate(
+            r#"enum Align { start, end }
+struct Node {
+    name<String>
+    next?<Node | Nil>
+    aligns?<Array<Align>>
+}
+let arr<Array<Node>> = [{ name: 1 }, { name: "ok"; next: { extra: 1 } }]
+let map<Dictionary<String, Node>> = { a: {} }
+let maybe<Node | Nil> = { name: "x"; aligns: [start, middle] }
+let empty<Node | Nil> = nil
+let sides<Array<Align>> = [end, centre]
+"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'name' is declared as String but its value is a plain number",
+                "Field 'extra' is not declared in 'Node'",
+                "Missing field 'name' required by 'Node'",
+                "Missing field 'name' required by 'Node'",
+                "'aligns' must be one of start, end, found 'middle'",
+                "'sides' must be one of start, end, found 'centre'",
+            ]
+        );
+        assert_eq!(errors[3].span.start.line, 8);
+    }
+
+    #[test]
+    fn skips_dynamic_keys_and_unknown_values() {
+        let errors = validate(&format!(
+            "{}let base = 4px\nspread [top, bottom] as side {{\n    yield inset-${{side}} {{}}\n}}\nok<Button> {{\n    label: format(\"OK\")\n    width: base * 2\n    spread [1, 2] as i {{\n        yield item-${{i}}: i\n    }}\n}}\n",
+            BUTTON
+        ));
+        assert!(errors.is_empty(), "{:#?}", errors);
+    }
+}
@@ -0,0 +1,247 @@
+// Видимость объявлений за пределами их области:
+//
+// - `public` (по умолчанию) и `external` — ключ попадает в JSON, объявление
+//   верхнего уровня можно импортировать из другого файла;
+// - `internal` и `protected` — член виден только внутри своего контейнера
+//   или словаря: `app.gap` снаружи `app` — ошибка. В JSON такой ключ попадает,
+//   но импортировать его нельзя: на верхнем уровне это «только этот файл»;
+// - `private` — имя используется в файле как обычно, но ключ не попадает
+//   в JSON и не импортируется.
+//
+// `let` и `const` в JSON не попадают никогда, но импортируются по тем же
+// правилам, что и ключи. Импортированные имена дальше не экспортируются.
+// Импорт файла целиком (`import "base.lacon"`) добавляет в документ его
+// экспортируемые ключи и имен не объявляет.
+
+use super::error::{SemanticError, SemanticErrorType};
+use super::symbol_table::{Symbol, SymbolKind, SymbolTable};
+use crate::ast::expressions::{ExpressionKind, Segment};
+use crate::ast::program::Program;
+use crate::ast::statements::{Block, ImportDecl, Modifiers, StatementKind};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Internal,
+    Private,
+}
+
+impl Visibility {
+    pub fn of(symbol: &Symbol) -> Self {
+        if symbol.modifiers.contains(Modifiers::PRIVATE) {
+            Visibility::Private
+        } else if symbol
+            .modifiers
+            .intersects(Modifiers::INTERNAL | Modifiers::PROTECTED)
+        {
+            Visibility::Internal
+        } else {
+            Visibility::Public
+        }
+    }
+
+    /// Попадает ли ключ с такой видимостью в JSON
+    pub fn is_emitted(self) -> bool {
+        self != Visibility::Private
+    }
+}
+
+/// Имена верхнего уровня файла и их видимость — то, что видят импорты
+#[derive(Debug, Clone, Default)]
+pub struct Exports {
+    names: HashMap<String, Visibility>,
+}
+
+impl Exports {
+    pub fn of(table: &SymbolTable) -> Self {
+        let mut names = HashMap::new();
+        for &id in &table.scope(table.root()).symbols {
+            let symbol = table.symbol(id);
+            if matches!(symbol.kind, SymbolKind::Import | SymbolKind::Implicit) {
+                continue;
+            }
+            // Видимость задает первое объявление: `x = 2` после `private let x`
+            // имя не открывает
+            names
+                .entry(symbol.name.clone())
+                .or_insert_with(|| Visibility::of(symbol));
+        }
+        Self { names }
+    }
+
+    pub fn visibility(&self, name: &str) -> Option<Visibility> {
+        self.names.get(name).copied()
+    }
+
+    pub fn is_exported(&self, name: &str) -> bool {
+        self.visibility(name) == Some(Visibility::Public)
+    }
+}
+
+/// Проверка импортов документа. `resolve` по пути из `import` возвращает
+/// экспорт файла или `None`, если файл не найден.
+pub fn check_imports(
+    program: &Program,
+    mut resolve: impl FnMut(&str) -> Option<Exports>,
+) -> Vec<SemanticError> {
+    let mut errors = Vec::new();
+    visit_block(&program.body, &mut resolve, &mut errors);
+    errors
+}
+
+fn visit_block(
+    block: &Block,
+    resolve: &mut impl FnMut(&str) -> Option<Exports>,
+    errors: &mut Vec<SemanticError>,
+) {
+    for statement in &block.statements {
+        match &statement.kind {
+            StatementKind::Import(import) => check_import(import, resolve, errors),
+            StatementKind::Container(container) => visit_block(&container.body, resolve, errors),
+            _ => {}
+        }
+    }
+}
+
+fn check_import(
+    import: &ImportDecl,
+    resolve: &mut impl FnMut(&str) -> Option<Exports>,
+    errors: &mut Vec<SemanticError>,
+) {
+    // Путь со вставками известен только при вычислении
+    let ExpressionKind::String(segments) = &import.source.kind else {
+        return;
+    };
+    let Some(path) = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => Some(text.as_str()),
+            Segment::Interpolation(_) => None,
+        })
+        .collect::<Option<String>>()
+    else {
+        return;
+    };
+
+    let Some(exports) = resolve(&path) else {
+        errors.push(SemanticError::new(
+            SemanticErrorType::UnresolvedImport(path.clone()),
+            import.source.span,
+            format!("Cannot resolve import '{}'", path),
+        ));
+        return;
+    };
+
+    for name in &import.names {
+        let Some(text) = name.as_static() else {
+            continue;
+        };
+        let message = match exports.visibility(text) {
+            Some(Visibility::Public) => continue,
+            Some(Visibility::Private) => format!("'{}' is private in '{}'", text, path),
+            Some(Visibility::Internal) => format!("'{}' is internal to '{}'", text, path),
+            None => format!("'{}' is not exported by '{}'", text, path),
+        };
+        errors.push(SemanticError::new(
+            SemanticErrorType::NotExported {
+                name: text.to_string(),
+                source: path.clone(),
+            },
+            name.span,
+            message,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::semantic::analyzer::{Analysis, Analyzer};
+    use crate::semantic::type_inference::TypeInference;
+    use crate::shared::unit::registry::UnitRegistry;
+
+    fn analyze(source: &str) -> (Program, Analysis) {
+        let (program, errors) = parse(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let analysis = Analyzer::analyze(&program);
+        (program, analysis)
+    }
+
+    const COLORS: &str = r#"primary: #3366ff
+private let base = #112233
+private secret: base
+internal shade: #000
+let accent = #ff6633
+"#;
+
+    #[test]
+    fn reports_imports_of_hidden_names() {
+        let (_, colors) = analyze(COLORS);
+        let exports = Exports::of(&colors.table);
+        assert!(exports.is_exported("primary"));
+        assert!(exports.is_exported("accent"));
+        assert!(!exports.is_exported("base"));
+
+        let (program, analysis) = analyze(
+            "import primary, accent, secret, shade, missing from \"colors.lacon\"\nimport \"theme.lacon\"\nbutton: primary\naccent = #fff\n",
+        );
+        let messages: Vec<&str> = analysis.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 1, "{:?}", messages);
+        assert!(messages[0].starts_with("Cannot assign to 'accent'"));
+
+        let errors = check_imports(&program, |path| {
+            (path == "colors.lacon").then(|| exports.clone())
+        });
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'secret' is private in 'colors.lacon'",
+                "'shade' is internal to 'colors.lacon'",
+                "'missing' is not exported by 'colors.lacon'",
+                "Cannot resolve import 'theme.lacon'",
+            ]
+        );
+        assert_eq!(errors[0].span.start.column, 25);
+        assert_eq!(errors[3].span.start.line, 2);
+    }
+
+    #[test]
+    fn internal_members_stay_inside_their_container() {
+        let (_, analysis) = analyze(
+            r#"container App
+    internal gap: 4px
+    panel {
+        padding: parent.gap * 2
+        internal inner: 1px
+    }
+    border: this.panel.inner
+outer: App.gap
+"#,
+        );
+        let messages: Vec<&str> = analysis.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "'inner' is internal and not visible outside its container",
+                "'gap' is internal and not visible outside its container",
+            ]
+        );
+        assert_eq!(analysis.errors[0].span.start.line, 7);
+        assert_eq!(analysis.errors[1].span.start.line, 8);
+    }
+
+    #[test]
+    fn private_keys_are_not_emitted() {
+        let (program, analysis) = analyze(COLORS);
+        let registry = UnitRegistry::new();
+        let inference = TypeInference::infer(&program, &analysis.table, &registry);
+        let names: Vec<&str> = inference
+            .root_types(&analysis.table)
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["primary", "shade"]);
+    }
+}
@@ -1 +1 @@
-pub mod unit;
\ No newline at end of file
+pub mod unit;
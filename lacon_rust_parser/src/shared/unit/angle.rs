@@ -0,0 +1,180 @@
+// Градусы-минуты-секунды: `45°30′15″`, `45°30.5′`.
+// Минуты и секунды записываются знаками штриха (′ ″), а не кавычками,
+// чтобы не пересекаться со строками.
+
+pub const MINUTE: char = '′';
+pub const SECOND: char = '″';
+
+/// Разобранная запись после целых градусов: `°30′15″` -> (7, 30, 15)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DmsSuffix {
+    /// Длина записи в символах, начиная с `°`
+    pub len: usize,
+    pub minutes: f64,
+    pub seconds: f64,
+}
+
+impl DmsSuffix {
+    /// Минуты и секунды в пределах [0, 60)
+    pub fn is_valid(&self) -> bool {
+        self.minutes < 60.0 && self.seconds < 60.0
+    }
+
+    /// Десятичные градусы; знак берется от градусов (`-45°30′` == -45.5)
+    pub fn to_degrees(&self, degrees: f64) -> f64 {
+        let fraction = self.minutes / 60.0 + self.seconds / 3600.0;
+        if degrees.is_sign_negative() {
+            degrees - fraction
+        } else {
+            degrees + fraction
+        }
+    }
+}
+
+/// Распознает `°M′` или `°M′S″` в начале `input`.
+/// Дробными могут быть только последние минуты или секунды.
+pub fn scan_dms(input: &[char]) -> Option<DmsSuffix> {
+    if input.first() != Some(&'°') {
+        return None;
+    }
+
+    let mut pos = 1;
+    let (minutes, minutes_fractional) = scan_decimal(input, &mut pos)?;
+    if input.get(pos) != Some(&MINUTE) {
+        return None;
+    }
+    pos += 1;
+
+    let mut seconds = 0.0;
+    let checkpoint = pos;
+    if let Some((value, _)) = scan_decimal(input, &mut pos) {
+        if input.get(pos) == Some(&SECOND) && !minutes_fractional {
+            seconds = value;
+            pos += 1;
+        } else {
+            pos = checkpoint;
+        }
+    }
+
+    Some(DmsSuffix {
+        len: pos,
+        minutes,
+        seconds,
+    })
+}
+
+fn scan_decimal(input: &[char], pos: &mut usize) -> Option<(f64, bool)> {
+    let start = *pos;
+    while input.get(*pos).is_some_and(char::is_ascii_digit) {
+        *pos += 1;
+    }
+    if *pos == start {
+        return None;
+    }
+
+    let mut fractional = false;
+    if input.get(*pos) == Some(&'.') && input.get(*pos + 1).is_some_and(char::is_ascii_digit) {
+        fractional = true;
+        *pos += 1;
+        while input.get(*pos).is_some_and(char::is_ascii_digit) {
+            *pos += 1;
+        }
+    }
+
+    let text: String = input[start..*pos].iter().collect();
+    Some((text.parse().ok()?, fractional))
+}
+
+/// Обратная запись: 45.504166… -> `45°30′15″`.
+/// `seconds_digits` — знаков после запятой у секунд.
+pub fn format_dms(degrees: f64, seconds_digits: usize) -> String {
+    let sign = if degrees < 0.0 { "-" } else { "" };
+    let scale = 10f64.powi(seconds_digits as i32);
+
+    // Округляем сразу общее число секунд, чтобы 59.9999″ перешло в минуты
+    let total = (degrees.abs() * 3600.0 * scale).round() / scale;
+    let whole_degrees = (total / 3600.0).floor();
+    let minutes = ((total - whole_degrees * 3600.0) / 60.0).floor();
+    let seconds = total - whole_degrees * 3600.0 - minutes * 60.0;
+
+    format!(
+        "{}{}°{}{}{:.*}{}",
+        sign, whole_degrees, minutes, MINUTE, seconds_digits, seconds, SECOND
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(text: &str) -> Option<DmsSuffix> {
+        scan_dms(&text.chars().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn scans_dms_suffixes() {
+        let full = scan("°30′15″ + 1").unwrap();
+        assert_eq!((full.len, full.minutes, full.seconds), (7, 30.0, 15.0));
+        assert!((full.to_degrees(45.0) - (45.0 + 30.0 / 60.0 + 15.0 / 3600.0)).abs() < 1e-12);
+        assert_eq!(full.to_degrees(-45.0), -full.to_degrees(45.0));
+
+        let minutes = scan("°30.5′").unwrap();
+        assert_eq!((minutes.len, minutes.minutes), (6, 30.5));
+        // После дробных минут секунды не допускаются
+        assert_eq!(scan("°30.5′15″").unwrap().len, 6);
+
+        assert_eq!(scan("°C"), None);
+        assert_eq!(scan("°30"), None);
+        assert!(!scan("°75′").unwrap().is_valid());
+    }
+
+    #[test]
+    fn formats_dms() {
+        assert_eq!(
+            format_dms(45.0 + 30.0 / 60.0 + 15.0 / 3600.0, 0),
+            "45°30′15″"
+        );
+        assert_eq!(format_dms(-0.5, 1), "-0°30′0.0″");
+        // 59.99999″ округляется до следующей минуты
+        assert_eq!(format_dms(10.0 + 59.999_99 / 3600.0, 0), "10°1′0″");
+    }
+
+    #[test]
+    fn lexes_dms_literals() {
+        use crate::lexer::scanner::Scanner;
+        use crate::lexer::token_type::TokenType;
+        use crate::shared::unit::conversion::convert;
+
+        let mut scanner = Scanner::new("a = 45°30′15″\nb = 90° + 30′ + 2grad\n".to_string());
+        let units: Vec<(TokenType, String, String)> = scanner
+            .scan_tokens()
+            .iter()
+            .filter(|t| t.token_type.is_unit())
+            .map(|t| {
+                let literal = t.literal.clone().unwrap();
+                (
+                    t.token_type.clone(),
+                    literal,
+                    t.get_unit_suffix().to_string(),
+                )
+            })
+            .collect();
+
+        assert_eq!(units[0].0, TokenType::UnitDms);
+        assert_eq!(units[0].2, "°");
+        assert!(
+            (units[0].1.parse::<f64>().unwrap() - (45.0 + 30.0 / 60.0 + 15.0 / 3600.0)).abs()
+                < 1e-12
+        );
+        let suffixes: Vec<&str> = units[1..].iter().map(|u| u.2.as_str()).collect();
+        assert_eq!(suffixes, ["°", "′", "grad"]);
+        assert!(scanner.errors.is_empty());
+
+        assert!((convert(1.0, "turn", "grad").unwrap() - 400.0).abs() < 1e-9);
+        assert!((convert(90.0, "deg", "rad").unwrap() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+        let mut invalid = Scanner::new("c = 45°75′\n".to_string());
+        invalid.scan_tokens();
+        assert_eq!(invalid.errors.len(), 1);
+    }
+}
@@ -0,0 +1,550 @@
+use super::conversion::ConversionError;
+use super::definition::{PrefixGroup, UnitDef};
+use super::dimensions::{Dimension, DimensionVector};
+use super::props::UnitProps;
+use super::registry::UnitRegistry;
+use std::borrow::Cow;
+use std::fmt;
+
+// Грамматика суффикса:
+//   expr  := term (('·' | '*' | '/') term)*     — слева направо, как в `J/kg/K`
+//   term  := atom power?
+//   atom  := symbol | '(' expr ')'
+//   power := digits | '^' '-'? digits | '⁻'? superscripts
+// symbol — любая зарегистрированная единица, в том числе с приставкой (kg, kΩ).
+// Шкалы со смещением (°C) входят в произведение как разности: J/(kg·°C) == J/(kg·K).
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompoundError {
+    UnknownUnit(String),
+    /// Логарифмические шкалы (°Da) нельзя умножать и делить
+    NonLinearUnit(String),
+    /// Единица вёрстки (em, px) не сводится к СИ без контекста отрисовки
+    LayoutUnit(String),
+    UnexpectedChar {
+        position: usize,
+        found: char,
+    },
+    UnexpectedEnd,
+    InvalidPower(String),
+}
+
+impl fmt::Display for CompoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompoundError::UnknownUnit(s) => write!(f, "Unknown unit '{}'", s),
+            CompoundError::NonLinearUnit(s) => {
+                write!(f, "Unit '{}' cannot be part of a compound unit", s)
+            }
+            CompoundError::LayoutUnit(s) => {
+                write!(f, "Layout unit '{}' cannot be part of a compound unit", s)
+            }
+            CompoundError::UnexpectedChar { position, found } => {
+                write!(
+                    f,
+                    "Unexpected '{}' at {} in unit expression",
+                    found, position
+                )
+            }
+            CompoundError::UnexpectedEnd => write!(f, "Unexpected end of unit expression"),
+            CompoundError::InvalidPower(s) => write!(f, "Invalid unit power '{}'", s),
+        }
+    }
+}
+
+/// Составная единица, сведенная к вектору размерности и множителю
+/// к когерентным единицам СИ (kg, m, s, A, K, mol, cd; B для данных)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompoundUnit {
+    /// Исходная запись, н-р `W/(m2·K)`
+    pub symbol: String,
+    pub dimension: DimensionVector,
+    pub scale: f64,
+}
+
+impl CompoundUnit {
+    /// Разбор всей строки целиком
+    pub fn parse(expr: &str, registry: &UnitRegistry) -> Result<Self, CompoundError> {
+        let chars: Vec<char> = expr.chars().collect();
+        let mut parser = Parser {
+            chars: &chars,
+            pos: 0,
+            depth: 0,
+            registry,
+            strict: true,
+        };
+
+        let (dimension, scale) = parser.expr()?;
+        if let Some(&found) = chars.get(parser.pos) {
+            return Err(CompoundError::UnexpectedChar {
+                position: parser.pos,
+                found,
+            });
+        }
+
+        Ok(Self {
+            symbol: expr.to_string(),
+            dimension,
+            scale,
+        })
+    }
+
+    /// Именованная размерность (Force для `kg·m/s2`), если такая есть
+    pub fn named_dimension(&self) -> Option<Dimension> {
+        Dimension::from_vector(self.dimension)
+    }
+
+    /// Обычное определение единицы для выражений с именованной размерностью
+    pub fn to_unit_def(&self) -> Option<UnitDef> {
+        let dimension = self.named_dimension()?;
+        Some(UnitDef {
+            symbol: Cow::Owned(self.symbol.clone()),
+            dimension,
+            parts: None,
+            numerator_group: PrefixGroup::None,
+            denominator_group: PrefixGroup::None,
+            props: UnitProps {
+                scale: self.scale / dimension.coherent_factor(),
+                ..UnitProps::DEFAULT
+            },
+        })
+    }
+
+    /// Обычная единица как составная: `kΩ` -> (вектор Ω, 1e3).
+    /// Шкалы со смещением входят как разности, нелинейные и единицы вёрстки — нет.
+    pub fn from_unit(def: &UnitDef) -> Result<Self, CompoundError> {
+        if !def.props.basis.is_absolute() {
+            return Err(CompoundError::LayoutUnit(def.symbol.to_string()));
+        }
+        let scale = def
+            .normalize_delta(1.0)
+            .ok_or_else(|| CompoundError::NonLinearUnit(def.symbol.to_string()))?;
+        Ok(Self {
+            symbol: def.symbol.to_string(),
+            dimension: def.dimension.vector(),
+            scale: scale * def.dimension.coherent_factor(),
+        })
+    }
+
+    /// Произведение (`m·N`) или частное (`J/s`) двух единиц
+    pub fn combine(&self, other: &CompoundUnit, divide: bool) -> Result<Self, CompoundError> {
+        let wrap = |symbol: &str| {
+            if symbol.contains(['/', '·']) {
+                format!("({})", symbol)
+            } else {
+                symbol.to_string()
+            }
+        };
+        let (symbol, dimension, scale) = if divide {
+            (
+                format!("{}/{}", self.symbol, wrap(&other.symbol)),
+                self.dimension.checked_div(other.dimension),
+                self.scale / other.scale,
+            )
+        } else {
+            (
+                format!("{}·{}", self.symbol, wrap(&other.symbol)),
+                self.dimension.checked_mul(other.dimension),
+                self.scale * other.scale,
+            )
+        };
+        let dimension = dimension.ok_or_else(|| CompoundError::InvalidPower(symbol.clone()))?;
+        Ok(Self {
+            symbol,
+            dimension,
+            scale,
+        })
+    }
+
+    /// Целая степень единицы: `(m/s)^2`
+    pub fn powi(&self, exponent: i8) -> Result<Self, CompoundError> {
+        let symbol = if self.symbol.contains(['/', '·']) {
+            format!("({})^{}", self.symbol, exponent)
+        } else {
+            format!("{}^{}", self.symbol, exponent)
+        };
+        let dimension = self
+            .dimension
+            .checked_powi(exponent)
+            .ok_or_else(|| CompoundError::InvalidPower(symbol.clone()))?;
+        Ok(Self {
+            symbol,
+            dimension,
+            scale: self.scale.powi(exponent as i32),
+        })
+    }
+
+    pub fn convert_to(&self, value: f64, target: &CompoundUnit) -> Result<f64, ConversionError> {
+        if self.dimension != target.dimension {
+            return Err(ConversionError::VectorMismatch {
+                from: self.dimension,
+                to: target.dimension,
+            });
+        }
+
+        Ok(value * self.scale / target.scale)
+    }
+}
+
+/// Длина самого длинного корректного составного суффикса в начале `input`.
+/// Оператор, за которым не следует единица (`10m/2`, `3m·x`), в суффикс не входит.
+/// `*` — умножение значений (`3m*t`), в суффиксе он допустим только в скобках: `W/(m*K)`.
+pub fn match_len(input: &[char], registry: &UnitRegistry) -> usize {
+    // `10(` — не единица
+    if input.first() == Some(&'(') {
+        return 0;
+    }
+
+    let mut parser = Parser {
+        chars: input,
+        pos: 0,
+        depth: 0,
+        registry,
+        strict: false,
+    };
+    match parser.expr() {
+        Ok(_) => parser.pos,
+        Err(_) => 0,
+    }
+}
+
+pub fn is_symbol_char(c: char) -> bool {
+    // `_` — явные варианты систем мер: gal_us, ton_uk
+    c.is_alphabetic() || matches!(c, '°' | '%' | '‰' | '_')
+}
+
+fn superscript_digit(c: char) -> Option<u32> {
+    match c {
+        '⁰' => Some(0),
+        '¹' => Some(1),
+        '²' => Some(2),
+        '³' => Some(3),
+        '⁴'..='⁹' => Some(c as u32 - '⁰' as u32),
+        _ => None,
+    }
+}
+
+struct Parser<'a> {
+    chars: &'a [char],
+    pos: usize,
+    /// Глубина вложенности скобок
+    depth: usize,
+    registry: &'a UnitRegistry,
+    /// В нестрогом режиме (сканер) разбор останавливается перед ошибкой
+    strict: bool,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn expr(&mut self) -> Result<(DimensionVector, f64), CompoundError> {
+        let start = self.pos;
+        let (mut dimension, mut scale) = self.term()?;
+
+        while let Some(op @ ('·' | '⋅' | '*' | '/')) = self.peek() {
+            if op == '*' && !self.strict && self.depth == 0 {
+                break;
+            }
+            let save = self.pos;
+            self.pos += 1;
+
+            let (d, s) = match self.term() {
+                Ok(term) => term,
+                Err(_) if !self.strict => {
+                    self.pos = save;
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let combined = if op == '/' {
+                dimension.checked_div(d)
+            } else {
+                dimension.checked_mul(d)
+            };
+            // Степень, не помещающаяся в i8 (`m^100·m^100`), завершает суффикс
+            dimension = match combined {
+                Some(combined) => combined,
+                None if !self.strict => {
+                    self.pos = save;
+                    break;
+                }
+                None => {
+                    let text = self.chars[start..self.pos].iter().collect();
+                    return Err(CompoundError::InvalidPower(text));
+                }
+            };
+            if op == '/' {
+                scale /= s;
+            } else {
+                scale *= s;
+            }
+        }
+
+        Ok((dimension, scale))
+    }
+
+    fn term(&mut self) -> Result<(DimensionVector, f64), CompoundError> {
+        let start = self.pos;
+        let (dimension, scale) = self.atom()?;
+        let power = self.power()?;
+        let dimension = dimension.checked_powi(power).ok_or_else(|| {
+            CompoundError::InvalidPower(self.chars[start..self.pos].iter().collect())
+        })?;
+        Ok((dimension, scale.powi(power as i32)))
+    }
+
+    fn atom(&mut self) -> Result<(DimensionVector, f64), CompoundError> {
+        match self.peek() {
+            None => Err(CompoundError::UnexpectedEnd),
+            Some('(') => {
+                self.pos += 1;
+                self.depth += 1;
+                let inner = self.expr();
+                self.depth -= 1;
+                let inner = inner?;
+                match self.peek() {
+                    Some(')') => {
+                        self.pos += 1;
+                        Ok(inner)
+                    }
+                    Some(found) => Err(CompoundError::UnexpectedChar {
+                        position: self.pos,
+                        found,
+                    }),
+                    None => Err(CompoundError::UnexpectedEnd),
+                }
+            }
+            Some(c) if is_symbol_char(c) => {
+                let start = self.pos;
+                while self.peek().is_some_and(is_symbol_char) {
+                    self.pos += 1;
+                }
+                let symbol: String = self.chars[start..self.pos].iter().collect();
+
+                let (factor, def) = self
+                    .registry
+                    .resolve(&symbol)
+                    .ok_or_else(|| CompoundError::UnknownUnit(symbol.clone()))?;
+                // Сканер все равно примет `10px/s` одним токеном
+                if self.strict && !def.props.basis.is_absolute() {
+                    return Err(CompoundError::LayoutUnit(symbol));
+                }
+                let scale = def
+                    .normalize_delta(factor)
+                    .ok_or_else(|| CompoundError::NonLinearUnit(symbol.clone()))?;
+
+                Ok((
+                    def.dimension.vector(),
+                    scale * def.dimension.coherent_factor(),
+                ))
+            }
+            Some(found) => Err(CompoundError::UnexpectedChar {
+                position: self.pos,
+                found,
+            }),
+        }
+    }
+
+    /// Целая степень; без степени — 1
+    fn power(&mut self) -> Result<i8, CompoundError> {
+        let start = self.pos;
+        let mut negative = false;
+        let mut digits = String::new();
+
+        match self.peek() {
+            Some('^') => {
+                self.pos += 1;
+                if self.peek() == Some('-') {
+                    negative = true;
+                    self.pos += 1;
+                }
+                while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+                    digits.push(c);
+                    self.pos += 1;
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+                    digits.push(c);
+                    self.pos += 1;
+                }
+            }
+            Some(c) if c == '⁻' || superscript_digit(c).is_some() => {
+                if c == '⁻' {
+                    negative = true;
+                    self.pos += 1;
+                }
+                while let Some(d) = self.peek().and_then(superscript_digit) {
+                    digits.push(char::from_digit(d, 10).unwrap());
+                    self.pos += 1;
+                }
+            }
+            _ => return Ok(1),
+        }
+
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match digits.parse::<i8>() {
+            Ok(0) | Err(_) if self.strict => Err(CompoundError::InvalidPower(text)),
+            Ok(0) | Err(_) => {
+                // `m^x`: степень не относится к единице
+                self.pos = start;
+                Ok(1)
+            }
+            Ok(power) => Ok(if negative { -power } else { power }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(expr: &str) -> CompoundUnit {
+        CompoundUnit::parse(expr, &UnitRegistry::new()).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual / expected - 1.0).abs() < 1e-12,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn resolves_dimension_and_scale() {
+        let force = parse("kg·m/s2");
+        assert_eq!(force.named_dimension(), Some(Dimension::Force));
+        assert_close(force.scale, 1.0);
+
+        assert_eq!(parse("N*m").dimension, Dimension::Energy.vector());
+        assert_eq!(parse("m^2").named_dimension(), Some(Dimension::Area));
+        assert_eq!(parse("km⁻¹").dimension, parse("km^-1").dimension);
+        assert_close(parse("km⁻¹").scale, parse("km^-1").scale);
+        assert_close(parse("km⁻¹").scale, 1e-3);
+
+        // Теплопроводность не имеет имени, но вектор известен
+        let conductivity = parse("W/(m·K)");
+        assert_eq!(conductivity.named_dimension(), None);
+        assert_eq!(conductivity.dimension.to_string(), "m·kg·s⁻³·K⁻¹");
+
+        // Удельная теплоемкость: шкала Цельсия входит как разность
+        let heat = parse("kJ/(kg·°C)");
+        assert_eq!(heat.dimension, parse("J/kg/K").dimension);
+        assert_close(heat.convert_to(1.0, &parse("J/(kg·K)")).unwrap(), 1000.0);
+    }
+
+    #[test]
+    fn compound_units_convert_as_unit_defs() {
+        let def = parse("g/cm3").to_unit_def().unwrap();
+        let builtin = UnitRegistry::new().find("g/m3").cloned().unwrap();
+        assert_eq!(def.dimension, Dimension::Density);
+        assert_close(def.convert_to(1.0, &builtin).unwrap(), 1e6);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        let registry = UnitRegistry::new();
+        for (expr, err) in [
+            ("kg·", CompoundError::UnexpectedEnd),
+            ("m/x", CompoundError::UnknownUnit("x".into())),
+            ("m^0", CompoundError::InvalidPower("^0".into())),
+            ("°Da/s", CompoundError::NonLinearUnit("°Da".into())),
+        ] {
+            assert_eq!(CompoundUnit::parse(expr, &registry).unwrap_err(), err);
+        }
+        assert!(matches!(
+            CompoundUnit::parse("W/(m2·K", &registry),
+            Err(CompoundError::UnexpectedEnd)
+        ));
+    }
+
+    #[test]
+    fn lexes_compound_suffixes() {
+        use crate::lexer::scanner::Scanner;
+
+        let source = "k = 0.6W/(m*K)\ntorque = 12N·m\nside = 4m² / 2m^2\nhalf = 10m/2\n";
+        let mut scanner = Scanner::new(source.to_string());
+        let units: Vec<_> = scanner
+            .scan_tokens()
+            .iter()
+            .filter_map(|t| {
+                t.get_compound_unit()
+                    .map(|u| (t.get_unit_suffix().to_string(), u))
+            })
+            .collect();
+
+        let suffixes: Vec<&str> = units.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(suffixes, ["W/(m*K)", "N·m", "m²", "m^2", "m"]);
+        assert_eq!(units[1].1.named_dimension(), Some(Dimension::Energy));
+    }
+
+    #[test]
+    fn falls_back_to_simple_unit_at_word_boundary() {
+        use crate::lexer::scanner::Scanner;
+        use crate::lexer::token_type::TokenType;
+
+        // `kg·g` не проходит границу слова перед `0`, остается `kg`
+        let mut scanner = Scanner::new("w = 70kg·g0\n".to_string());
+        let tokens: Vec<_> = scanner
+            .scan_tokens()
+            .iter()
+            .filter(|t| matches!(t.token_type, TokenType::Unit | TokenType::Identifier))
+            .map(|t| (t.token_type.clone(), t.lexeme.clone()))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (TokenType::Identifier, "w".to_string()),
+                (TokenType::Unit, "70kg".to_string()),
+                (TokenType::Identifier, "g0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scanner_prefix_length() {
+        let registry = UnitRegistry::new();
+        let len = |s: &str| match_len(&s.chars().collect::<Vec<_>>(), &registry);
+
+        assert_eq!(len("W/(m2·K) + 1"), 8);
+        assert_eq!(len("m/2"), 1);
+        assert_eq!(len("m*x"), 1);
+        assert_eq!(len("m^x"), 1);
+        assert_eq!(len("N*m)"), 1);
+        assert_eq!(len("W/(m*K)"), 7);
+        assert_eq!(len("(m)"), 0);
+    }
+
+    #[test]
+    fn power_overflow_is_an_error() {
+        use crate::lexer::scanner::Scanner;
+
+        let registry = UnitRegistry::new();
+        for expr in ["m^100·m^100", "m^100/m^-100", "(m^100)^2"] {
+            assert!(matches!(
+                CompoundUnit::parse(expr, &registry),
+                Err(CompoundError::InvalidPower(_))
+            ));
+        }
+        let len = |s: &str| match_len(&s.chars().collect::<Vec<_>>(), &registry);
+        assert_eq!(len("m^100·m^100"), 5);
+
+        let a = parse("m^100");
+        assert!(a.combine(&a, false).is_err());
+        assert!(a.powi(2).is_err());
+
+        let mut scanner = Scanner::new("a = 1m^100·m^100\n".to_string());
+        let suffixes: Vec<String> = scanner
+            .scan_tokens()
+            .iter()
+            .filter(|t| t.get_compound_unit().is_some())
+            .map(|t| t.get_unit_suffix().to_string())
+            .collect();
+        assert_eq!(suffixes, ["m^100"]);
+    }
+}
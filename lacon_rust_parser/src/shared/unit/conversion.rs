@@ -0,0 +1,311 @@
+use super::definition::UnitDef;
+use super::dimensions::{Dimension, DimensionVector};
+use super::layout::LayoutContext;
+use super::units::find_unit;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionError {
+    /// Символ не зарегистрирован в `UNITS`
+    UnknownUnit(String),
+    /// Единицы принадлежат разным размерностям (н-р, °C -> m)
+    DimensionMismatch { from: Dimension, to: Dimension },
+    /// То же для составных единиц без именованной размерности
+    VectorMismatch {
+        from: DimensionVector,
+        to: DimensionVector,
+    },
+    /// Разность на нелинейной шкале (°Da, dB, Np) не имеет смысла
+    NonLinearDelta(String),
+    /// Единице вёрстки (em, px, vw) не хватает параметра `LayoutContext`
+    MissingContext { unit: String, missing: &'static str },
+    /// Календарная единица (month, year) и точная (s, day) без соглашения
+    /// о длине месяца; см. `duration::convert_time`
+    CalendarConvention { from: String, to: String },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::UnknownUnit(symbol) => write!(f, "Unknown unit '{}'", symbol),
+            ConversionError::DimensionMismatch { from, to } => {
+                write!(f, "Cannot convert {:?} to {:?}", from, to)
+            }
+            ConversionError::VectorMismatch { from, to } => {
+                write!(f, "Cannot convert {} to {}", from, to)
+            }
+            ConversionError::NonLinearDelta(symbol) => {
+                write!(f, "Difference is undefined on nonlinear '{}' scale", symbol)
+            }
+            ConversionError::MissingContext { unit, missing } => {
+                write!(
+                    f,
+                    "Converting '{}' requires {} in the layout context",
+                    unit, missing
+                )
+            }
+            ConversionError::CalendarConvention { from, to } => {
+                write!(
+                    f,
+                    "Converting '{}' to '{}' requires a calendar convention",
+                    from, to
+                )
+            }
+        }
+    }
+}
+
+impl UnitDef {
+    /// Переводит значение в другую единицу той же размерности через базовую
+    /// (для температур — через Кельвин).
+    /// Единицы вёрстки разных основ (em -> px) требуют `convert_in`.
+    pub fn convert_to(&self, value: f64, target: &UnitDef) -> Result<f64, ConversionError> {
+        self.convert_in(value, target, &LayoutContext::default())
+    }
+
+    pub(super) fn convert_same_basis(
+        &self,
+        value: f64,
+        target: &UnitDef,
+    ) -> Result<f64, ConversionError> {
+        if self.dimension != target.dimension {
+            return Err(ConversionError::DimensionMismatch {
+                from: self.dimension,
+                to: target.dimension,
+            });
+        }
+        self.check_calendar(target)?;
+
+        Ok(target.denormalize(self.normalize(value)))
+    }
+
+    /// Месяц и год не переводятся в точные единицы молча по среднему году
+    fn check_calendar(&self, target: &UnitDef) -> Result<(), ConversionError> {
+        if self.is_calendar() != target.is_calendar() {
+            return Err(ConversionError::CalendarConvention {
+                from: self.symbol.to_string(),
+                to: target.symbol.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Переводит разность значений: учитывается только масштаб шкал,
+    /// поэтому Δ5°C == Δ9°F, а не 41°F.
+    pub fn convert_delta_to(&self, value: f64, target: &UnitDef) -> Result<f64, ConversionError> {
+        if self.dimension != target.dimension {
+            return Err(ConversionError::DimensionMismatch {
+                from: self.dimension,
+                to: target.dimension,
+            });
+        }
+        self.check_calendar(target)?;
+        // У единиц вёрстки нет смещения: разность переводится как значение
+        if self.props.basis != target.props.basis {
+            return self.convert_in(value, target, &LayoutContext::default());
+        }
+
+        let base = self
+            .normalize_delta(value)
+            .ok_or_else(|| ConversionError::NonLinearDelta(self.symbol.to_string()))?;
+        target
+            .denormalize_delta(base)
+            .ok_or_else(|| ConversionError::NonLinearDelta(target.symbol.to_string()))
+    }
+}
+
+/// Перевод по символам: `convert(25.0, "°C", "°F") == Ok(77.0)`
+pub fn convert(value: f64, from: &str, to: &str) -> Result<f64, ConversionError> {
+    let from_unit = find_unit(from).ok_or_else(|| ConversionError::UnknownUnit(from.into()))?;
+    let to_unit = find_unit(to).ok_or_else(|| ConversionError::UnknownUnit(to.into()))?;
+
+    from_unit.convert_to(value, to_unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::scanner::Scanner;
+
+    const EPSILON: f64 = 1e-9;
+
+    // (шкала, значение на шкале, ожидаемое значение в Кельвинах)
+    // Опорные точки — замерзание (273.15K) и кипение (373.15K) воды,
+    // для Веджвуда — его собственные реперы (0°W = 269°C).
+    const TEMPERATURE_TABLE: &[(&str, f64, f64)] = &[
+        ("K", 0.0, 0.0),
+        ("K", 273.15, 273.15),
+        ("C", 0.0, 273.15),
+        ("C", 100.0, 373.15),
+        ("C", -273.15, 0.0),
+        ("F", 32.0, 273.15),
+        ("F", 212.0, 373.15),
+        ("F", -459.67, 0.0),
+        ("Ra", 491.67, 273.15),
+        ("Ra", 671.67, 373.15),
+        ("N", 0.0, 273.15),
+        ("N", 33.0, 373.15),
+        ("D", 150.0, 273.15),
+        ("D", 0.0, 373.15),
+        ("D", 559.725, 0.0),
+        ("Re", 0.0, 273.15),
+        ("Re", 80.0, 373.15),
+        ("Ro", 7.5, 273.15),
+        ("Ro", 60.0, 373.15),
+        ("L", 253.0, 273.15),
+        ("L", 353.0, 373.15),
+        ("W", 0.0, 542.15),
+        ("W", 1.0, 567.007191),
+        ("Da", 0.0, 273.15),
+        ("Da", 100.0, 373.15),
+    ];
+
+    fn symbols_for(scale: &str) -> Vec<String> {
+        if scale == "K" {
+            vec!["K".to_string()]
+        } else {
+            vec![format!("deg{}", scale), format!("\u{00B0}{}", scale)]
+        }
+    }
+
+    #[test]
+    fn temperature_scales_table() {
+        let kelvin = find_unit("K").unwrap();
+
+        for &(scale, value, expected_k) in TEMPERATURE_TABLE {
+            for symbol in symbols_for(scale) {
+                let unit =
+                    find_unit(&symbol).unwrap_or_else(|| panic!("Unit {} not found", symbol));
+
+                let k = unit.normalize(value);
+                assert!(
+                    (k - expected_k).abs() < EPSILON,
+                    "{}{} -> {}K, expected {}K",
+                    value,
+                    symbol,
+                    k,
+                    expected_k
+                );
+
+                let back = kelvin.convert_to(expected_k, unit).unwrap();
+                assert!(
+                    (back - value).abs() < EPSILON,
+                    "{}K -> {}{}, expected {}{}",
+                    expected_k,
+                    back,
+                    symbol,
+                    value,
+                    symbol
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn temperature_round_trip_through_kelvin() {
+        let scales = ["C", "F", "Ra", "N", "D", "Re", "Ro", "L", "W", "Da"];
+
+        for from in scales {
+            for to in scales {
+                let from_sym = format!("\u{00B0}{}", from);
+                let to_sym = format!("\u{00B0}{}", to);
+
+                let there = convert(25.0, &from_sym, &to_sym).unwrap();
+                let back = convert(there, &to_sym, &from_sym).unwrap();
+                assert!(
+                    (back - 25.0).abs() < EPSILON,
+                    "25{} -> {}{} -> {}{}",
+                    from_sym,
+                    there,
+                    to_sym,
+                    back,
+                    from_sym
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn temperature_from_lexed_token() {
+        let mut scanner = Scanner::new("25°C 77degF".to_string());
+        let tokens = scanner.scan_tokens();
+        let units: Vec<_> = tokens.iter().filter(|t| t.token_type.is_unit()).collect();
+
+        assert_eq!(units.len(), 2);
+        for token in units {
+            let def = token.get_unit_def().expect("temperature unit");
+            let value: f64 = token.literal.as_ref().unwrap().parse().unwrap();
+            let k = def.convert_to(value, find_unit("K").unwrap()).unwrap();
+            assert!((k - 298.15).abs() < EPSILON, "{} -> {}K", token.lexeme, k);
+        }
+    }
+
+    #[test]
+    fn mismatched_dimensions() {
+        assert_eq!(
+            convert(1.0, "°C", "m"),
+            Err(ConversionError::DimensionMismatch {
+                from: Dimension::Temperature,
+                to: Dimension::Length,
+            })
+        );
+        assert!(matches!(
+            convert(1.0, "°X", "K"),
+            Err(ConversionError::UnknownUnit(_))
+        ));
+    }
+
+    #[test]
+    fn logarithmic_units() {
+        let close = |a: f64, b: f64| (a - b).abs() < EPSILON;
+
+        assert!(close(convert(30.0, "dBm", "W").unwrap(), 1.0));
+        assert!(close(convert(0.001, "W", "dBm").unwrap(), 0.0));
+        assert!(close(convert(0.0, "dBW", "dBm").unwrap(), 30.0));
+        // Полевые величины: 20·lg, а не 10·lg
+        assert!(close(convert(10.0, "V", "dBV").unwrap(), 20.0));
+        assert!(close(convert(0.0, "dBu", "V").unwrap(), 0.6f64.sqrt()));
+        // 1 Np = 20/ln(10) dB
+        assert!(close(convert(1.0, "Np", "dB").unwrap(), 20.0 / 10f64.ln()));
+        assert!(close(convert(1.0, "bel", "dB").unwrap(), 10.0));
+
+        // Усиления переводятся в линейные отношения и обратно
+        let gain = convert(3.0, "dB", "ratio").unwrap();
+        assert!(close(gain, 10f64.powf(0.3)));
+        assert!(close(convert(gain, "ratio", "dB").unwrap(), 3.0));
+        assert!(close(convert(3.0, "dB", "%").unwrap(), 100.0 * gain));
+        assert!(close(convert(100.0, "%", "dB").unwrap(), 0.0));
+        assert!(close(
+            convert(1.0, "Np", "ratio").unwrap(),
+            1f64.exp().powi(2)
+        ));
+
+        assert!(matches!(
+            convert(1.0, "dBm", "dBV"),
+            Err(ConversionError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn customary_units() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12 * b.abs().max(1.0);
+
+        assert!(close(convert(1.0, "mi", "yd").unwrap(), 1760.0));
+        assert!(close(convert(1.0, "lb", "oz").unwrap(), 16.0));
+        assert!(close(convert(1.0, "lb", "g").unwrap(), 453.59237));
+        assert!(close(convert(1.0, "ton_uk", "lb").unwrap(), 2240.0));
+        assert!(close(convert(1.0, "gal_us", "L").unwrap(), 3.785411784));
+        assert!(close(convert(1.0, "gal_uk", "pint_uk").unwrap(), 8.0));
+        assert!(close(convert(1.0, "gal_us", "floz_us").unwrap(), 128.0));
+        assert!(close(convert(1.0, "psi", "Pa").unwrap(), 6894.757293168361));
+        assert!(close(
+            convert(1.0, "hp_mech", "W").unwrap(),
+            745.6998715822702
+        ));
+        assert!(close(convert(60.0, "mph", "mi/h").unwrap(), 60.0));
+
+        // Неоднозначный символ равен варианту по умолчанию
+        assert_eq!(convert(1.0, "gal", "gal_us").unwrap(), 1.0);
+        assert_eq!(convert(1.0, "ton", "ton_us").unwrap(), 1.0);
+    }
+}
@@ -1,5 +1,6 @@
 use super::dimensions::Dimension;
 use super::props::{CalcMode, UnitProps};
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -21,11 +22,23 @@ impl PrefixGroup {
             PrefixGroup::Digital => "",
         }
     }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "SI" => Some(PrefixGroup::SI),
+            "Thermal" => Some(PrefixGroup::Thermal),
+            "Metric" => Some(PrefixGroup::Metric),
+            "None" => Some(PrefixGroup::None),
+            "Digital" => Some(PrefixGroup::Digital),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct UnitDef {
-    pub symbol: &'static str,
+    /// Встроенные единицы ссылаются на статику, объявленные в документе — владеют строкой
+    pub symbol: Cow<'static, str>,
     pub dimension: Dimension,
     pub parts: Option<(&'static str, &'static str)>,
     pub props: UnitProps,
@@ -43,7 +56,7 @@ impl UnitDef {
         props: UnitProps,
     ) -> Self {
         Self {
-            symbol,
+            symbol: Cow::Borrowed(symbol),
             dimension,
             parts,
             numerator_group: n_grp,
@@ -69,6 +82,10 @@ impl UnitDef {
                 let t1 = props.scale;
                 t0 * (t1 / t0).powf(value / 100.0)
             }
+            CalcMode::Logarithmic => {
+                // L = c * ln((X / X0)^k)  =>  X = X0 * e^(L / (c * k))
+                props.scale * (value / (props.offset * props.exponent)).exp()
+            }
         }
     }
 
@@ -83,17 +100,61 @@ impl UnitDef {
                 let t1 = props.scale;
                 100.0 * (base_value / t0).log(t1 / t0)
             }
+            CalcMode::Logarithmic => {
+                props.offset * props.exponent * (base_value / props.scale).ln()
+            }
+        }
+    }
+}
+
+impl UnitDef {
+    /// Аффинная шкала: ноль шкалы не совпадает с нулем базовой единицы
+    /// (°C, °F, °Da...). Для таких шкал абсолютное значение и разность
+    /// переводятся по-разному.
+    pub fn is_affine(&self) -> bool {
+        let props = self.get_props();
+        props.mode != CalcMode::Linear || props.offset != 0.0
+    }
+
+    /// Логарифмический уровень (dB, dBm, Np)
+    pub fn is_logarithmic(&self) -> bool {
+        self.get_props().mode == CalcMode::Logarithmic
+    }
+
+    /// Календарная единица (`month`, `year`): длина зависит от даты,
+    /// масштаб в реестре — лишь среднее григорианское значение
+    pub fn is_calendar(&self) -> bool {
+        self.dimension == Dimension::Time && matches!(self.symbol.as_ref(), "month" | "year")
+    }
+
+    /// Перевод разности (Δ) в базовую единицу — только масштаб, без смещения.
+    /// Для экспоненциальных и логарифмических шкал разность не определена.
+    pub fn normalize_delta(&self, value: f64) -> Option<f64> {
+        let props = self.get_props();
+
+        match props.mode {
+            CalcMode::Linear => Some(value * props.scale),
+            CalcMode::Exponential | CalcMode::Logarithmic => None,
+        }
+    }
+
+    pub fn denormalize_delta(&self, base_value: f64) -> Option<f64> {
+        let props = self.get_props();
+
+        match props.mode {
+            CalcMode::Linear => Some(base_value / props.scale),
+            CalcMode::Exponential | CalcMode::Logarithmic => None,
         }
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct UnitNode {
     pub is_final: bool,
     pub children: BTreeMap<char, UnitNode>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct UnitTree {
     pub root: UnitNode,
 }
@@ -135,36 +196,39 @@ impl UnitTree {
     }
 }
 
-#[macro_export]
-macro_rules! units_array {
-    // Обработка @multi
-    (
-        [$($accumulated:tt)*]
-        @multi [$($symbol:expr),+] $suffix:expr, $dim:expr, ($ng:expr, $dg:expr), $props:expr,
-        $($rest:tt)*
-    ) => {
-        units_array![
-            [$($accumulated)* $(UnitDef::new(concat!($symbol, $suffix), $dim, None, $ng, $dg, $props),)+]
-            $($rest)*
-        ]
-    };
-
-    // Обработка обычного элемента
-    (
-        [$($accumulated:tt)*]
-        $item:expr,
-        $($rest:tt)*
-    ) => {
-        units_array![
-            [$($accumulated)* $item,]
-            $($rest)*
-        ]
-    };
-
-    // Финальный случай
-    (
-        [$($accumulated:tt)*]
-    ) => {
-        &[$($accumulated)*]
-    };
+/// Статический минимальный ДКА для встроенных единиц, генерируется build.rs.
+/// Переходы состояния `s` лежат в `labels`/`targets` на отрезке
+/// `offsets[s]..offsets[s + 1]` и отсортированы по символу.
+#[derive(Debug)]
+pub struct UnitMatcher {
+    pub root: u32,
+    pub finals: &'static [bool],
+    pub offsets: &'static [u32],
+    pub labels: &'static [char],
+    pub targets: &'static [u32],
+}
+
+impl UnitMatcher {
+    /// Та же семантика, что и у `UnitTree::longest_match`
+    pub fn longest_match(&self, input: &[char]) -> usize {
+        let mut state = self.root as usize;
+        let mut last_final_idx = 0;
+
+        for (idx, ch) in input.iter().enumerate() {
+            let start = self.offsets[state] as usize;
+            let end = self.offsets[state + 1] as usize;
+
+            match self.labels[start..end].binary_search(ch) {
+                Ok(pos) => {
+                    state = self.targets[start + pos] as usize;
+                    if self.finals[state] {
+                        last_final_idx = idx + 1;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        last_final_idx
+    }
 }
@@ -1,8 +1,9 @@
-#[derive(Debug, Clone)]
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Dimension {
-    Degree,              // deg \\ AngleDegree
-    Radian,              // rad \\ AngleRadian
-    Percent,             // %   \\ Percentage
+    Angle,               // rad \\ Angle (deg, °, grad, turn, arcmin, arcsec)
+    Percent,             // %   \\ Безразмерные доли (%, ‰, ppm, ratio, dB)
     Length,              // m   \\ LengthUnit
     Time,                // s   \\ TimeUnit
     Frequency,           // Hz  \\ FrequencyUnit
@@ -37,3 +38,241 @@ pub enum Dimension {
     Area,                // m2 \\ AreaUnit
     Volume,              // m3 \\ VolumeUnit
 }
+
+impl Dimension {
+    /// Размерность по имени из аннотации типа (`<Length>`, `<Temperature>`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let dimension = match name {
+            // Degree и Radian — прежние имена, сохранены для аннотаций
+            "Angle" | "Degree" | "Radian" => Dimension::Angle,
+            // Ratio — прежнее имя размерности усилений (dB)
+            "Percent" | "Dimensionless" | "Ratio" => Dimension::Percent,
+            "Length" => Dimension::Length,
+            "Time" => Dimension::Time,
+            "Frequency" => Dimension::Frequency,
+            // Speed, Power, Voltage — привычные синонимы в аннотациях
+            "Velocity" | "Speed" => Dimension::Velocity,
+            "Acceleration" => Dimension::Acceleration,
+            "Jerk" => Dimension::Jerk,
+            "Snap" => Dimension::Snap,
+            "Crackle" => Dimension::Crackle,
+            "Pop" => Dimension::Pop,
+            "Size" => Dimension::Size,
+            "BitRate" => Dimension::BitRate,
+            "Mass" => Dimension::Mass,
+            "AreaDensity" => Dimension::AreaDensity,
+            "Density" => Dimension::Density,
+            "Amount" => Dimension::Amount,
+            "Fraction" => Dimension::Fraction,
+            "Dimension" => Dimension::Dimension,
+            "Temperature" => Dimension::Temperature,
+            "ElectricVoltage" | "Voltage" => Dimension::ElectricVoltage,
+            "ElectricCurrent" | "Current" => Dimension::ElectricCurrent,
+            "ElectricCharge" | "Charge" => Dimension::ElectricCharge,
+            "ElectricResistance" | "Resistance" => Dimension::ElectricResistance,
+            "ElectricConductance" | "Conductance" => Dimension::ElectricConductance,
+            "ElectricCapacitance" | "Capacitance" => Dimension::ElectricCapacitance,
+            "ElectricPower" | "Power" => Dimension::ElectricPower,
+            "LuminousIntensity" => Dimension::LuminousIntensity,
+            "LuminousFlux" => Dimension::LuminousFlux,
+            "Illuminance" => Dimension::Illuminance,
+            "Pressure" => Dimension::Pressure,
+            "Energy" => Dimension::Energy,
+            "Force" => Dimension::Force,
+            "Area" => Dimension::Area,
+            "Volume" => Dimension::Volume,
+            _ => return None,
+        };
+        Some(dimension)
+    }
+}
+
+/// Вектор размерности: степени базовых величин
+/// `[L, M, T, I, Θ, N, J, B]` (длина, масса, время, ток, температура,
+/// количество вещества, сила света, информация).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DimensionVector(pub [i8; 8]);
+
+impl DimensionVector {
+    pub const NONE: Self = Self([0; 8]);
+
+    /// Когерентные базовые единицы, в которых выражается вектор
+    const SYMBOLS: [&'static str; 8] = ["m", "kg", "s", "A", "K", "mol", "cd", "B"];
+
+    const fn of(l: i8, m: i8, t: i8, i: i8) -> Self {
+        Self([l, m, t, i, 0, 0, 0, 0])
+    }
+
+    const fn base(index: usize) -> Self {
+        let mut v = [0; 8];
+        v[index] = 1;
+        Self(v)
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Степень вектора; `None`, если показатель не помещается в i8
+    pub fn checked_powi(self, power: i8) -> Option<Self> {
+        let mut v = self.0;
+        for e in v.iter_mut() {
+            *e = e.checked_mul(power)?;
+        }
+        Some(Self(v))
+    }
+
+    /// Произведение единиц: степени складываются
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let mut v = self.0;
+        for (e, r) in v.iter_mut().zip(rhs.0) {
+            *e = e.checked_add(r)?;
+        }
+        Some(Self(v))
+    }
+
+    /// Частное единиц: степени вычитаются
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let mut v = self.0;
+        for (e, r) in v.iter_mut().zip(rhs.0) {
+            *e = e.checked_sub(r)?;
+        }
+        Some(Self(v))
+    }
+}
+
+impl fmt::Display for DimensionVector {
+    /// `kg·m²·s⁻³·K⁻¹`: сначала положительные степени, затем отрицательные
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_dimensionless() {
+            return write!(f, "1");
+        }
+
+        let mut order: Vec<usize> = (0..8).filter(|&i| self.0[i] != 0).collect();
+        order.sort_by_key(|&i| self.0[i] < 0);
+
+        let parts: Vec<String> = order
+            .into_iter()
+            .map(|i| match self.0[i] {
+                1 => Self::SYMBOLS[i].to_string(),
+                e => format!("{}{}", Self::SYMBOLS[i], superscript(e)),
+            })
+            .collect();
+        write!(f, "{}", parts.join("·"))
+    }
+}
+
+pub fn superscript(value: i8) -> String {
+    value
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+impl Dimension {
+    /// Вектор размерности в базовых величинах
+    pub const fn vector(&self) -> DimensionVector {
+        match self {
+            // Углы, проценты, отношения и доли безразмерны
+            Dimension::Angle | Dimension::Percent | Dimension::Fraction | Dimension::Dimension => {
+                DimensionVector::NONE
+            }
+            Dimension::Length => DimensionVector::of(1, 0, 0, 0),
+            Dimension::Time => DimensionVector::of(0, 0, 1, 0),
+            Dimension::Frequency => DimensionVector::of(0, 0, -1, 0),
+            Dimension::Velocity => DimensionVector::of(1, 0, -1, 0),
+            Dimension::Acceleration => DimensionVector::of(1, 0, -2, 0),
+            Dimension::Jerk => DimensionVector::of(1, 0, -3, 0),
+            Dimension::Snap => DimensionVector::of(1, 0, -4, 0),
+            Dimension::Crackle => DimensionVector::of(1, 0, -5, 0),
+            Dimension::Pop => DimensionVector::of(1, 0, -6, 0),
+            Dimension::Size => DimensionVector::base(7),
+            Dimension::BitRate => DimensionVector([0, 0, -1, 0, 0, 0, 0, 1]),
+            Dimension::Mass => DimensionVector::of(0, 1, 0, 0),
+            Dimension::AreaDensity => DimensionVector::of(-2, 1, 0, 0),
+            Dimension::Density => DimensionVector::of(-3, 1, 0, 0),
+            Dimension::Amount => DimensionVector::base(5),
+            Dimension::Temperature => DimensionVector::base(4),
+            Dimension::ElectricVoltage => DimensionVector::of(2, 1, -3, -1),
+            Dimension::ElectricCurrent => DimensionVector::of(0, 0, 0, 1),
+            Dimension::ElectricCharge => DimensionVector::of(0, 0, 1, 1),
+            Dimension::ElectricResistance => DimensionVector::of(2, 1, -3, -2),
+            Dimension::ElectricConductance => DimensionVector::of(-2, -1, 3, 2),
+            Dimension::ElectricCapacitance => DimensionVector::of(-2, -1, 4, 2),
+            Dimension::ElectricPower => DimensionVector::of(2, 1, -3, 0),
+            // Стерадиан безразмерен: люмен = кандела·ср
+            Dimension::LuminousIntensity | Dimension::LuminousFlux => DimensionVector::base(6),
+            Dimension::Illuminance => DimensionVector([-2, 0, 0, 0, 0, 0, 1, 0]),
+            Dimension::Pressure => DimensionVector::of(-1, 1, -2, 0),
+            Dimension::Energy => DimensionVector::of(2, 1, -2, 0),
+            Dimension::Force => DimensionVector::of(1, 1, -2, 0),
+            Dimension::Area => DimensionVector::of(2, 0, 0, 0),
+            Dimension::Volume => DimensionVector::of(3, 0, 0, 0),
+        }
+    }
+
+    /// Базовая единица размерности в когерентных единицах СИ.
+    /// Масса в реестре отсчитывается от грамма, поэтому g = 1e-3 kg.
+    pub const fn coherent_factor(&self) -> f64 {
+        match self {
+            Dimension::Mass | Dimension::AreaDensity | Dimension::Density => 1e-3,
+            _ => 1.0,
+        }
+    }
+
+    /// Именованная размерность для вектора; безразмерный вектор
+    /// и векторы без имени (теплопроводность и т.п.) не сопоставляются
+    pub fn from_vector(vector: DimensionVector) -> Option<Self> {
+        if vector.is_dimensionless() {
+            return None;
+        }
+        NAMED.iter().copied().find(|d| d.vector() == vector)
+    }
+}
+
+/// Порядок важен: при совпадении векторов выбирается первая (LuminousIntensity, а не LuminousFlux)
+const NAMED: &[Dimension] = &[
+    Dimension::Length,
+    Dimension::Time,
+    Dimension::Frequency,
+    Dimension::Velocity,
+    Dimension::Acceleration,
+    Dimension::Jerk,
+    Dimension::Snap,
+    Dimension::Crackle,
+    Dimension::Pop,
+    Dimension::Size,
+    Dimension::BitRate,
+    Dimension::Mass,
+    Dimension::AreaDensity,
+    Dimension::Density,
+    Dimension::Amount,
+    Dimension::Temperature,
+    Dimension::ElectricVoltage,
+    Dimension::ElectricCurrent,
+    Dimension::ElectricCharge,
+    Dimension::ElectricResistance,
+    Dimension::ElectricConductance,
+    Dimension::ElectricCapacitance,
+    Dimension::ElectricPower,
+    Dimension::LuminousIntensity,
+    Dimension::Illuminance,
+    Dimension::Pressure,
+    Dimension::Energy,
+    Dimension::Force,
+    Dimension::Area,
+    Dimension::Volume,
+];
@@ -0,0 +1,194 @@
+// Единицы вёрстки. `em`, `px`, `vw`, `fr` — не доли метра, а доли величины,
+// известной только при отрисовке: размера шрифта, плотности экрана, окна.
+// Между собой и с физическими единицами (in, pt, cm) они переводятся
+// только через `LayoutContext`; без него перевод сообщает, чего не хватает.
+
+use super::conversion::ConversionError;
+use super::definition::UnitDef;
+use super::dimensions::Dimension;
+
+/// Величина, в долях которой задан `scale` единицы
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutBasis {
+    /// Обычная единица: scale — доля базовой единицы размерности (m)
+    #[default]
+    Absolute,
+    /// px — пиксель устройства, зависит от DPI
+    Pixel,
+    /// em — размер шрифта родителя
+    Font,
+    /// rem — размер шрифта корня документа
+    RootFont,
+    ViewportWidth,
+    ViewportHeight,
+    ViewportMin,
+    ViewportMax,
+    /// fr — доля свободного места в треке сетки
+    Space,
+}
+
+impl LayoutBasis {
+    /// Имя варианта, как его пишут loader.rs и build.rs
+    pub fn from_variant(name: &str) -> Option<Self> {
+        let basis = match name {
+            "Absolute" => LayoutBasis::Absolute,
+            "Pixel" => LayoutBasis::Pixel,
+            "Font" => LayoutBasis::Font,
+            "RootFont" => LayoutBasis::RootFont,
+            "ViewportWidth" => LayoutBasis::ViewportWidth,
+            "ViewportHeight" => LayoutBasis::ViewportHeight,
+            "ViewportMin" => LayoutBasis::ViewportMin,
+            "ViewportMax" => LayoutBasis::ViewportMax,
+            "Space" => LayoutBasis::Space,
+            _ => return None,
+        };
+        Some(basis)
+    }
+
+    pub fn is_absolute(self) -> bool {
+        self == LayoutBasis::Absolute
+    }
+}
+
+/// Параметры отрисовки. Размеры — в пикселях, DPI — пикселей на дюйм.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayoutContext {
+    pub root_font_size: Option<f64>,
+    pub parent_font_size: Option<f64>,
+    pub dpi: Option<f64>,
+    pub viewport_width: Option<f64>,
+    pub viewport_height: Option<f64>,
+    /// Размер `1fr`: свободное место трека, деленное на сумму `fr`
+    pub fraction_size: Option<f64>,
+}
+
+impl LayoutContext {
+    /// Умолчания браузера: 96 DPI, шрифт 16px. Окно и сетка не заданы.
+    pub fn css() -> Self {
+        Self {
+            root_font_size: Some(16.0),
+            parent_font_size: Some(16.0),
+            dpi: Some(96.0),
+            ..Self::default()
+        }
+    }
+
+    /// Сколько пикселей в единице базиса (для Absolute — в метре)
+    fn pixels_per(&self, basis: LayoutBasis) -> Result<f64, &'static str> {
+        let require = |value: Option<f64>, name: &'static str| value.ok_or(name);
+        match basis {
+            LayoutBasis::Absolute => Ok(require(self.dpi, "dpi")? / 0.0254),
+            LayoutBasis::Pixel => Ok(1.0),
+            LayoutBasis::Font => require(self.parent_font_size, "parent font size"),
+            LayoutBasis::RootFont => require(self.root_font_size, "root font size"),
+            LayoutBasis::ViewportWidth => require(self.viewport_width, "viewport width"),
+            LayoutBasis::ViewportHeight => require(self.viewport_height, "viewport height"),
+            LayoutBasis::ViewportMin | LayoutBasis::ViewportMax => {
+                let width = require(self.viewport_width, "viewport width")?;
+                let height = require(self.viewport_height, "viewport height")?;
+                Ok(if basis == LayoutBasis::ViewportMin {
+                    width.min(height)
+                } else {
+                    width.max(height)
+                })
+            }
+            LayoutBasis::Space => require(self.fraction_size, "fraction size"),
+        }
+    }
+}
+
+impl UnitDef {
+    /// Перевод с учетом контекста отрисовки: `2em` -> `32px` при шрифте 16px.
+    /// `fr` (Fraction) переводится в длины и обратно только здесь.
+    pub fn convert_in(
+        &self,
+        value: f64,
+        target: &UnitDef,
+        context: &LayoutContext,
+    ) -> Result<f64, ConversionError> {
+        let layout = |d: Dimension| matches!(d, Dimension::Length | Dimension::Fraction);
+        let (from_basis, to_basis) = (self.props.basis, target.props.basis);
+
+        if from_basis == to_basis || !layout(self.dimension) || !layout(target.dimension) {
+            return self.convert_same_basis(value, target);
+        }
+
+        let missing = |unit: &UnitDef, what: &'static str| ConversionError::MissingContext {
+            unit: unit.symbol.to_string(),
+            missing: what,
+        };
+        let from = context
+            .pixels_per(from_basis)
+            .map_err(|what| missing(self, what))?;
+        let to = context
+            .pixels_per(to_basis)
+            .map_err(|what| missing(target, what))?;
+
+        let pixels = self.normalize(value) * from;
+        Ok(target.denormalize(pixels / to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::unit::units::find_unit;
+
+    fn unit(symbol: &str) -> &'static UnitDef {
+        find_unit(symbol).unwrap()
+    }
+
+    fn convert(value: f64, from: &str, to: &str, context: &LayoutContext) -> f64 {
+        unit(from).convert_in(value, unit(to), context).unwrap()
+    }
+
+    #[test]
+    fn resolves_relative_units() {
+        let css = LayoutContext::css();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        assert!(close(convert(2.0, "em", "px", &css), 32.0));
+        assert!(close(convert(1.0, "in", "px", &css), 96.0));
+        assert!(close(convert(12.0, "pt", "px", &css), 16.0));
+        assert!(close(convert(1.0, "pc", "pt", &css), 12.0));
+
+        let nested = LayoutContext {
+            parent_font_size: Some(20.0),
+            viewport_width: Some(1280.0),
+            viewport_height: Some(720.0),
+            fraction_size: Some(150.0),
+            ..css
+        };
+        assert!(close(convert(1.0, "em", "rem", &nested), 1.25));
+        assert!(close(convert(50.0, "vw", "px", &nested), 640.0));
+        assert!(close(convert(10.0, "vmin", "vh", &nested), 10.0));
+        assert!(close(convert(2.0, "fr", "px", &nested), 300.0));
+    }
+
+    #[test]
+    fn reports_missing_context() {
+        // Без контекста относительные единицы не сводятся к физическим
+        assert_eq!(
+            unit("em").convert_to(1.0, unit("px")),
+            Err(ConversionError::MissingContext {
+                unit: "em".into(),
+                missing: "parent font size",
+            })
+        );
+        assert!(matches!(
+            unit("px").convert_to(1.0, unit("in")),
+            Err(ConversionError::MissingContext { missing: "dpi", .. })
+        ));
+        assert!(matches!(
+            unit("vw").convert_in(1.0, unit("px"), &LayoutContext::css()),
+            Err(ConversionError::MissingContext {
+                missing: "viewport width",
+                ..
+            })
+        ));
+
+        // Одна основа — контекст не нужен
+        assert!((unit("ft").convert_to(1.0, unit("in")).unwrap() - 12.0).abs() < 1e-12);
+        assert_eq!(unit("vh").convert_to(1.0, unit("vh")), Ok(1.0));
+    }
+}
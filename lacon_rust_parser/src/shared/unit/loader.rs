@@ -0,0 +1,538 @@
+// Разбор файла определений единиц (units.def).
+//
+// Модуль самодостаточен (без `crate::`/`super::`): его же подключает build.rs
+// для генерации встроенного реестра, а `UnitRegistry::load_definitions`
+// использует его для пользовательских файлов.
+//
+// Формат — одна запись на строку, `#` — комментарий:
+//
+//   [prefixes]
+//   k      1e3        SI
+//
+//   [units]
+//   m      Length     SI
+//   g/m3   Density    SI/SI  parts=g/m3 exponent=3 formula=Mass/Length.Length.Length
+//   {deg|°}C  Temperature  None  offset=273.15
+//
+// Колонки единицы: символ, размерность, группа приставок (`SI` или `SI/None`
+// для числителя/знаменателя), далее необязательные `ключ=значение`:
+// scale, offset, exponent (арифметические выражения, допустимы `pi` и `ln(x)`),
+// mode (linear|exponential|logarithmic), parts (числитель/знаменатель),
+// formula (`Mass`, `Length/Time.Time`, `/Time`),
+// ambiguous (`gal_us|gal_uk` — символ значит разное в разных системах;
+// значение берется от первого варианта, сканер предупреждает),
+// basis (pixel|font|root-font|viewport-width|viewport-height|viewport-min|
+// viewport-max|space — единица вёрстки, см. layout.rs).
+// `{a|b}X` разворачивается в несколько символов с общими свойствами.
+
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefixSpec {
+    pub symbol: String,
+    pub factor: f64,
+    pub group: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaSpec {
+    None,
+    Simple(String),
+    Complex { num: Vec<String>, den: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitSpec {
+    pub symbol: String,
+    pub dimension: String,
+    pub parts: Option<(String, String)>,
+    pub numerator_group: String,
+    pub denominator_group: String,
+    pub scale: f64,
+    pub offset: f64,
+    pub exponent: f64,
+    /// `Linear`, `Exponential` или `Logarithmic`
+    pub mode: String,
+    pub formula: FormulaSpec,
+    /// Явные варианты неоднозначного символа, первый — по умолчанию
+    pub ambiguous: Vec<String>,
+    /// Вариант `LayoutBasis`: `Absolute`, `Pixel`, `Font`...
+    pub basis: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Definitions {
+    pub prefixes: Vec<PrefixSpec>,
+    pub units: Vec<UnitSpec>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Prefixes,
+    Units,
+}
+
+pub fn parse(text: &str) -> Result<Definitions, DefinitionError> {
+    let mut defs = Definitions::default();
+    let mut section = Section::Units;
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let content = raw.split('#').next().unwrap_or("").trim();
+        if content.is_empty() {
+            continue;
+        }
+
+        match content {
+            "[prefixes]" => {
+                section = Section::Prefixes;
+                continue;
+            }
+            "[units]" => {
+                section = Section::Units;
+                continue;
+            }
+            _ => {}
+        }
+
+        let err = |message: String| DefinitionError { line, message };
+        let columns: Vec<&str> = content.split_whitespace().collect();
+
+        match section {
+            Section::Prefixes => {
+                let [symbol, factor, group] = columns[..] else {
+                    return Err(err("expected `symbol factor group`".into()));
+                };
+                defs.prefixes.push(PrefixSpec {
+                    symbol: symbol.to_string(),
+                    factor: eval(factor).map_err(err)?,
+                    group: group.to_string(),
+                    line,
+                });
+            }
+            Section::Units => {
+                if columns.len() < 3 {
+                    return Err(err(
+                        "expected `symbol dimension group [key=value...]`".into()
+                    ));
+                }
+                let (n_group, d_group) = match columns[2].split_once('/') {
+                    Some((n, d)) => (n.to_string(), d.to_string()),
+                    None => (columns[2].to_string(), columns[2].to_string()),
+                };
+
+                let mut spec = UnitSpec {
+                    symbol: String::new(),
+                    dimension: columns[1].to_string(),
+                    parts: None,
+                    numerator_group: n_group,
+                    denominator_group: d_group,
+                    scale: 1.0,
+                    offset: 0.0,
+                    exponent: 1.0,
+                    mode: "Linear".into(),
+                    formula: FormulaSpec::None,
+                    ambiguous: Vec::new(),
+                    basis: "Absolute".into(),
+                    line,
+                };
+
+                for prop in &columns[3..] {
+                    let (key, value) = prop
+                        .split_once('=')
+                        .ok_or_else(|| err(format!("expected `key=value`, found `{}`", prop)))?;
+                    match key {
+                        "scale" => spec.scale = eval(value).map_err(err)?,
+                        "offset" => spec.offset = eval(value).map_err(err)?,
+                        "exponent" => spec.exponent = eval(value).map_err(err)?,
+                        "mode" => {
+                            spec.mode = match value {
+                                "linear" => "Linear".into(),
+                                "exponential" => "Exponential".into(),
+                                "logarithmic" => "Logarithmic".into(),
+                                _ => return Err(err(format!("unknown mode `{}`", value))),
+                            }
+                        }
+                        "basis" => {
+                            spec.basis = match value {
+                                "pixel" => "Pixel".into(),
+                                "font" => "Font".into(),
+                                "root-font" => "RootFont".into(),
+                                "viewport-width" => "ViewportWidth".into(),
+                                "viewport-height" => "ViewportHeight".into(),
+                                "viewport-min" => "ViewportMin".into(),
+                                "viewport-max" => "ViewportMax".into(),
+                                "space" => "Space".into(),
+                                _ => return Err(err(format!("unknown basis `{}`", value))),
+                            }
+                        }
+                        "parts" => {
+                            let (n, d) = value.split_once('/').ok_or_else(|| {
+                                err(format!("parts must be `num/den`: {}", value))
+                            })?;
+                            spec.parts = Some((n.to_string(), d.to_string()));
+                        }
+                        "formula" => spec.formula = parse_formula(value),
+                        "ambiguous" => {
+                            spec.ambiguous = value.split('|').map(String::from).collect()
+                        }
+                        _ => return Err(err(format!("unknown property `{}`", key))),
+                    }
+                }
+
+                for symbol in expand_symbol(columns[0]).map_err(err)? {
+                    defs.units.push(UnitSpec {
+                        symbol,
+                        ..spec.clone()
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(defs)
+}
+
+impl Definitions {
+    /// Проверка реестра: без повторов символов и без совпадений форм с приставками
+    /// (н-р, новая единица `in` с группой SI дала бы `min`, уже занятое минутой).
+    pub fn validate(&self) -> Result<(), DefinitionError> {
+        let mut seen_prefixes: HashMap<(&str, &str), usize> = HashMap::new();
+        for p in &self.prefixes {
+            if let Some(first) = seen_prefixes.insert((&p.group, &p.symbol), p.line) {
+                return Err(DefinitionError {
+                    line: p.line,
+                    message: format!(
+                        "prefix `{}` ({}) is already defined on line {}",
+                        p.symbol, p.group, first
+                    ),
+                });
+            }
+        }
+
+        let mut owners: HashMap<String, &UnitSpec> = HashMap::new();
+        for unit in &self.units {
+            for form in self.forms(unit) {
+                if let Some(other) = owners.get(&form) {
+                    let message = if other.symbol == unit.symbol {
+                        format!(
+                            "unit `{}` is already defined on line {}",
+                            unit.symbol, other.line
+                        )
+                    } else {
+                        format!(
+                            "`{}` is ambiguous: `{}` (line {}) and `{}`",
+                            form, other.symbol, other.line, unit.symbol
+                        )
+                    };
+                    return Err(DefinitionError {
+                        line: unit.line,
+                        message,
+                    });
+                }
+                owners.insert(form, unit);
+            }
+        }
+
+        for unit in self.units.iter().filter(|u| !u.ambiguous.is_empty()) {
+            let err = |message: String| DefinitionError {
+                line: unit.line,
+                message,
+            };
+            for variant in &unit.ambiguous {
+                let Some(other) = owners.get(variant) else {
+                    return Err(err(format!("unknown variant `{}`", variant)));
+                };
+                if other.dimension != unit.dimension {
+                    return Err(err(format!(
+                        "variant `{}` is not {}",
+                        variant, unit.dimension
+                    )));
+                }
+            }
+            let default = owners[&unit.ambiguous[0]];
+            if default.scale != unit.scale {
+                return Err(err(format!(
+                    "`{}` must have the scale of its default variant `{}`",
+                    unit.symbol, default.symbol
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Все написания единицы, которые распознает сканер
+    pub fn forms(&self, unit: &UnitSpec) -> Vec<String> {
+        let prefixes_of = |group: &str| -> Vec<&str> {
+            self.prefixes
+                .iter()
+                .filter(|p| p.group == group)
+                .map(|p| p.symbol.as_str())
+                .chain(std::iter::once(""))
+                .collect()
+        };
+
+        let mut forms = vec![unit.symbol.clone()];
+        match &unit.parts {
+            Some((n, d)) => {
+                for p_n in prefixes_of(&unit.numerator_group) {
+                    for p_d in prefixes_of(&unit.denominator_group) {
+                        if !p_n.is_empty() || !p_d.is_empty() {
+                            forms.push(format!("{}{}/{}{}", p_n, n, p_d, d));
+                        }
+                    }
+                }
+            }
+            None if unit.numerator_group != "None" => {
+                for p in prefixes_of(&unit.numerator_group) {
+                    if !p.is_empty() {
+                        forms.push(format!("{}{}", p, unit.symbol));
+                    }
+                }
+            }
+            None => {}
+        }
+        forms
+    }
+}
+
+/// `{deg|°}C` -> ["degC", "°C"]
+fn expand_symbol(symbol: &str) -> Result<Vec<String>, String> {
+    let Some(open) = symbol.find('{') else {
+        return Ok(vec![symbol.to_string()]);
+    };
+    let close = symbol[open..]
+        .find('}')
+        .map(|i| open + i)
+        .ok_or_else(|| format!("unclosed `{{` in `{}`", symbol))?;
+
+    let (head, tail) = (&symbol[..open], &symbol[close + 1..]);
+    Ok(symbol[open + 1..close]
+        .split('|')
+        .map(|variant| format!("{}{}{}", head, variant, tail))
+        .collect())
+}
+
+fn parse_formula(value: &str) -> FormulaSpec {
+    let list = |s: &str| -> Vec<String> {
+        s.split('.')
+            .filter(|d| !d.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+
+    match value.split_once('/') {
+        Some((num, den)) => FormulaSpec::Complex {
+            num: list(num),
+            den: list(den),
+        },
+        None if value.is_empty() => FormulaSpec::None,
+        None => FormulaSpec::Simple(value.to_string()),
+    }
+}
+
+/// Вычисление числового выражения: `5/9`, `459.67*5/9`, `pi/180`, `1e-30`, `1024^3`, `10/ln(10)`
+pub fn eval(expr: &str) -> Result<f64, String> {
+    let chars: Vec<char> = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut pos = 0;
+    let value = eval_sum(&chars, &mut pos)?;
+    if pos != chars.len() {
+        return Err(format!("unexpected `{}` in `{}`", chars[pos], expr));
+    }
+    Ok(value)
+}
+
+fn eval_sum(chars: &[char], pos: &mut usize) -> Result<f64, String> {
+    let mut value = eval_product(chars, pos)?;
+    while let Some(&op) = chars.get(*pos) {
+        match op {
+            '+' => {
+                *pos += 1;
+                value += eval_product(chars, pos)?;
+            }
+            '-' => {
+                *pos += 1;
+                value -= eval_product(chars, pos)?;
+            }
+            _ => break,
+        }
+    }
+    Ok(value)
+}
+
+fn eval_product(chars: &[char], pos: &mut usize) -> Result<f64, String> {
+    let mut value = eval_power(chars, pos)?;
+    while let Some(&op) = chars.get(*pos) {
+        match op {
+            '*' => {
+                *pos += 1;
+                value *= eval_power(chars, pos)?;
+            }
+            '/' => {
+                *pos += 1;
+                value /= eval_power(chars, pos)?;
+            }
+            _ => break,
+        }
+    }
+    Ok(value)
+}
+
+/// `1024^3`; степень правоассоциативна
+fn eval_power(chars: &[char], pos: &mut usize) -> Result<f64, String> {
+    let base = eval_atom(chars, pos)?;
+    if chars.get(*pos) == Some(&'^') {
+        *pos += 1;
+        return Ok(base.powf(eval_power(chars, pos)?));
+    }
+    Ok(base)
+}
+
+fn eval_atom(chars: &[char], pos: &mut usize) -> Result<f64, String> {
+    match chars.get(*pos) {
+        Some('-') => {
+            *pos += 1;
+            Ok(-eval_atom(chars, pos)?)
+        }
+        Some('(') => {
+            *pos += 1;
+            let value = eval_sum(chars, pos)?;
+            if chars.get(*pos) != Some(&')') {
+                return Err("expected `)`".into());
+            }
+            *pos += 1;
+            Ok(value)
+        }
+        Some('l') if chars.get(*pos + 1) == Some(&'n') => {
+            *pos += 2;
+            if chars.get(*pos) != Some(&'(') {
+                return Err("expected `(` after `ln`".into());
+            }
+            Ok(eval_atom(chars, pos)?.ln())
+        }
+        Some('p') if chars.get(*pos + 1) == Some(&'i') => {
+            *pos += 2;
+            Ok(std::f64::consts::PI)
+        }
+        Some(c) if c.is_ascii_digit() || *c == '.' => {
+            let start = *pos;
+            while let Some(&c) = chars.get(*pos) {
+                let is_exponent_sign =
+                    (c == '-' || c == '+') && matches!(chars.get(*pos - 1), Some('e' | 'E'));
+                if c.is_ascii_digit()
+                    || c == '.'
+                    || c == '_'
+                    || c == 'e'
+                    || c == 'E'
+                    || is_exponent_sign
+                {
+                    *pos += 1;
+                } else {
+                    break;
+                }
+            }
+            let literal: String = chars[start..*pos].iter().filter(|&&c| c != '_').collect();
+            literal
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number `{}`", literal))
+        }
+        Some(c) => Err(format!("unexpected `{}`", c)),
+        None => Err("unexpected end of expression".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_units_and_prefixes() {
+        let defs = parse(
+            "[prefixes]\nk 1e3 SI\n\n[units]\n\
+             m Length SI            # метр\n\
+             {deg|°}F Temperature None scale=5/9 offset=459.67*5/9\n\
+             m/s2 Acceleration SI/SI parts=m/s2 exponent=2 formula=Length/Time.Time\n",
+        )
+        .unwrap();
+
+        assert_eq!(defs.prefixes[0].factor, 1e3);
+        let symbols: Vec<&str> = defs.units.iter().map(|u| u.symbol.as_str()).collect();
+        assert_eq!(symbols, ["m", "degF", "°F", "m/s2"]);
+        assert_eq!(defs.units[1].offset, 459.67 * 5.0 / 9.0);
+        assert_eq!(
+            defs.units[3].formula,
+            FormulaSpec::Complex {
+                num: vec!["Length".into()],
+                den: vec!["Time".into(), "Time".into()],
+            }
+        );
+        assert!(defs.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_duplicates_and_collisions() {
+        let duplicate = parse("m Length SI\nm Length None\n").unwrap();
+        assert_eq!(duplicate.validate().unwrap_err().line, 2);
+
+        // "m" + "in" с группой SI совпадает с минутой
+        let collision =
+            parse("[prefixes]\nm 1e-3 SI\n[units]\nmin Time None\nin Length SI\n").unwrap();
+        let err = collision.validate().unwrap_err();
+        assert!(err.message.contains("`min` is ambiguous"), "{}", err);
+
+        assert!(parse("m Length SI scale=abc\n").is_err());
+        assert!(parse("m Length SI mode=weird\n").is_err());
+        assert!(parse("em Length None basis=glyph\n").is_err());
+    }
+
+    #[test]
+    fn ambiguous_variants() {
+        let defs = parse(
+            "gal_us Volume None scale=3.785411784\n\
+             gal_uk Volume None scale=4.54609\n\
+             gal    Volume None scale=3.785411784 ambiguous=gal_us|gal_uk\n",
+        )
+        .unwrap();
+        assert_eq!(defs.units[2].ambiguous, ["gal_us", "gal_uk"]);
+        defs.validate().unwrap();
+
+        let wrong_default = parse(
+            "gal_us Volume None scale=3.785411784\n\
+             gal    Volume None scale=4.54609 ambiguous=gal_us\n",
+        )
+        .unwrap();
+        assert_eq!(wrong_default.validate().unwrap_err().line, 2);
+        let unknown = parse("gal Volume None ambiguous=gal_xx\n").unwrap();
+        assert!(unknown.validate().is_err());
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        assert_eq!(
+            eval("273.15 - 7.5 * 40 / 21").unwrap(),
+            273.15 - 7.5 * 40.0 / 21.0
+        );
+        assert_eq!(eval("pi/180").unwrap(), std::f64::consts::PI / 180.0);
+        assert_eq!(eval("-2/3").unwrap(), -2.0 / 3.0);
+        assert_eq!(eval("1e-30").unwrap(), 1e-30);
+        assert_eq!(eval("1_048_576").unwrap(), 1048576.0);
+        assert_eq!(eval("1024^2").unwrap(), 1048576.0);
+        assert_eq!(eval("10/ln(10)").unwrap(), 10.0 / 10f64.ln());
+    }
+}
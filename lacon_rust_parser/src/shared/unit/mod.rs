@@ -1,6 +1,11 @@
-pub mod dimensions;
-#[macro_use]
+pub mod angle;
+pub mod compound;
+pub mod conversion;
 pub mod definition;
+pub mod dimensions;
+pub mod layout;
+pub mod loader;
 pub mod prefixes;
 pub mod props;
+pub mod registry;
 pub mod units;
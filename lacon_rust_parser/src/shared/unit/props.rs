@@ -1,9 +1,14 @@
 use super::dimensions::Dimension;
+use super::layout::LayoutBasis;
+use std::borrow::Cow;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CalcMode {
     Linear,
     Exponential,
+    /// Уровни (dB, dBm, Np): scale — опорное значение, offset — единиц уровня
+    /// на непер, exponent — 1 для мощности, 2 для полевых величин (напряжение)
+    Logarithmic,
 }
 
 #[derive(Debug, Clone)]
@@ -12,8 +17,8 @@ pub enum Formula {
     Simple(Dimension), // Одна размерность (н-р, Mass)
     Complex {
         // Составная (н-р, m/s2)
-        num: &'static [Dimension],
-        den: &'static [Dimension],
+        num: Cow<'static, [Dimension]>,
+        den: Cow<'static, [Dimension]>,
     },
 }
 
@@ -23,6 +28,8 @@ pub struct UnitProps {
     pub offset: f64,
     pub exponent: f64,
     pub mode: CalcMode,
+    /// Для единиц вёрстки (em, px, vw) — величина, в долях которой задан scale
+    pub basis: LayoutBasis,
     pub formula: Formula,
 }
 
@@ -32,6 +39,7 @@ impl UnitProps {
         offset: 0.0,
         exponent: 1.0,
         mode: CalcMode::Linear,
+        basis: LayoutBasis::Absolute,
         formula: Formula::None,
     };
 }
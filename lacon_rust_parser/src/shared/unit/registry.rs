@@ -0,0 +1,379 @@
+use super::definition::{PrefixGroup, UnitDef, UnitTree};
+use super::dimensions::Dimension;
+use super::layout::LayoutBasis;
+use super::loader::{self, DefinitionError, FormulaSpec, UnitSpec};
+use super::prefixes::PREFIXES;
+use super::props::{CalcMode, Formula, UnitProps};
+use super::units::{UNITS_MATCHER, ambiguous_variants, find_unit, insert_unit, unit_forms};
+use std::borrow::Cow;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitRegistryError {
+    /// Символ уже распознается (встроенная единица или ее форма с приставкой)
+    DuplicateSymbol(String),
+    UnknownDimension(String),
+    UnknownPrefixGroup(String),
+    /// Правая часть объявления ссылается на неизвестную единицу
+    UnknownBaseUnit(String),
+    DimensionMismatch {
+        expected: Dimension,
+        found: Dimension,
+    },
+    /// Нельзя выводить единицу из шкалы со смещением (°C, °F)
+    AffineBase(String),
+    InvalidValue(String),
+    /// Ошибка в файле определений
+    Definition(DefinitionError),
+}
+
+impl fmt::Display for UnitRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitRegistryError::DuplicateSymbol(s) => write!(f, "Unit '{}' is already defined", s),
+            UnitRegistryError::UnknownDimension(s) => write!(f, "Unknown dimension '{}'", s),
+            UnitRegistryError::UnknownPrefixGroup(s) => write!(f, "Unknown prefix group '{}'", s),
+            UnitRegistryError::UnknownBaseUnit(s) => write!(f, "Unknown base unit '{}'", s),
+            UnitRegistryError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Unit declared as {:?} but its value is {:?}",
+                expected, found
+            ),
+            UnitRegistryError::AffineBase(s) => {
+                write!(f, "Cannot derive a unit from offset scale '{}'", s)
+            }
+            UnitRegistryError::InvalidValue(s) => write!(f, "Invalid unit value '{}'", s),
+            UnitRegistryError::Definition(e) => write!(f, "Unit definitions, {}", e),
+        }
+    }
+}
+
+/// Реестр единиц документа.
+/// Встроенные `UNITS` видны всегда, объявленные в исходнике (`unit tile<Length> = 16px`)
+/// хранятся отдельно вместе со своим деревом для сканера.
+#[derive(Debug, Default, Clone)]
+pub struct UnitRegistry {
+    units: Vec<UnitDef>,
+    tree: UnitTree,
+    /// Неоднозначные символы из загруженных файлов определений
+    ambiguous: Vec<(String, Vec<String>)>,
+}
+
+impl UnitRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Только локально объявленные единицы
+    pub fn units(&self) -> &[UnitDef] {
+        &self.units
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Поиск по точному символу: сначала локальные, затем встроенные
+    pub fn find(&self, symbol: &str) -> Option<&UnitDef> {
+        self.units
+            .iter()
+            .find(|u| u.symbol == symbol)
+            .or_else(|| find_unit(symbol))
+    }
+
+    /// Разбор суффикса с приставкой: `km` -> (1e3, m)
+    pub fn resolve(&self, suffix: &str) -> Option<(f64, &UnitDef)> {
+        if let Some(def) = self.find(suffix) {
+            return Some((1.0, def));
+        }
+
+        PREFIXES.iter().find_map(|(p_sym, factor, p_group)| {
+            let def = self.find(suffix.strip_prefix(p_sym)?)?;
+            (def.parts.is_none() && def.numerator_group == *p_group).then_some((*factor, def))
+        })
+    }
+
+    /// Явные варианты символа, если он значит разное в разных системах
+    /// (`gal`: первым идет вариант, который подставляется по умолчанию)
+    pub fn ambiguity(&self, symbol: &str) -> Option<Vec<&str>> {
+        if let Some((_, variants)) = self.ambiguous.iter().find(|(s, _)| s == symbol) {
+            return Some(variants.iter().map(String::as_str).collect());
+        }
+        ambiguous_variants(symbol).map(<[&str]>::to_vec)
+    }
+
+    /// Длина самого длинного суффикса единицы в начале `input`
+    pub fn longest_match(&self, input: &[char]) -> usize {
+        UNITS_MATCHER
+            .longest_match(input)
+            .max(self.tree.longest_match(input))
+    }
+
+    pub fn register(&mut self, def: UnitDef) -> Result<(), UnitRegistryError> {
+        if def.symbol.is_empty() {
+            return Err(UnitRegistryError::InvalidValue(String::new()));
+        }
+        // Ни символ, ни его формы с приставками не должны уже распознаваться
+        for form in unit_forms(&def) {
+            let chars: Vec<char> = form.chars().collect();
+            if self.longest_match(&chars) == chars.len() {
+                return Err(UnitRegistryError::DuplicateSymbol(form));
+            }
+        }
+
+        insert_unit(&mut self.tree, &def);
+        self.units.push(def);
+        Ok(())
+    }
+
+    /// Объявление вида `symbol<Dimension, Group> = value base`
+    pub fn declare(
+        &mut self,
+        symbol: &str,
+        dimension: &str,
+        group: Option<&str>,
+        value: f64,
+        base: &str,
+    ) -> Result<&UnitDef, UnitRegistryError> {
+        let dimension = Dimension::from_name(dimension)
+            .ok_or_else(|| UnitRegistryError::UnknownDimension(dimension.into()))?;
+        let group = match group {
+            Some(name) => PrefixGroup::from_name(name)
+                .ok_or_else(|| UnitRegistryError::UnknownPrefixGroup(name.into()))?,
+            None => PrefixGroup::None,
+        };
+
+        let (factor, base_def) = self
+            .resolve(base)
+            .ok_or_else(|| UnitRegistryError::UnknownBaseUnit(base.into()))?;
+
+        if base_def.dimension != dimension {
+            return Err(UnitRegistryError::DimensionMismatch {
+                expected: dimension,
+                found: base_def.dimension,
+            });
+        }
+        if base_def.is_affine() {
+            return Err(UnitRegistryError::AffineBase(base_def.symbol.to_string()));
+        }
+        if !value.is_finite() || value == 0.0 {
+            return Err(UnitRegistryError::InvalidValue(value.to_string()));
+        }
+
+        let def = UnitDef {
+            symbol: Cow::Owned(symbol.to_string()),
+            dimension,
+            parts: None,
+            numerator_group: group,
+            denominator_group: group,
+            props: UnitProps {
+                scale: value * factor * base_def.props.scale,
+                // `tile<Length> = 16px` остается пиксельной единицей
+                basis: base_def.props.basis,
+                formula: base_def.props.formula.clone(),
+                ..UnitProps::DEFAULT
+            },
+        };
+
+        self.register(def)?;
+        Ok(self.units.last().unwrap())
+    }
+
+    /// Загрузка пользовательского файла в формате units.def.
+    /// Приставки переопределять нельзя, составные `parts` — только во встроенном реестре.
+    pub fn load_definitions(&mut self, text: &str) -> Result<(), UnitRegistryError> {
+        let defs = loader::parse(text).map_err(UnitRegistryError::Definition)?;
+        if let Some(prefix) = defs.prefixes.first() {
+            return Err(UnitRegistryError::Definition(DefinitionError {
+                line: prefix.line,
+                message: "prefixes cannot be redefined".into(),
+            }));
+        }
+        defs.validate().map_err(UnitRegistryError::Definition)?;
+
+        for spec in &defs.units {
+            let in_line = |e: UnitRegistryError| {
+                UnitRegistryError::Definition(DefinitionError {
+                    line: spec.line,
+                    message: e.to_string(),
+                })
+            };
+            let def = unit_from_spec(spec).map_err(in_line)?;
+            self.register(def).map_err(in_line)?;
+            if !spec.ambiguous.is_empty() {
+                self.ambiguous
+                    .push((spec.symbol.clone(), spec.ambiguous.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Подключение единиц импортированного документа
+    pub fn extend(&mut self, other: &UnitRegistry) -> Result<(), UnitRegistryError> {
+        for def in &other.units {
+            // Повторный импорт того же определения не считается конфликтом
+            if self.units.iter().any(|u| u.symbol == def.symbol) {
+                continue;
+            }
+            self.register(def.clone())?;
+            if let Some(entry) = other.ambiguous.iter().find(|(s, _)| *s == def.symbol) {
+                self.ambiguous.push(entry.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unit_from_spec(spec: &UnitSpec) -> Result<UnitDef, UnitRegistryError> {
+    let dimension = |name: &str| {
+        Dimension::from_name(name).ok_or_else(|| UnitRegistryError::UnknownDimension(name.into()))
+    };
+    let group = |name: &str| {
+        PrefixGroup::from_name(name)
+            .ok_or_else(|| UnitRegistryError::UnknownPrefixGroup(name.into()))
+    };
+    let dimensions = |names: &[String]| {
+        names
+            .iter()
+            .map(|n| dimension(n))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    if spec.parts.is_some() {
+        return Err(UnitRegistryError::InvalidValue(format!(
+            "parts of '{}' (compound units are built-in only)",
+            spec.symbol
+        )));
+    }
+
+    let formula = match &spec.formula {
+        FormulaSpec::None => Formula::None,
+        FormulaSpec::Simple(name) => Formula::Simple(dimension(name)?),
+        FormulaSpec::Complex { num, den } => Formula::Complex {
+            num: Cow::Owned(dimensions(num)?),
+            den: Cow::Owned(dimensions(den)?),
+        },
+    };
+
+    Ok(UnitDef {
+        symbol: Cow::Owned(spec.symbol.clone()),
+        dimension: dimension(&spec.dimension)?,
+        parts: None,
+        numerator_group: group(&spec.numerator_group)?,
+        denominator_group: group(&spec.denominator_group)?,
+        props: UnitProps {
+            scale: spec.scale,
+            offset: spec.offset,
+            exponent: spec.exponent,
+            mode: match spec.mode.as_str() {
+                "Exponential" => CalcMode::Exponential,
+                "Logarithmic" => CalcMode::Logarithmic,
+                _ => CalcMode::Linear,
+            },
+            basis: LayoutBasis::from_variant(&spec.basis)
+                .ok_or_else(|| UnitRegistryError::InvalidValue(spec.basis.clone()))?,
+            formula,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declare_and_resolve() {
+        let mut registry = UnitRegistry::new();
+        registry
+            .declare("furlong", "Length", None, 201.168, "m")
+            .unwrap();
+        registry
+            .declare("tile", "Length", Some("SI"), 16.0, "px")
+            .unwrap();
+
+        let (factor, tile) = registry.resolve("ktile").unwrap();
+        assert_eq!(factor, 1e3);
+        assert_eq!(tile.normalize(1.0), 16.0);
+
+        let furlong = registry.find("furlong").unwrap();
+        let mile = registry.find("mi").unwrap();
+        assert!((furlong.convert_to(8.0, mile).unwrap() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn declaration_errors() {
+        let mut registry = UnitRegistry::new();
+
+        assert_eq!(
+            registry.declare("m", "Length", None, 1.0, "m").unwrap_err(),
+            UnitRegistryError::DuplicateSymbol("m".into())
+        );
+        // "km" уже распознается как приставка + метр
+        assert!(matches!(
+            registry.declare("km", "Length", None, 1.0, "m"),
+            Err(UnitRegistryError::DuplicateSymbol(_))
+        ));
+        assert!(matches!(
+            registry.declare("tick", "Time", None, 1.0, "px"),
+            Err(UnitRegistryError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            registry.declare("warm", "Temperature", None, 2.0, "°C"),
+            Err(UnitRegistryError::AffineBase(_))
+        ));
+        assert!(matches!(
+            registry.declare("tile", "Lenght", None, 1.0, "px"),
+            Err(UnitRegistryError::UnknownDimension(_))
+        ));
+    }
+
+    #[test]
+    fn load_definitions_file() {
+        let mut registry = UnitRegistry::new();
+        registry
+            .load_definitions(
+                "# игровые единицы\n\
+                 tile    Length  SI    scale=16\n\
+                 tick    Time    None  scale=1/20\n",
+            )
+            .unwrap();
+
+        assert_eq!(registry.find("tick").unwrap().normalize(20.0), 1.0);
+        assert_eq!(registry.resolve("Mtile").unwrap().0, 1e6);
+
+        // "in" с приставками SI дает "min", а это уже минута
+        let err = UnitRegistry::new()
+            .load_definitions("in2 Length None\nin Length SI\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+
+        assert!(matches!(
+            UnitRegistry::new().load_definitions("[prefixes]\nx 2 SI\n"),
+            Err(UnitRegistryError::Definition(_))
+        ));
+    }
+
+    #[test]
+    fn ambiguous_symbols_warn() {
+        use crate::lexer::error::LexicalWarningType;
+        use crate::lexer::scanner::Scanner;
+
+        let registry = UnitRegistry::new();
+        assert_eq!(registry.ambiguity("gal").unwrap(), ["gal_us", "gal_uk"]);
+        assert_eq!(registry.ambiguity("gal_us"), None);
+
+        let mut scanner = Scanner::new("a = 5gal\nb = 2gal_uk + 1ton\nc = 30lbf/gal\n".into());
+        scanner.scan_tokens();
+        let warned: Vec<&LexicalWarningType> =
+            scanner.warnings.iter().map(|w| &w.warning_type).collect();
+        assert_eq!(
+            warned,
+            [
+                &LexicalWarningType::AmbiguousUnit("gal".into()),
+                &LexicalWarningType::AmbiguousUnit("ton".into()),
+                &LexicalWarningType::AmbiguousUnit("gal".into()),
+            ]
+        );
+        assert!(scanner.warnings[0].message.contains("gal_us or gal_uk"));
+    }
+}
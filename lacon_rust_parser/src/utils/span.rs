@@ -0,0 +1,48 @@
+use crate::lexer::position::Position;
+use crate::lexer::token::Token;
+use std::fmt;
+
+/// Участок исходного кода: от начала первого токена до конца последнего.
+/// Используется узлами AST и диагностиками семантического анализа.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// Пустой участок в точке (н-р, пропущенный элемент массива)
+    pub fn point(position: Position) -> Self {
+        Self::new(position, position)
+    }
+
+    /// Участок, занятый лексемой токена
+    pub fn of_token(token: &Token) -> Self {
+        let end = Position {
+            line: token.position.line,
+            column: token.position.column + token.lexeme.chars().count(),
+            offset: token.position.offset + token.length as usize,
+        };
+        Self::new(token.position, end)
+    }
+
+    /// Участок от начала `self` до конца `other`
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.start <= position && position < self.end
+    }
+}
+
+/// Форматированный вывод: начало участка, "3:7"
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.start)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::lexer::token_type::TokenType;
 
+#[allow(clippy::manual_strip)]
 pub fn get_unit_type(suffix: &str) -> Option<TokenType> {
     // 1. СНАЧАЛА проверяем температуру и градусы угла
     // (Потому что у них специфические префиксы deg и °)
@@ -104,6 +105,7 @@ fn match_other_exact_units(suffix: &str) -> Option<TokenType> {
     }
 }
 
+#[allow(clippy::manual_strip)]
 fn match_temperature_and_degree(suffix: &str) -> Option<TokenType> {
     const DEG_SIGN: &str = "\u{00B0}";
     if suffix == "K" {